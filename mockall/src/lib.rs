@@ -32,6 +32,7 @@
 //! * [`Call counts`](#call-counts)
 //! * [`Sequences`](#sequences)
 //! * [`Checkpoints`](#checkpoints)
+//! * [`Scenarios`](#scenarios)
 //! * [`Reference arguments`](#reference-arguments)
 //! * [`Reference return values`](#reference-return-values)
 //! * [`impl Trait`](#impl-trait)
@@ -41,10 +42,13 @@
 //! * [`Associated types`](#associated-types-1)
 //! * [`Multiple and inherited traits`](#multiple-and-inherited-traits)
 //! * [`External traits`](#external-traits)
+//! * [`Receivers`](#receivers)
 //! * [`Static methods`](#static-methods)
 //! * [`Modules`](#modules)
 //! * [`Foreign functions`](#foreign-functions)
+//! * [`Function pointers`](#function-pointers)
 //! * [`Debug`](#debug)
+//! * [`Errors`](#errors)
 //! * [`Async Traits`](#async-traits)
 //! * [`Crate features`](#crate-features)
 //! * [`Examples`](#examples)
@@ -72,10 +76,10 @@
 //!
 //! ## Static Return values
 //!
-//! Every expectation must have an associated return value (though when the
-//! **nightly** feature is enabled expectations will automatically return the
-//! default values of their return types, if their return types implement
-//! `Default`.).  For methods that return a `static` value, the macros will
+//! Every expectation for a method that returns something other than `()` must
+//! have an associated return value (though expectations will automatically
+//! return the default values of their return types, if their return types
+//! implement `Default`.).  For methods that return a `static` value, the macros will
 //! generate an `Expectation` struct like
 //! [`this`](examples::__mock_MockFoo_Foo::__foo::Expectation).
 //! There are two ways to set such an expectation's return value: with a
@@ -83,7 +87,8 @@
 //! ([`return_const`](examples::__mock_MockFoo_Foo::__foo::Expectation::return_const))
 //! or a closure
 //! ([`returning`](examples::__mock_MockFoo_Foo::__foo::Expectation::returning)).
-//! A closure will take the method's arguments by value.
+//! A closure will take the method's arguments by value.  It's an `FnMut`, so
+//! it may be called any number of times and may keep state between calls.
 //!
 //! ```
 //! # use mockall::*;
@@ -146,17 +151,78 @@
 //! # }
 //! ```
 //!
-//! Mock objects are always `Send`.  If you need to use a return type that
-//! isn't, you can use the
+//! If the return type implements `Default`, then
+//! [`return_default`](examples::__mock_MockFoo_Foo::__foo::Expectation::return_default)
+//! is a shortcut for returning the default value.  In fact, an expectation
+//! with no return value at all will return the default, so simple interaction
+//! tests can set only a call count or an argument matcher.  That doesn't work
+//! for generic return types that aren't bounded by `Default`, unless the
+//! **nightly** feature is enabled.
+//!
+//! ```
+//! # use mockall::*;
+//! # use mockall::predicate::*;
+//! #[automock]
+//! trait MyTrait {
+//!     fn foo(&self) -> Vec<u32>;
+//!     fn bar(&self, x: u32);
+//!     fn baz(&self, x: u32) -> Option<u32>;
+//! }
+//!
+//! let mut mock = MockMyTrait::new();
+//! mock.expect_foo()
+//!     .return_default();
+//! mock.expect_bar()
+//!     .times(1);
+//! mock.expect_baz()
+//!     .with(eq(5))
+//!     .times(1);
+//! assert!(mock.foo().is_empty());
+//! mock.bar(5);
+//! assert_eq!(None, mock.baz(5));
+//! ```
+//!
+//! Forgetting to set a return value is a runtime error.  If you would rather
+//! catch that mistake at compile time, use the `expect_*_pending` methods
+//! instead.  They return a
+//! [`PendingExpectation`](examples::__mock_MockFoo_Foo::__foo::PendingExpectation),
+//! which can be configured just like an `Expectation`.  But it won't take
+//! effect until a return value is supplied, and the type system won't let
+//! you access the finished `Expectation` until then.  These methods are
+//! available for methods that are neither static nor generic and that return
+//! `'static` values.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock]
+//! trait MyTrait {
+//!     fn foo(&self, x: u32) -> u32;
+//! }
+//!
+//! let mut mock = MockMyTrait::new();
+//! mock.expect_foo_pending()
+//!     .times(1)
+//!     .returning(|x| x + 1);
+//! assert_eq!(5, mock.foo(4));
+//! ```
+//!
+//! Mock objects are always `Send` and `Sync`, unless a method returns a
+//! non-`'static` reference to a type that isn't.  If you need to use a return
+//! type that isn't `Send`, you can use the
 //! [`return_const_st`](examples::__mock_MockFoo_Foo::__foo::Expectation::return_const_st),
 //! [`returning_st`](examples::__mock_MockFoo_Foo::__foo::Expectation::returning_st),
 //! or
 //! [`return_once_st`](examples::__mock_MockFoo_Foo::__foo::Expectation::return_once_st),
 //! methods. If you need to match arguments that are not `Send`, you can use the
+//! [`with_st`](examples::__mock_MockFoo_Foo::__foo::Expectation::with_st)
+//! or
 //! [`withf_st`](examples::__mock_MockFoo_Foo::__foo::Expectation::withf_st)
+//! methods.
 //! These take a non-`Send` object and add runtime access checks.  The wrapped
 //! object will be `Send`, but accessing it from multiple threads will cause a
-//! runtime panic.
+//! runtime panic.  That includes dropping it, so a mock object with such
+//! expectations should be dropped on the thread that created them.  Raw
+//! pointers are a common example of such types.
 //!
 //! ```
 //! # use mockall::*;
@@ -200,7 +266,24 @@
 //! mock.foo(0);    // Panics!
 //! ```
 //!
+//! `with` takes one predicate per argument.  When no expectation matches a
+//! call, the panic message shows the actual arguments and explains why each
+//! expectation didn't match.  It names the arguments that each expectation's
+//! predicates rejected, like
+//! ``Expectation(var == 42) rejected argument `x` ``.
+//!
 //! See [`predicate`] for a list of Mockall's builtin predicate functions.
+//! They can be composed with [`predicate::and`], [`predicate::or`], and
+//! [`predicate::not`], like `with(and(gt(10), not(eq(42))))`.
+//!
+//! Mockall's [`Predicate`] is the very same trait as the
+//! [`predicates`](https://docs.rs/predicates) crate's, so `with` accepts
+//! anything from that crate, like `predicates::path::is_file()` or
+//! `predicates::str::contains("foo").trim()`, as well as any predicate
+//! defined by the user's own crate.  To write one, implement `Predicate`,
+//! `predicates::reflection::PredicateReflection`, and `Display`.  The latter
+//! describes the predicate in failure messages.  Or, more simply, implement
+//! [`ArgMatcher`] and wrap it with [`predicate::matcher`].
 //! For convenience,
 //! [`withf`](examples::__mock_MockFoo_Foo::__foo::Expectation::withf)
 //! is a shorthand for setting the commonly used
@@ -224,6 +307,57 @@
 //! # }
 //! ```
 //!
+//! To check just one argument with a closure, use [`predicate::func`] instead,
+//! like `with(func(|x: &u32| x % 2 == 0), eq(5))`.
+//!
+//! ### Capturing arguments
+//!
+//! Complicated assertions can be awkward to write as matchers.  Instead, an
+//! expectation's `capture` method returns a [`Captured`] handle, which records
+//! the arguments of every call that the expectation handles.  The test can
+//! examine them after the code under test has run.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock]
+//! trait Foo {
+//!     fn foo(&self, key: &str, value: u32);
+//! }
+//!
+//! let mut mock = MockFoo::new();
+//! let calls = mock.expect_foo()
+//!     .return_const(())
+//!     .capture();
+//! mock.foo("height", 5);
+//! mock.foo("width", 3);
+//! let calls = calls.take();
+//! assert_eq!(2, calls.len());
+//! assert!(calls.iter().all(|(key, value)| !key.is_empty() && *value > 0));
+//! ```
+//!
+//! For side effects that must happen during the call, like notifying another
+//! thread, use `inspect` instead.  Its closure receives the arguments by
+//! reference on every call that the expectation handles, without changing the
+//! return value.
+//!
+//! ```
+//! # use mockall::*;
+//! # use std::sync::{Mutex, mpsc};
+//! #[automock]
+//! trait Foo {
+//!     fn foo(&self, x: u32) -> u32;
+//! }
+//!
+//! let (tx, rx) = mpsc::channel();
+//! let tx = Mutex::new(tx);
+//! let mut mock = MockFoo::new();
+//! mock.expect_foo()
+//!     .inspect(move |x| tx.lock().unwrap().send(*x).unwrap())
+//!     .return_const(0u32);
+//! std::thread::spawn(move || mock.foo(5));
+//! assert_eq!(5, rx.recv().unwrap());
+//! ```
+//!
 //! ### Matching multiple calls
 //!
 //! Matchers can also be used to discriminate between different invocations of
@@ -272,6 +406,198 @@
 //!     .return_const(None);
 //! ```
 //!
+//! Note that the order is FIFO, not LIFO: adding a catch-all expectation
+//! *after* a specific one doesn't override it, and adding a specific
+//! expectation after a catch-all has no effect.  An expectation that has
+//! already been called as many times as its [`times`](#call-counts) allows is
+//! skipped, so later expectations can take over for subsequent calls.  To let
+//! a later expectation override an earlier one, raise its `priority` instead,
+//! or lower the earlier one's below the default of 0.  Expectations with higher
+//! priority are checked first.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock]
+//! trait Foo {
+//!     fn foo(&self, x: u32) -> u32;
+//! }
+//!
+//! let mut mock = MockFoo::new();
+//! mock.expect_foo()
+//!     .times(1)
+//!     .return_const(1u32);
+//! mock.expect_foo()
+//!     .return_const(2u32);
+//! assert_eq!(1, mock.foo(0));
+//! assert_eq!(2, mock.foo(0));
+//! assert_eq!(2, mock.foo(0));
+//! ```
+//!
+//! The usual way to add a catch-all expectation is with `by_default`.  Such
+//! an expectation has the lowest possible priority, and no limit on how many
+//! times it may be called.  So it handles every call that no other
+//! expectation claims, no matter the order in which they were added, and it
+//! doesn't get in the way of verifying the stricter expectations.
+//!
+//! ```
+//! # use mockall::*;
+//! # use mockall::predicate::*;
+//! #[automock]
+//! trait Foo {
+//!     fn foo(&self, x: u32) -> u32;
+//! }
+//!
+//! let mut mock = MockFoo::new();
+//! mock.expect_foo()
+//!     .by_default()
+//!     .return_const(0u32);
+//! mock.expect_foo()
+//!     .with(eq(4))
+//!     .times(1)
+//!     .return_const(42u32);
+//! assert_eq!(0, mock.foo(1));
+//! assert_eq!(42, mock.foo(4));
+//! assert_eq!(0, mock.foo(4));
+//! ```
+//!
+//! A long setup can also be written as a single statement, with the mock
+//! struct's builder.  For every `expect_*` method on the mock, the builder has
+//! a method of the same name, which takes a closure to configure the new
+//! expectation.  Static methods are the exception: they still need a
+//! [context](#static-methods).  A mock struct that has a method of its own
+//! named `builder` gets no builder.
+//!
+//! ```
+//! # use mockall::*;
+//! # use mockall::predicate::*;
+//! #[automock]
+//! trait Foo {
+//!     fn foo(&self, x: u32) -> u32;
+//!     fn bar(&self);
+//! }
+//!
+//! let mock = MockFoo::builder()
+//!     .expect_foo(|e| e.with(eq(1)).return_const(2u32))
+//!     .expect_foo(|e| e.return_const(0u32))
+//!     .expect_bar(|e| e.times(1).return_const(()))
+//!     .build();
+//! assert_eq!(2, mock.foo(1));
+//! assert_eq!(0, mock.foo(3));
+//! mock.bar();
+//! ```
+//!
+//! ### Nice mocks
+//!
+//! Setting such fallbacks for every method of a wide trait gets tedious.  A
+//! mock object created with `new_nice` instead of `new` is "nice": when none of
+//! a method's expectations match, it returns the default value of the
+//! method's return type instead of panicking.  So a test need only set
+//! expectations for the methods that it cares about.  Nice mocks still panic
+//! for static methods, methods that return references, and methods whose
+//! return types don't implement `Default`.
+//!
+//! ```
+//! # use mockall::*;
+//! # use mockall::predicate::*;
+//! #[automock]
+//! trait Foo {
+//!     fn open(&self, path: String) -> Option<u32>;
+//!     fn size(&self, fd: u32) -> u64;
+//!     fn close(&self, fd: u32);
+//! }
+//!
+//! let mut mock = MockFoo::new_nice();
+//! mock.expect_open()
+//!     .with(eq(String::from("something.txt")))
+//!     .returning(|_| Some(5));
+//! assert_eq!(Some(5), mock.open(String::from("something.txt")));
+//! assert_eq!(None, mock.open(String::from("other.txt")));
+//! assert_eq!(0, mock.size(5));
+//! mock.close(5);
+//! ```
+//!
+//! If no test ever sets expectations for some method, it can be marked with
+//! [`#[mockall::stub]`](attr.stub.html) instead.  Then it always returns its
+//! default value, or a fixed expression, without the cost of expectations.
+//!
+//! When debugging, it can help to see every unexpected call at once, rather
+//! than only the first.  A mock object created with `new_recording` behaves
+//! like a nice mock, but it also records each call that no expectation
+//! matched.  They will all be reported when the mock object is checkpointed
+//! or dropped.  Or, `take_unexpected` can be used to examine them first.
+//! Only calls that a nice mock could handle are recorded; the rest still panic
+//! immediately.
+//!
+//! ```should_panic(expected = "2 unexpected call(s)")
+//! # use mockall::*;
+//! #[automock]
+//! trait Foo {
+//!     fn foo(&self, x: u32) -> u32;
+//! }
+//!
+//! let mut mock = MockFoo::new_recording();
+//! mock.foo(1);
+//! mock.foo(2);
+//! mock.checkpoint();  // Panics, listing both calls
+//! ```
+//!
+//! ### Spies
+//!
+//! Sometimes a test only needs to intercept a few calls, and the real
+//! implementation would do fine for the rest.  For a mock struct that
+//! implements traits, `spy` creates a mock object that wraps a real object of
+//! the same traits.  Any call that none of a method's expectations match is
+//! forwarded to the real object.  Only methods that take `&self` or `&mut
+//! self`, aren't generic, and return owned values can be forwarded; the
+//! others behave as usual.
+//!
+//! ```
+//! # use mockall::*;
+//! # use mockall::predicate::*;
+//! #[automock]
+//! trait Foo {
+//!     fn foo(&self, x: u32) -> u32;
+//! }
+//!
+//! struct Real;
+//! impl Foo for Real {
+//!     fn foo(&self, x: u32) -> u32 {
+//!         x + 1
+//!     }
+//! }
+//!
+//! let mut mock = MockFoo::spy(Real);
+//! mock.expect_foo()
+//!     .with(eq(0))
+//!     .return_const(42u32);
+//! assert_eq!(42, mock.foo(0));
+//! assert_eq!(6, mock.foo(5));
+//! ```
+//!
+//! Calls forwarded that way aren't counted.  To verify how many times the real
+//! object gets called, set an expectation with `forward`.  Its matching calls
+//! are forwarded, too, but they are counted like any other expectation's.
+//!
+//! ```
+//! # use mockall::*;
+//! # #[automock]
+//! # trait Foo {
+//! #     fn foo(&self, x: u32) -> u32;
+//! # }
+//! # struct Real;
+//! # impl Foo for Real {
+//! #     fn foo(&self, x: u32) -> u32 {
+//! #         x + 1
+//! #     }
+//! # }
+//! let mut mock = MockFoo::spy(Real);
+//! mock.expect_foo()
+//!     .times(2)
+//!     .forward();
+//! assert_eq!(1, mock.foo(0));
+//! assert_eq!(6, mock.foo(5));
+//! ```
+//!
 //! ## Call counts
 //!
 //! By default, every expectation is allowed to be called an unlimited number of
@@ -295,7 +621,37 @@
 //! mock.foo(1);    // Panics!
 //! ```
 //!
+//! The argument to `times` may be any of Rust's range expressions over `usize`,
+//! as well as a plain number.  So `.times(3)`, `.times(1..5)`, `.times(2..)`,
+//! `.times(..=4)` and `.times(..)` are all valid, and `.times(0)` is the same as
+//! `.never()`.  Exceeding the maximum causes an immediate panic, while falling
+//! short of the minimum is detected when the mock object is dropped or
+//! checkpointed.  So with `.times(1..=5)`, the sixth call will panic.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock]
+//! trait Foo {
+//!     fn foo(&self, x: u32);
+//! }
+//!
+//! let mut mock = MockFoo::new();
+//! mock.expect_foo()
+//!     .times(2..)
+//!     .return_const(());
+//!
+//! mock.foo(0);
+//! mock.foo(1);
+//! mock.foo(2);
+//! ```
+//!
+//! `.at_least(n)` and `.at_most(n)` set just one end of the range, leaving the
+//! other unchanged.  So `.at_least(2).at_most(5)` is the same as
+//! `.times(2..=5)`.
+//!
 //! See also
+//! [`at_least`](examples::__mock_MockFoo_Foo::__foo::Expectation::at_least),
+//! [`at_most`](examples::__mock_MockFoo_Foo::__foo::Expectation::at_most),
 //! [`never`](examples::__mock_MockFoo_Foo::__foo::Expectation::never) and
 //! [`times`](examples::__mock_MockFoo_Foo::__foo::Expectation::times).
 //!
@@ -303,7 +659,9 @@
 //!
 //! By default expectations may be matched in any order.  But it's possible to
 //! specify the order by using a [`Sequence`].  Any expectations may be added to
-//! the same sequence.  They don't even need to come from the same object.
+//! the same sequence.  They don't even need to come from the same object, or
+//! from objects of the same type.  The `Sequence` itself keeps track of which
+//! expectation is next, so it can also order calls to static methods.
 //!
 //! ```should_panic(expected = "Method sequence violation")
 //! # use mockall::*;
@@ -331,6 +689,39 @@
 //! # }
 //! ```
 //!
+//! An expectation may belong to more than one sequence, by calling
+//! `in_sequence` once for each.  Every call is checked against all of them.
+//! That's useful for partial orderings, where two chains of calls share one
+//! step but are otherwise independent.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock]
+//! trait Device {
+//!     fn init(&self);
+//!     fn read(&self);
+//!     fn write(&self);
+//! }
+//!
+//! # fn main() {
+//! let mut seq_r = Sequence::new();
+//! let mut seq_w = Sequence::new();
+//! let mut mock = MockDevice::new();
+//! // read and write must both come after init, but in either order.
+//! mock.expect_init()
+//!     .times(1)
+//!     .in_sequence(&mut seq_r)
+//!     .in_sequence(&mut seq_w)
+//!     .return_const(());
+//! mock.expect_read().times(1).in_sequence(&mut seq_r).return_const(());
+//! mock.expect_write().times(1).in_sequence(&mut seq_w).return_const(());
+//!
+//! mock.init();
+//! mock.write();
+//! mock.read();
+//! # }
+//! ```
+//!
 //! ## Checkpoints
 //!
 //! Sometimes its useful to validate all expectations mid-test, throw them away,
@@ -338,7 +729,10 @@
 //! `checkpoint` method.  When called, it will immediately validate all methods'
 //! expectations.  So any expectations that haven't satisfied their call count
 //! will panic.  Afterwards, those expectations will be cleared so you can add
-//! new expectations and keep testing.
+//! new expectations and keep testing.  Dropping a mock object verifies it the
+//! same way.  Either way, if several expectations are unsatisfied, or a
+//! recording mock saw unexpected calls, a single panic message lists every
+//! problem.
 //!
 //! ```should_panic
 //! # use mockall::*;
@@ -375,6 +769,107 @@
 //! # }
 //! ```
 //!
+//! To check the call counts without panicking, use `try_verify` instead.  It
+//! returns an [`ExpectationError`] for every expectation that hasn't yet been
+//! called enough times, and leaves all of the expectations in place.  That
+//! suits test frameworks that report failures as errors, and tests that verify
+//! from inside an async task.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock]
+//! trait Foo {
+//!     fn foo(&self);
+//! }
+//!
+//! # fn main() {
+//! let mut mock = MockFoo::new();
+//! mock.expect_foo()
+//!     .times(1)
+//!     .return_const(());
+//! assert!(mock.try_verify().is_err());
+//! mock.foo();
+//! assert!(mock.try_verify().is_ok());
+//! # }
+//! ```
+//!
+//! Custom test harnesses may also replace Mockall's panics altogether with
+//! their own failure reporting, by installing a handler with
+//! [`set_failure_handler`].
+//!
+//! A mock object that is dropped while its thread is already panicking can't
+//! panic again without aborting the process.  Instead, it clears its
+//! expectations and records any failures, which
+//! [`take_unwinding_failures`] returns.  So mocks are safe to use inside
+//! property tests, which catch panics in order to shrink the failing input.
+//!
+//! ## Scenarios
+//!
+//! A test that uses several mock objects may want to verify all of them in one
+//! place.  A [`Scenario`] collects expectations from any number of mocks.
+//! Expectations added to it with `in_scenario` aren't verified when their mock
+//! is dropped or checkpointed.  Instead, [`Scenario::verify`] checks them all
+//! at once and reports every unsatisfied expectation in a single panic
+//! message.  The scenario also provides a [`Sequence`] for ordering calls
+//! across all of its mocks.
+//!
+//! ```should_panic(expected = "Scenario has 2 unsatisfied expectation(s)")
+//! # use mockall::*;
+//! #[automock]
+//! trait Foo {
+//!     fn foo(&self);
+//! }
+//!
+//! let mut scenario = Scenario::new();
+//! let mut mock1 = MockFoo::new();
+//! mock1.expect_foo()
+//!     .times(1)
+//!     .in_scenario(&mut scenario)
+//!     .return_const(());
+//!
+//! let mut mock2 = MockFoo::new();
+//! mock2.expect_foo()
+//!     .times(1)
+//!     .in_scenario(&mut scenario)
+//!     .return_const(());
+//!
+//! drop(mock1);
+//! drop(mock2);
+//! scenario.verify();  // Panics!  Reports both expectations.
+//! ```
+//!
+//! A mock object's own `in_scenario` method adds all of its current
+//! expectations to a scenario at once.  That's handy when the code under test
+//! consumes the mock with a method that takes `self` by value.  The scenario
+//! outlives the mock, so the test can still verify the mock's other
+//! expectations afterwards.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock]
+//! trait Writer {
+//!     fn write(&mut self, buf: &[u8]);
+//!     fn into_inner(self) -> Vec<u8>;
+//! }
+//!
+//! fn finish(mut w: impl Writer) -> Vec<u8> {
+//!     w.write(b"done");
+//!     w.into_inner()
+//! }
+//!
+//! let mut scenario = Scenario::new();
+//! let mut mock = MockWriter::new();
+//! mock.expect_write()
+//!     .times(1)
+//!     .return_const(());
+//! mock.expect_into_inner()
+//!     .returning(Vec::new);
+//! mock.in_scenario(&mut scenario);
+//!
+//! finish(mock);
+//! scenario.verify();
+//! ```
+//!
 //! ## Reference arguments
 //!
 //! Mockall can mock methods with reference arguments, too.  There's one catch:
@@ -397,11 +892,56 @@
 //! assert_eq!(6, mock.foo(&5));
 //! ```
 //!
+//! So a slice argument like `&[u8]` needs a `Predicate<[u8]>`.  The
+//! [`predicate::slice`] module provides those, matching on the slice's
+//! contents, length, or elements.
+//!
+//! ```
+//! # use mockall::*;
+//! use mockall::predicate::slice;
+//! #[automock]
+//! trait Foo {
+//!     fn write(&mut self, buf: &[u8]);
+//! }
+//!
+//! let mut mock = MockFoo::new();
+//! mock.expect_write()
+//!     .with(slice::eq(b"abc"))
+//!     .return_const(());
+//!
+//! mock.write(b"abc");
+//! ```
+//!
+//! Likewise, a `&str` argument needs a `Predicate<str>`.  The easiest one is
+//! [`predicate::eq_str`], which also works for `String` arguments.
+//!
+//! Arguments may also be references to trait objects, like `&dyn Error` or
+//! `&dyn Fn(u32) -> u32`.  They're never stored by the expectation, so they
+//! needn't be `'static`.  However, it's hard to build a [`Predicate`] for a
+//! trait object, so they are best matched with `withf`.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock]
+//! trait Foo {
+//!     fn foo(&self, f: &dyn Fn(u32) -> u32) -> u32;
+//! }
+//!
+//! let mut mock = MockFoo::new();
+//! mock.expect_foo()
+//!     .withf(|f| f(1) == 2)
+//!     .returning(|f| f(5));
+//!
+//! assert_eq!(6, mock.foo(&|x| x + 1));
+//! ```
+//!
 //! ## Reference return values
 //!
 //! Mockall can also use reference return values.  There is one restriction: the
-//! lifetime of the returned reference must be either the same as the lifetime
-//! of the mock object, or `'static`.
+//! lifetime of the returned reference must be the same as the lifetime of the
+//! mock object, the same as the lifetime of one of the arguments, or
+//! `'static`.  The mock object's lifetime may be elided or named, as in
+//! `fn get<'a>(&'a self) -> &'a Thing`.
 //!
 //! Mockall creates different expectation types for methods that return
 //! references.  Their API is the same as the basic `Expectation`, except for
@@ -482,7 +1022,31 @@
 //! # }
 //! ```
 //!
-//! Unsized types that are common targets for
+//! To check how the caller mutated the returned value, use
+//! [`inspect_var`](examples::__mock_MockFoo_Foo::__baz::Expectation::inspect_var).
+//! Its closure receives the value when the expectation is dropped, either by
+//! [`checkpoint`](#checkpoints) or by dropping the mock.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock]
+//! trait Container {
+//!     fn buf(&mut self) -> &mut Vec<u32>;
+//! }
+//!
+//! # fn main() {
+//! let mut mock = MockContainer::new();
+//! mock.expect_buf()
+//!     .return_var(Vec::new())
+//!     .inspect_var(|v| assert_eq!(vec![1, 2], *v));
+//!
+//! mock.buf().push(1);
+//! mock.buf().push(2);
+//! mock.checkpoint();
+//! # }
+//! ```
+//!
+//! Unsized types that are common targets for
 //! [`Deref`](core::ops::Deref)
 //! are special.  Mockall
 //! will automatically use the type's owned form for the Expectation.
@@ -525,6 +1089,31 @@
 //! # }
 //! ```
 //!
+//! Methods that return a reference borrowed from one of their arguments use
+//! the ordinary `Expectation` type.  The
+//! [`returning`](examples::__mock_MockFoo_Foo::__foo::Expectation::returning)
+//! closure receives the argument, so it can return a borrow of it.  Such
+//! methods may even be static.
+//!
+//! ```
+//! # use mockall::*;
+//! # use std::collections::HashMap;
+//! #[automock]
+//! trait Cache {
+//!     fn lookup<'a>(&self, map: &'a HashMap<u32, String>, k: u32) -> &'a str;
+//! }
+//!
+//! # fn main() {
+//! let mut mock = MockCache::new();
+//! mock.expect_lookup()
+//!     .returning(|map, k| &map[&k]);
+//!
+//! let mut map = HashMap::new();
+//! map.insert(1, String::from("one"));
+//! assert_eq!("one", mock.lookup(&map, 1));
+//! # }
+//! ```
+//!
 //!
 //! ## Impl Trait
 //!
@@ -557,6 +1146,33 @@
 //! # }
 //! ```
 //!
+//! For any method whose Expectation returns `Box<dyn Trait>`, whether it was
+//! written that way or transformed from `impl Trait`, the Expectation also has
+//! `return_boxed` and `returning_boxed` methods that do the boxing for you.
+//! `return_boxed` moves its value into the return, so it's good for only one
+//! call.
+//!
+//! ```
+//! # use mockall::*;
+//! # use std::fmt::Debug;
+//! # struct Foo {}
+//! # #[automock]
+//! # impl Foo {
+//! #     fn foo(&self) -> impl Debug { 4 }
+//! # }
+//! # fn main() {
+//! let mut mock = MockFoo::new();
+//! mock.expect_foo()
+//!     .times(1)
+//!     .return_boxed(String::from("Hello, World!"));
+//! println!("{:?}", mock.foo());
+//! # }
+//! ```
+//!
+//! The same goes for trait methods that return `impl Trait`, which Rust allows
+//! since 1.75.0.  Code that's generic over the trait only sees the `impl
+//! Trait` surface, so it can't tell that the mock returns a `Box`.
+//!
 //! However, `impl Trait` isn't *exactly* equivalent to `Box<dyn Trait>` but
 //! with fewer allocations.  There are some things the former can do but the
 //! latter can't.  For one thing, you can't build a trait object out of a
@@ -623,6 +1239,34 @@
 //! # }
 //! ```
 //!
+//! For any method whose Expectation returns `Pin<Box<dyn Future<Output=T>>>`,
+//! whether it was written that way or transformed from `impl Future`, the
+//! Expectation also has `returning_future` and `returning_async` methods that
+//! do the pinning for you.  `returning_future` returns a future that resolves
+//! to a clone of its argument, and `returning_async` takes a closure that
+//! returns any future, usually an `async move` block.
+//!
+//! ```
+//! # use mockall::*;
+//! # use futures::executor::block_on;
+//! # use std::{future::Future, pin::Pin};
+//! #[automock]
+//! trait Foo {
+//!     fn foo(&self, x: i32) -> Pin<Box<dyn Future<Output=i32> + Send>>;
+//! }
+//!
+//! # fn main() {
+//! let mut mock = MockFoo::new();
+//! mock.expect_foo()
+//!     .times(1)
+//!     .returning_future(42);
+//! mock.expect_foo()
+//!     .returning_async(|x| async move { x + 1 });
+//! assert_eq!(42, block_on(mock.foo(0)));
+//! assert_eq!(5, block_on(mock.foo(4)));
+//! # }
+//! ```
+//!
 //! ## Mocking structs
 //!
 //! Mockall mocks structs as well as traits.  The problem here is a namespace
@@ -667,6 +1311,10 @@
 //! }
 //! # fn main() {}
 //! ```
+//! Every method in the `impl` block gets mocked, including private helpers,
+//! and each mock method keeps the visibility of the original.  Associated
+//! constants are copied to the mock struct verbatim.
+//!
 //! For structs with more than one `impl` block or that have unsupported
 //! `#[derive(X)]` attributes, e.g. `Clone`, see [`mock!`] instead.
 //!
@@ -699,6 +1347,45 @@
 //! assert_eq!(-5, mock.foo(5i8));
 //! ```
 //!
+//! `impl Trait` in argument position works the same way.  Each such argument
+//! becomes an additional generic parameter of the expect_* method, after any
+//! named ones.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock]
+//! trait Foo {
+//!     fn log<T: ToString + 'static>(&self, msg: impl Into<String> + 'static,
+//!                                   ctx: T);
+//! }
+//!
+//! let mut mock = MockFoo::new();
+//! mock.expect_log::<u32, &'static str>()
+//!     .return_const(());
+//!
+//! mock.log("hello", 42u32);
+//! ```
+//!
+//! Const generic parameters are treated the same way: each value gets its own
+//! expectations.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock]
+//! trait Reader {
+//!     fn read<const N: usize>(&self) -> [u8; N];
+//! }
+//!
+//! let mut mock = MockReader::new();
+//! mock.expect_read::<2>()
+//!     .return_const([1, 2]);
+//! mock.expect_read::<3>()
+//!     .return_const([3, 4, 5]);
+//!
+//! assert_eq!([1, 2], mock.read::<2>());
+//! assert_eq!([3, 4, 5], mock.read::<3>());
+//! ```
+//!
 //! ### With non-`static` type parameters
 //!
 //! Mocking methods with non-`'static` type parameters is harder.  The way
@@ -741,8 +1428,7 @@
 //!
 //! Mocking generic structs and generic traits is not a problem.  The mock
 //! struct will be generic, too.  The same restrictions apply as with mocking
-//! generic methods: each generic parameter must be `'static`, and generic
-//! lifetime parameters are not allowed.
+//! generic methods: each generic type parameter must be `'static`.
 //!
 //! ```
 //! # use mockall::*;
@@ -759,6 +1445,51 @@
 //! # }
 //! ```
 //!
+//! Const generic parameters work too.  Each value of the parameter gets its
+//! own set of expectations for static methods, just like each type does.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock]
+//! trait Block<const N: usize> {
+//!     fn read(&self) -> [u8; N];
+//! }
+//!
+//! # fn main() {
+//! let mut mock = MockBlock::<2>::new();
+//! mock.expect_read()
+//!     .return_const([4, 2]);
+//! assert_eq!([4, 2], mock.read());
+//! # }
+//! ```
+//!
+//! Traits that are generic only over lifetimes, like visitors, can be mocked
+//! as well.  The mock struct will have the same lifetime parameters, but its
+//! expectations won't; they must accept arguments of any lifetime, and return
+//! `'static` values.
+//!
+//! ```
+//! # use mockall::*;
+//! pub struct Node(u32);
+//!
+//! #[automock]
+//! trait Visitor<'ast> {
+//!     fn visit(&mut self, n: &'ast Node);
+//! }
+//!
+//! # fn main() {
+//! let nodes = [Node(1), Node(2)];
+//! let mut mock = MockVisitor::new();
+//! mock.expect_visit()
+//!     .withf(|n| n.0 > 0)
+//!     .times(2)
+//!     .return_const(());
+//! for n in nodes.iter() {
+//!     mock.visit(n);
+//! }
+//! # }
+//! ```
+//!
 //! ## Associated types
 //!
 //! Traits with associated types can be mocked too.  Unlike generic traits, the
@@ -781,11 +1512,21 @@
 //! assert_eq!(4, mock.foo(4));
 //! ```
 //!
+//! Every associated type must be given a metaitem, and every metaitem must
+//! name one of the trait's associated types.  The substitution applies
+//! everywhere in the method signatures, including generic bounds and where
+//! clauses.
+//!
 //! ## Multiple and inherited traits
 //!
 //! Creating a mock struct that implements multiple traits, whether inherited or
-//! not, requires using the [`mock!`] macro.  But once created,
-//! using it is just the same as using any other mock object:
+//! not, requires using the [`mock!`] macro.  In particular,
+//! [`#[automock]`](attr.automock.html) can't mock a trait's supertraits, because
+//! it only sees the definition of the trait it's attached to.  Instead, list
+//! an `impl` block for each supertrait in [`mock!`].  All of the traits share a
+//! single mock struct, so one `checkpoint` call verifies all of their
+//! expectations.  Once created, using it is just the same as using any other
+//! mock object:
 //!
 //! ```
 //! # use mockall::*;
@@ -818,6 +1559,104 @@
 //! # }
 //! ```
 //!
+//! If two of the traits have methods with the same name, or if one has a
+//! method with the same name as one of the struct's own methods, then the
+//! traits' expectation methods will be qualified with the trait's name.  For
+//! example, `expect_A_len` and `expect_B_len` instead of `expect_len`.  The
+//! same goes for the `*_context` methods of static methods.  If the traits are
+//! different instantiations of the same generic trait, then the names are
+//! qualified with the generic arguments too.  For example, mocking both
+//! `From<u32>` and `From<String>` produces `From_u32_from_context` and
+//! `From_String_from_context`.
+//!
+//! ```
+//! # use mockall::*;
+//! pub trait A {
+//!     fn len(&self) -> usize;
+//! }
+//!
+//! pub trait B {
+//!     fn len(&self) -> usize;
+//! }
+//!
+//! mock! {
+//!     C {}
+//!     impl A for C {
+//!         fn len(&self) -> usize;
+//!     }
+//!     impl B for C {
+//!         fn len(&self) -> usize;
+//!     }
+//! }
+//! # fn main() {
+//! let mut mock = MockC::new();
+//! mock.expect_A_len().return_const(1usize);
+//! mock.expect_B_len().return_const(2usize);
+//! assert_eq!(1, A::len(&mock));
+//! assert_eq!(2, B::len(&mock));
+//! # }
+//! ```
+//!
+//! ### Extension traits
+//!
+//! An extension trait provides extra methods for every implementor of some
+//! base trait, through a blanket `impl`.  Mockall can't set expectations on the
+//! extension methods themselves, because Rust doesn't allow the mock struct to
+//! implement the extension trait a second time.  But mocking the base trait is
+//! usually enough, because the extension methods call the base methods.  Set
+//! expectations on those instead.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock]
+//! pub trait Foo {
+//!     fn foo(&self) -> u32;
+//! }
+//!
+//! pub trait FooExt: Foo {
+//!     fn double_foo(&self) -> u32 {
+//!         2 * self.foo()
+//!     }
+//! }
+//!
+//! impl<T: Foo> FooExt for T {}
+//!
+//! let mut mock = MockFoo::new();
+//! mock.expect_foo().times(1).return_const(21u32);
+//! assert_eq!(42, mock.double_foo());
+//! ```
+//!
+//! If the extension trait is implemented separately for each type instead of
+//! through a blanket `impl`, then [`mock!`] can implement it for the mock
+//! struct, too, and its methods can have expectations of their own.
+//!
+//! ```
+//! # use mockall::*;
+//! pub trait Foo {
+//!     fn foo(&self) -> u32;
+//! }
+//!
+//! pub trait FooExt: Foo {
+//!     fn double_foo(&self) -> u32 {
+//!         2 * self.foo()
+//!     }
+//! }
+//!
+//! mock! {
+//!     Foo {}
+//!     impl Foo for Foo {
+//!         fn foo(&self) -> u32;
+//!     }
+//!     impl FooExt for Foo {
+//!         fn double_foo(&self) -> u32;
+//!     }
+//! }
+//!
+//! let mut mock = MockFoo::new();
+//! mock.expect_double_foo().times(1).return_const(4u32);
+//! assert_eq!(4, mock.double_foo());
+//! ```
+//!
 //! ## External traits
 //!
 //! Mockall can mock traits and structs defined in external crates that are
@@ -842,13 +1681,136 @@
 //! # }
 //! ```
 //!
+//! As with `clone` here, any method that returns `Self` will return the mock
+//! type.  That includes builder-style methods that take `self` by value.  Their
+//! expectations can return a fresh mock object that has been configured with
+//! expectations of its own.
+//!
+//! ### Operator traits
+//!
+//! The operator traits in `std::ops`, like `Add`, `Neg`, and `Index`, are
+//! external traits too.  Mock them with [`mock!`], including their associated
+//! `Output` types.  Their expectation methods are named after the trait
+//! methods, like `expect_add` and `expect_index`.  If the same operator is
+//! implemented for several right-hand side types, then the names are qualified
+//! as described in [Multiple and inherited
+//! traits](#multiple-and-inherited-traits), like `expect_Add_u32_add`.
+//!
+//! ```
+//! # use mockall::*;
+//! # use std::ops::{Add, Index, Neg};
+//! mock! {
+//!     Num {}
+//!     impl Add<u32> for Num {
+//!         type Output = u32;
+//!         fn add(self, rhs: u32) -> u32;
+//!     }
+//!     impl Neg for Num {
+//!         type Output = Self;
+//!         fn neg(self) -> Self;
+//!     }
+//!     impl Index<usize> for Num {
+//!         type Output = u32;
+//!         fn index(&self, i: usize) -> &u32;
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let mut mock = MockNum::new();
+//! mock.expect_index()
+//!     .return_const(5u32);
+//! mock.expect_neg()
+//!     .returning(|| {
+//!         let mut negated = MockNum::new();
+//!         negated.expect_add().returning(|rhs| rhs + 1);
+//!         negated
+//!     });
+//! assert_eq!(5, mock[0]);
+//! assert_eq!(3, -mock + 2);
+//! # }
+//! ```
+//!
+//! ### Iterators
+//!
+//! `Iterator` can be mocked like any other external trait.  Rather than setting
+//! one expectation for every call to `next`, use `return_items`.  It returns
+//! each item in turn, and then `None` forever after.  `return_items` is
+//! available for every method that returns an `Option`.
+//!
+//! ```
+//! # use mockall::*;
+//! mock! {
+//!     Iter {}
+//!     impl Iterator for Iter {
+//!         type Item = u32;
+//!         fn next(&mut self) -> Option<u32>;
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let mut mock = MockIter::new();
+//! mock.expect_next()
+//!     .return_items(vec![1, 2, 3]);
+//! assert_eq!(6u32, mock.sum());
+//! # }
+//! ```
+//!
+//! ## Receivers
+//!
+//! Besides `&self`, `&mut self`, and `self`, mocked methods may use boxed
+//! receivers like `self: Box<Self>`.  Such a method consumes the mock object,
+//! so its other expectations will be verified as soon as the method returns.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock]
+//! trait Task {
+//!     fn run(self: Box<Self>) -> u32;
+//! }
+//!
+//! # fn main() {
+//! let mut mock = MockTask::new();
+//! mock.expect_run()
+//!     .return_const(42u32);
+//! let task: Box<dyn Task> = Box::new(mock);
+//! assert_eq!(42, task.run());
+//! # }
+//! ```
+//!
+//! Reference-counted receivers, `self: Rc<Self>` and `self: Arc<Self>`, work
+//! too.  They don't consume the mock unless the last reference is dropped.
+//!
+//! Pinned receivers are supported as well.  Since mock objects are `Unpin`,
+//! that's enough to mock `Future`, `Stream`, and similar traits:
+//!
+//! ```
+//! # use mockall::*;
+//! # use futures::executor::block_on;
+//! # use std::{future::Future, pin::Pin, task::{Context, Poll}};
+//! mock! {
+//!     MyFuture {}
+//!     impl Future for MyFuture {
+//!         type Output = u32;
+//!         fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u32>;
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let mut mock = MockMyFuture::new();
+//! mock.expect_poll()
+//!     .return_const(Poll::Ready(42));
+//! assert_eq!(42, block_on(mock));
+//! # }
+//! ```
+//!
 //! ## Static methods
 //!
 //! Mockall can also mock static methods.  But be careful!  The expectations are
 //! global.  If you want to use a static method in multiple tests, you must
 //! provide your own synchronization. See the [`synchronization
 //! example`](https://github.com/asomers/mockall/blob/master/mockall/examples/synchronization.rs)
-//! for a basic implementation. For ordinary methods, expectations are
+//! for a basic implementation, or use
+//! [thread-local expectations](#thread-local-expectations). For ordinary methods, expectations are
 //! set on the mock object.  But static methods don't have any mock object.
 //! Instead, you must create a `Context` object just to set their expectations.
 //!
@@ -936,6 +1898,10 @@
 //! ctx.checkpoint();   // Panics!
 //! ```
 //!
+//! Static methods of mock structs can also be checkpointed without a context
+//! object, like `MockA::foo_checkpoint()`.  That's handy when the context is
+//! owned by a test fixture.
+//!
 //! A mock object's checkpoint method does *not* checkpoint static methods.
 //! This behavior is useful when using multiple mock objects at once.  For
 //! example:
@@ -961,6 +1927,32 @@
 //! # }
 //! ```
 //!
+//! ### Thread-local expectations
+//!
+//! Synchronizing tests that use the same static method means they can't run in
+//! parallel.  Alternatively, the `thread_local` metaitem gives every thread its
+//! own expectations.  Since `cargo test` runs each test in its own thread, tests
+//! won't see each other's expectations.  But code under test that calls the
+//! static method from a different thread won't see them either.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock(thread_local)]
+//! pub trait A {
+//!     fn foo() -> u32;
+//! }
+//!
+//! let ctx = MockA::foo_context();
+//! ctx.expect().returning(|| 99);
+//! std::thread::spawn(|| {
+//!     // This thread has its own expectations for MockA::foo
+//!     let ctx = MockA::foo_context();
+//!     ctx.expect().returning(|| 42);
+//!     assert_eq!(42, MockA::foo());
+//! }).join().unwrap();
+//! assert_eq!(99, MockA::foo());
+//! ```
+//!
 //! One more thing: Mockall normally creates a zero-argument `new` method for
 //! every mock struct.  But it *won't* do that when mocking a struct that
 //! already has a method named `new`.  The `default` method will still be
@@ -1005,66 +1997,268 @@
 //! # fn main() {}
 //! ```
 //!
-//! ### Foreign functions
+//! Like static methods, each mock function has its own `Context`.  The mock
+//! module also has a `checkpoint` function, which checkpoints every function
+//! in the module at once.
 //!
-//! One reason to mock modules is when working with foreign functions.  Modules
-//! may contain foreign functions, even though structs and traits may not.  Like
-//! static methods, the expectations are global.
+//! To mock just a few functions, there's no need to write a module.  Instead,
+//! declare them in [`mock!`].  For each function `foo`, that creates a mock
+//! function `mock_foo` and a `mock_foo_context` function.  `#[double]` will
+//! swap in `mock_foo` for `foo`, just as it does for modules.
 //!
 //! ```
+//! # use mockall::*;
 //! # use mockall_double::*;
-//! mod outer {
-//!     # use mockall::*;
-//!     #[automock]
-//!     pub mod ffi {
-//!         extern "C" {
-//!             pub fn foo(x: u32) -> i64;
-//!         }
+//! mod config {
+//!     pub fn parse(input: &str) -> Option<u32> {
+//!         // ...
+//!         # None
+//!     }
+//!     #[cfg(test)]
+//!     mockall::mock! {
+//!         pub fn parse(input: &str) -> Option<u32>;
 //!     }
 //! }
 //!
 //! #[double]
-//! use outer::ffi;
-//!
-//! fn do_stuff() -> i64 {
-//!     unsafe{ ffi::foo(42) }
-//! }
+//! use config::parse;
 //!
 //! #[cfg(test)]
 //! mod t {
 //!     use super::*;
 //!
 //!     #[test]
-//!     fn test_foo() {
-//!         let ctx = ffi::foo_context();
+//!     fn test_parse() {
+//!         let ctx = config::mock_parse_context();
 //!         ctx.expect()
-//!             .returning(|x| i64::from(x + 1));
-//!         assert_eq!(43, do_stuff());
+//!             .returning(|_| Some(7));
+//!         assert_eq!(Some(7), parse("seven"));
 //!     }
 //! }
 //! # fn main() {}
 //! ```
 //!
-//! ## Debug
+//! Any traits defined in an automocked module will be mocked too, just as if
+//! each had its own `#[automock]` attribute.  The mock structs are placed in
+//! the original module, next to their traits.  That's convenient for crates
+//! that keep all of their interface traits in one place.
 //!
-//! `#[automock]` will automatically generate `Debug` impls when mocking traits
-//! and struct impls.  `mock!` will too, if you add a `#[derive(Debug)]`, like
-//! this:
-//! ```no_run
+//! ```
 //! # use mockall::*;
-//! mock! {
-//!     #[derive(Debug)]
-//!     pub Foo {}
-//! }
-//! # fn main() {
+//! #[automock]
+//! pub mod ports {
+//!     pub trait Reader {
+//!         fn read(&self) -> u32;
+//!     }
+//!     pub trait Writer {
+//!         fn write(&mut self, x: u32);
+//!     }
+//! }
+//!
+//! # fn main() {
+//! use ports::*;
+//! let mut reader = MockReader::new();
+//! reader.expect_read().return_const(42u32);
+//! let mut writer = MockWriter::new();
+//! writer.expect_write().return_const(());
+//! # writer.write(reader.read());
+//! # }
+//! ```
+//!
+//! ### Foreign functions
+//!
+//! One reason to mock modules is when working with foreign functions.  Modules
+//! may contain foreign functions, even though structs and traits may not.  Like
+//! static methods, the expectations are global.
+//!
+//! ```
+//! # use mockall_double::*;
+//! mod outer {
+//!     # use mockall::*;
+//!     #[automock]
+//!     pub mod ffi {
+//!         extern "C" {
+//!             pub fn foo(x: u32) -> i64;
+//!         }
+//!     }
+//! }
+//!
+//! #[double]
+//! use outer::ffi;
+//!
+//! fn do_stuff() -> i64 {
+//!     unsafe{ ffi::foo(42) }
+//! }
+//!
+//! #[cfg(test)]
+//! mod t {
+//!     use super::*;
+//!
+//!     #[test]
+//!     fn test_foo() {
+//!         let ctx = ffi::foo_context();
+//!         ctx.expect()
+//!             .returning(|x| i64::from(x + 1));
+//!         assert_eq!(43, do_stuff());
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Sometimes the code under test calls foreign functions directly, rather than
+//! through a module that can be replaced with `#[double]`.  For that case,
+//! `#[automock(no_mangle)]` will additionally export each mock foreign
+//! function under the original function's symbol name (or its `link_name`).
+//! Any call through the original `extern` declaration will then reach the mock
+//! instead of the real library.  The exported functions are not gated on
+//! `cfg(test)` or on any feature: they are emitted in every build that applies
+//! the attribute, where they would clash with the real library's symbols.  So
+//! apply the attribute only in test builds, like
+//! `#[cfg_attr(test, automock(no_mangle))]`, and link the test binary without
+//! the real library.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock(no_mangle)]
+//! pub mod ffi {
+//!     extern "C" {
+//!         pub fn mockall_doc_no_mangle(x: u32) -> i64;
+//!     }
+//! }
+//!
+//! fn do_stuff() -> i64 {
+//!     unsafe{ ffi::mockall_doc_no_mangle(42) }
+//! }
+//!
+//! # fn main() {
+//! let ctx = mock_ffi::mockall_doc_no_mangle_context();
+//! ctx.expect()
+//!     .returning(|x| i64::from(x + 1));
+//! assert_eq!(43, do_stuff());
+//! # }
+//! ```
+//!
+//! Variadic foreign functions, like `printf`, can be mocked too.  Their
+//! expectations only see the fixed arguments.  Stable Rust can't define
+//! variadic functions, so the mock function will only accept the fixed
+//! arguments unless the **nightly** feature is enabled.  With **nightly**, the
+//! mock function is variadic too, and the crate that contains it must enable
+//! `#![feature(c_variadic)]`.
+//!
+//! ### Function pointers
+//!
+//! The functions in a mock module are ordinary functions, so they can be
+//! used wherever a bare function pointer is required, such as a callback
+//! registry.  Their behavior is controlled by the same global expectations as
+//! when they are called directly.  A function declared `extern "C"` in the
+//! original module keeps that ABI in the mock module, so it can be handed to C
+//! code too.
+//!
+//! ```
+//! # use mockall::*;
+//! #[automock]
+//! pub mod callbacks {
+//!     pub extern "C" fn on_event(code: u32) -> u32 {
+//!         // ...
+//!         # 0
+//!     }
+//! }
+//!
+//! fn register(cb: extern "C" fn(u32) -> u32) -> u32 {
+//!     // Pretend that this is a C library, and it calls the callback
+//!     cb(42)
+//! }
+//!
+//! # fn main() {
+//! let ctx = mock_callbacks::on_event_context();
+//! ctx.expect()
+//!     .returning(|code| code + 1);
+//! assert_eq!(43, register(mock_callbacks::on_event));
+//! # }
+//! ```
+//!
+//! ## Debug
+//!
+//! `#[automock]` will automatically generate `Debug` impls when mocking traits
+//! and struct impls.  `mock!` will too, if you add a `#[derive(Debug)]`, like
+//! this:
+//! ```no_run
+//! # use mockall::*;
+//! mock! {
+//!     #[derive(Debug)]
+//!     pub Foo {}
+//! }
+//! # fn main() {
 //! #     format!("{:?}", &MockFoo::default());
 //! # }
 //! ```
 //!
+//! ## Errors
+//!
+//! A mock struct can implement [`std::error::Error`], along with the
+//! `Display` and `Debug` impls that it requires.  If it mocks `source`, then
+//! declare it with a `'static` lifetime, because Mockall can't return
+//! references that don't live in the expectation itself.  The [`error_chain`]
+//! function links a list of errors together without mocking `source` at all.
+//! It returns an [`ErrorChain`], which owns every error in the chain, so they
+//! all get verified when it is dropped.
+//!
+//! ```
+//! # use mockall::*;
+//! # use std::error::Error;
+//! # use std::fmt::{self, Display, Formatter};
+//! mock! {
+//!     #[derive(Debug)]
+//!     pub IoError {}
+//!     impl Error for IoError {}
+//!     impl Display for IoError {
+//!         fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result;
+//!     }
+//! }
+//!
+//! fn root_cause(e: &(dyn Error + 'static)) -> String {
+//!     match e.source() {
+//!         Some(source) => root_cause(source),
+//!         None => e.to_string()
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let errors = ["retry failed", "connection reset"].iter().map(|msg| {
+//!     let mut e = MockIoError::new();
+//!     e.expect_fmt().returning(move |f| f.write_str(msg));
+//!     e
+//! }).collect::<Vec<_>>();
+//! let e = error_chain(errors);
+//! assert_eq!("connection reset", root_cause(&e));
+//! # }
+//! ```
+//!
 //! ## Async Traits
 //!
-//! Async traits aren't yet (as of 1.47.0) a part of the Rust language.  But
-//! they're available from the
+//! Since Rust 1.75.0, traits may contain `async fn` methods.  Mockall mocks
+//! them just like `async fn` methods of structs: the mock method is still
+//! `async`, but its Expectation deals in the future's output type rather than
+//! the future itself.  So `returning` takes a plain closure, and the mock
+//! method wraps its result in an already-ready future.
+//!
+//! ```
+//! # use mockall::*;
+//! # use futures::executor::block_on;
+//! #[automock]
+//! trait Foo {
+//!     async fn foo(&self, x: u32) -> u32;
+//! }
+//!
+//! # fn main() {
+//! let mut mock = MockFoo::new();
+//! mock.expect_foo()
+//!     .returning(|x| x + 1);
+//! assert_eq!(5, block_on(mock.foo(4)));
+//! # }
+//! ```
+//!
+//! On older compilers, async traits are available from the
 //! [`async_trait`](https://docs.rs/async-trait/0.1.38/async_trait/) crate.
 //! Mockall is compatible with this crate, with two important limitations:
 //!
@@ -1098,26 +2292,34 @@
 //! Mockall has a **nightly** feature.  Currently this feature has two
 //! effects:
 //!
-//! * The compiler will produce better error messages.
+//! * Failure messages will print the values of all `Debug` arguments.
+//!   Without the **nightly** feature, an argument whose type is generic prints
+//!   as `?` unless the generic parameter is bounded by `Debug`.
 //!
-//! * Expectations for methods whose return type implements `Default` needn't
-//!   have their return values explicitly set.  Instead, they will automatically
-//!   return the default value.
+//! * Expectations for methods whose return type is generic needn't have their
+//!   return values explicitly set, as long as the type that the generic
+//!   parameter is instantiated with implements `Default`.  Without the
+//!   **nightly** feature, a generic return type can only be defaulted if it's
+//!   bounded by `Default`.
 //!
 //! With **nightly** enabled, you can omit the return value like this:
 #![cfg_attr(feature = "nightly", doc = "```")]
 #![cfg_attr(not(feature = "nightly"), doc = "```should_panic")]
 //! # use mockall::*;
 //! #[automock]
-//! trait Foo {
-//!     fn foo(&self) -> Vec<u32>;
+//! trait Foo<T: 'static> {
+//!     fn foo(&self) -> T;
 //! }
 //!
-//! let mut mock = MockFoo::new();
+//! let mut mock = MockFoo::<Vec<u32>>::new();
 //! mock.expect_foo();
 //! assert!(mock.foo().is_empty());
 //! ```
 //!
+//! The **proptest** feature adds the `strategy` module, which converts
+//! between Mockall's argument matchers and
+//! [`proptest`](https://docs.rs/proptest) strategies.
+//!
 //! ## Examples
 //!
 //! For additional examples of Mockall in action, including detailed
@@ -1145,12 +2347,14 @@ use std::{
     any,
     fmt::{self, Debug, Formatter},
     marker::PhantomData,
+    mem,
     ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo,
           RangeToInclusive},
     sync::{
-        Arc,
-        atomic::{AtomicUsize, Ordering}
+        Arc, Mutex, PoisonError, RwLock,
+        atomic::{AtomicBool, AtomicUsize, Ordering}
     },
+    cell::{Cell, RefCell},
 };
 
 #[doc(hidden)]
@@ -1165,8 +2369,7 @@ pub use lazy_static::lazy_static;
 pub use predicates::{
     boolean::PredicateBooleanExt,
     prelude::{
-        Predicate, PredicateBoxExt, PredicateFileContentExt, PredicateStrExt,
-        predicate
+        Predicate, PredicateBoxExt, PredicateFileContentExt, PredicateStrExt
     }
 };
 #[doc(hidden)]
@@ -1176,6 +2379,11 @@ pub use predicates_tree::CaseTreeExt;
 extern crate self as mockall;
 #[cfg(doc)]
 pub mod examples;
+pub mod predicate;
+pub use predicate::ArgMatcher;
+#[cfg(feature = "proptest")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "proptest")))]
+pub mod strategy;
 
 /// Automatically generate mock types for structs and traits.
 ///
@@ -1242,6 +2450,65 @@ pub mod examples;
 /// }
 /// ```
 ///
+/// Code under test often takes a trait implementor by reference or inside of a
+/// smart pointer, like `impl Foo for &T` or `Box<dyn Foo>`.  The `forward`
+/// metaitem will implement the mocked trait for any of `&MockFoo`,
+/// `&mut MockFoo`, `Box<MockFoo>`, `Rc<MockFoo>`, or `Arc<MockFoo>` by
+/// forwarding every method to the mock object.  Each method's receiver must be
+/// compatible with the wrapper; for example, `&mut self` methods can't be
+/// forwarded through `Arc`.  Methods that use `Self`, other than through its
+/// associated types, can't be forwarded at all.
+/// ```
+/// # use mockall_derive::*;
+/// # use std::sync::Arc;
+/// #[automock(forward(&, Arc))]
+/// trait Foo {
+///     fn foo(&self, x: u32) -> u32;
+/// }
+///
+/// fn do_foo<F: Foo>(f: F) -> u32 {
+///     f.foo(4)
+/// }
+///
+/// let mut mock = MockFoo::new();
+/// mock.expect_foo()
+///     .returning(|x| x + 1);
+/// assert_eq!(5, do_foo(&mock));
+/// assert_eq!(5, do_foo(Arc::new(mock)));
+/// ```
+///
+/// Static methods' expectations are normally global.  The `thread_local`
+/// metaitem gives each thread its own, so tests can mock the same static
+/// method in parallel.  It works on traits, structs, and modules.
+/// ```
+/// # use mockall_derive::*;
+/// #[automock(thread_local)]
+/// trait Foo {
+///     fn foo(x: u32) -> u32;
+/// }
+/// ```
+///
+/// Code that refers to its dependencies through trait object aliases can
+/// `#[automock]` the alias, too.  That doesn't create a new mock struct.
+/// Instead, it names the trait's existing mock after the alias, and checks at
+/// compile time that the mock satisfies all of the alias's bounds.
+///
+/// ```
+/// # use mockall::*;
+/// #[automock]
+/// pub trait Database {
+///     fn get(&self, key: u32) -> Option<String>;
+/// }
+///
+/// #[automock]
+/// pub type Db = dyn Database + Send + Sync;
+///
+/// let mut mock = MockDb::new();
+/// mock.expect_get().return_const(None);
+/// let db: &Db = &mock;
+/// # db.get(0);
+/// ```
+///
 /// Finally, `#[automock]` can also mock foreign functions.  This requires
 /// another metaitem to specify the mock module name.
 ///
@@ -1253,6 +2520,40 @@ pub mod examples;
 /// }
 /// ```
 ///
+/// The `no_mangle` metaitem additionally exports each mock foreign function
+/// under its original symbol name.  The exports are emitted in every build
+/// that uses the metaitem, so it should only be applied for tests, like
+/// `#[cfg_attr(test, automock(no_mangle))]`.  See [Foreign
+/// functions](../mockall/index.html#foreign-functions) for details.
+///
+/// If the autogenerated "MockFoo" name isn't acceptable, for example because
+/// two mocked traits from different crates have the same name, the `name`
+/// metaitem chooses a different name for the mock struct.
+/// ```
+/// # use mockall_derive::*;
+/// #[automock(name = "FakeFoo")]
+/// trait Foo {
+///     fn foo(&self) -> u32;
+/// }
+///
+/// let mut mock = FakeFoo::new();
+/// mock.expect_foo().return_const(42u32);
+/// assert_eq!(42, mock.foo());
+/// ```
+///
+/// Ordinarily each `expect_*` method has the same visibility as the method it
+/// mocks, or as the mock struct for trait methods.  The `expect_vis` metaitem
+/// overrides that.  For example, a crate that exports its mock objects to
+/// downstream users, but doesn't want those users to set expectations, can
+/// restrict the `expect_*` methods to the current crate.
+/// ```
+/// # use mockall_derive::*;
+/// #[automock(expect_vis(pub(crate)))]
+/// pub trait Foo {
+///     fn foo(&self) -> u32;
+/// }
+/// ```
+///
 /// [`checkpoint`]: ../mockall/index.html#checkpoints
 ///
 /// # Limitations
@@ -1263,8 +2564,6 @@ pub mod examples;
 ///   structs that implement traits.
 /// * Mocking a struct or trait defined in another crate.
 /// * Mocking a trait with trait bounds.
-/// * If the autogenerated "MockFoo" name isn't acceptable, and you want
-///   to choose your own name for the mock structure.
 pub use mockall_derive::automock;
 
 /// Decorates a method or function to tell Mockall to treat its generic arguments
@@ -1279,10 +2578,12 @@ pub use mockall_derive::automock;
 ///   you won't be able to do `my_mock.expect_foo::<i32>(...)`.
 /// * It can't be used on methods with a closure argument (though this may be
 /// fixable).
-/// * Concretized methods' expectations may only be matched with `.withf` or
-/// `.withf_st`, not `.with`.
+/// * Concretized methods' expectations may only be matched with `.withf`,
+/// `.withf_st`, or `.with_as_ref`, not `.with`.
 /// * It only works for parameters that can be turned into a trait object.
-/// may be fixable).
+/// That rules out parameters bounded by more than one non-auto trait.
+/// Parameters with no trait bounds at all become [`Opaque`] trait objects,
+/// which can't be inspected.
 /// * Mockall needs to know how to turn the function argument into a trait
 /// object.  Given a generic parameter `T`, currently supported patterns are:
 ///   - `T`
@@ -1290,6 +2591,9 @@ pub use mockall_derive::automock;
 ///   - `&mut T`
 ///   - `&[T]`
 ///
+/// `impl Trait` arguments, like `msg: impl Display` or `msg: &impl Display`,
+/// are treated just like generic parameters.
+///
 /// # Examples
 /// ```
 /// # use std::path::Path;
@@ -1309,6 +2613,50 @@ pub use mockall_derive::automock;
 /// # }
 /// ```
 ///
+/// That makes it easy to mock methods like loggers, whose expectations would
+/// otherwise be generic.
+/// ```
+/// # use std::fmt::Display;
+/// # use mockall::*;
+/// #[automock]
+/// trait Logger {
+///     #[mockall::concretize]
+///     fn log(&self, msg: impl Display + Send);
+/// }
+///
+/// # fn main() {
+/// let mut mock = MockLogger::new();
+/// mock.expect_log()
+///     .withf(|msg| msg.to_string() == "42")
+///     .return_const(());
+/// mock.log(42);
+/// # }
+/// ```
+///
+/// If a generic argument is bounded only by `AsRef`, and none of the others
+/// become trait objects, then expectations will also have a `with_as_ref`
+/// method.  It works like `with`, but converts the `AsRef` arguments before
+/// evaluating their predicates.
+/// ```
+/// # use std::path::{Path, PathBuf};
+/// # use mockall::*;
+/// #[automock]
+/// trait Foo {
+///     #[mockall::concretize]
+///     fn foo<P: AsRef<Path>>(&self, p: P);
+/// }
+///
+/// # fn main() {
+/// let mut mock = MockFoo::new();
+/// mock.expect_foo()
+///     .with_as_ref(predicate::eq(Path::new("/tmp")))
+///     .times(2)
+///     .return_const(());
+/// mock.foo("/tmp");
+/// mock.foo(PathBuf::from("/tmp"));
+/// # }
+/// ```
+///
 /// NB: This attribute must be imported with its canonical name.  It won't work
 /// otherwise!
 /// ```compile_fail
@@ -1321,6 +2669,79 @@ pub use mockall_derive::automock;
 /// ```
 pub use mockall_derive::concretize;
 
+/// A trait implemented by every type.
+///
+/// [`#[concretize]`](attr.concretize.html) turns arguments whose generic type
+/// has no trait bounds into `&dyn Opaque`.  Their values can't be inspected,
+/// but expectations may still match on the method's other arguments.
+pub trait Opaque {}
+impl<T: ?Sized> Opaque for T {}
+
+/// Decorates a trait method to tell Mockall not to mock it.
+///
+/// The mock struct will use the trait's default implementation of the method
+/// instead of creating an expectation for it.  That's useful for traits with
+/// many provided methods built on a few required ones: only the required ones
+/// need expectations.  The attribute may be used with either [`automock`] or
+/// [`mock!`], but the method must have a default implementation.
+///
+/// # Examples
+/// ```
+/// # use mockall::*;
+/// #[automock]
+/// trait Shape {
+///     fn width(&self) -> u32;
+///     fn height(&self) -> u32;
+///     #[mockall::keep_default]
+///     fn area(&self) -> u32 {
+///         self.width() * self.height()
+///     }
+/// }
+///
+/// # fn main() {
+/// let mut mock = MockShape::new();
+/// mock.expect_width().return_const(3u32);
+/// mock.expect_height().return_const(4u32);
+/// assert_eq!(12, mock.area());
+/// # }
+/// ```
+///
+/// Like [`concretize`](attr.concretize.html), this attribute must be imported
+/// with its canonical name.
+pub use mockall_derive::keep_default;
+
+/// Decorates a method to give it a fixed stub implementation, rather than
+/// mocking it.
+///
+/// The mock method simply returns `Default::default()`, or the expression
+/// given as the attribute's argument, which may refer to the method's
+/// arguments.  It has no expectations, so Mockall generates much less code for
+/// it, and calling it doesn't need to take any locks.  That's handy for wide
+/// traits with many methods that tests never check.  The attribute may be used
+/// with either [`automock`] or [`mock!`].
+///
+/// # Examples
+/// ```
+/// # use mockall::*;
+/// #[automock]
+/// trait Foo {
+///     fn foo(&self, x: u32) -> u32;
+///     #[mockall::stub]
+///     fn name(&self) -> String;
+///     #[mockall::stub(x * 2)]
+///     fn double(&self, x: u32) -> u32;
+/// }
+///
+/// # fn main() {
+/// let mut mock = MockFoo::new();
+/// mock.expect_foo().return_const(1u32);
+/// assert_eq!(1, mock.foo(0));
+/// assert_eq!("", mock.name());
+/// assert_eq!(6, mock.double(3));
+/// # }
+/// ```
+pub use mockall_derive::stub;
+
 /// Manually mock a structure.
 ///
 /// Sometimes `automock` can't be used.  In those cases you can use `mock!`,
@@ -1330,10 +2751,12 @@ pub use mockall_derive::concretize;
 ///
 /// * Optional visibility specifier
 /// * Real structure name and generics fields
-/// * 0 or more methods of the structure, written without bodies, enclosed in a
-///   {} block
-/// * 0 or more impl blocks implementing traits on the structure, also without
-///   bodies.
+/// * Optionally, `as` followed by the name of the mock structure.  The default
+///   is the real structure's name prepended with "Mock".
+/// * 0 or more methods of the structure, usually written without bodies,
+///   enclosed in a {} block
+/// * 0 or more impl blocks implementing traits on the structure, also usually
+///   without bodies.
 ///
 /// # Examples
 ///
@@ -1353,6 +2776,33 @@ pub use mockall_derive::concretize;
 /// }
 /// # fn main() {}
 /// ```
+/// A method written with a body isn't mocked at all.  It's a fake: the mock
+/// struct uses that body as-is, and there's no `expect_` method for it.  That
+/// suits methods whose behavior never needs to vary between tests.  A fake can
+/// call the struct's other methods, including mocked ones.
+/// ```
+/// # use mockall_derive::mock;
+/// trait Named {
+///     fn name(&self) -> String;
+/// }
+/// mock!{
+///     pub MyStruct {
+///         fn id(&self) -> u32 { 42 }
+///         fn bar(&self) -> u8;
+///     }
+///     impl Named for MyStruct {
+///         fn name(&self) -> String {
+///             format!("struct {}", self.id())
+///         }
+///     }
+/// }
+/// # fn main() {
+/// let mut mock = MockMyStruct::new();
+/// mock.expect_bar().return_const(5u8);
+/// assert_eq!(5, mock.bar());
+/// assert_eq!("struct 42", mock.name());
+/// # }
+/// ```
 /// Mocking an unsupported `#[derive(X)]` attribute, e.g. `Clone`, is
 /// similar.
 /// ```
@@ -1370,37 +2820,133 @@ pub use mockall_derive::concretize;
 /// }
 /// # fn main() {}
 /// ```
-///
-/// When mocking a generic struct's implementation of a generic trait, use the
-/// same namespace for their generic parameters.  For example, if you wanted to
-/// mock `Rc`, do
+/// Since `Clone` has only one method, its impl block may be abbreviated to
+/// `impl Clone for MyStruct;`.  `clone` will still have an expectation, which
+/// can return a separately configured mock object.
 /// ```
 /// # use mockall_derive::mock;
 /// mock!{
-///     pub Rc<T: 'static> {}
-///     impl<T: 'static> AsRef<T> for Rc<T> {
-///         fn as_ref(&self) -> &T;
+///     pub MyStruct {
+///         fn bar(&self);
 ///     }
+///     impl Clone for MyStruct;
 /// }
-/// # fn main() {}
+/// # fn main() {
+/// let mut mock = MockMyStruct::new();
+/// mock.expect_clone()
+///     .returning(MockMyStruct::new);
+/// # }
+/// ```
+/// `Deref` and `DerefMut` impls may omit their methods, because the methods'
+/// signatures follow from the `Deref` impl's `Target` type.  That makes
+/// smart-pointer-like types easy to mock.  `expect_deref` and
+/// `expect_deref_mut` work just like the expectations of any other method that
+/// returns a reference.  `impl DerefMut for MyStruct {}` may also be
+/// abbreviated to `impl DerefMut for MyStruct;`.
 /// ```
-/// *not*
-/// ```compile_fail
 /// # use mockall_derive::mock;
+/// # use std::ops::{Deref, DerefMut};
 /// mock!{
-///     pub Rc<Q: 'static> {}
-///     impl<T: 'static> AsRef<T> for Rc<T> {
-///         fn as_ref(&self) -> &T;
+///     pub MyStruct {}
+///     impl Deref for MyStruct {
+///         type Target = Vec<u32>;
 ///     }
+///     impl DerefMut for MyStruct;
 /// }
-/// # fn main() {}
+/// # fn main() {
+/// let mut mock = MockMyStruct::new();
+/// mock.expect_deref()
+///     .return_const(vec![1, 2, 3]);
+/// assert_eq!(3, mock.len());
+/// # }
 /// ```
-/// Associated types can easily be mocked by specifying a concrete type in the
-/// `mock!{}` invocation.
+/// `Display` and `Debug` impls may be abbreviated too, like `impl fmt::Display
+/// for MyStruct;`.  Then `expect_fmt` takes no arguments and returns a
+/// `String`, which the mock will write to the `Formatter`.  That makes it easy
+/// to verify code that formats its collaborators.  If both traits are mocked,
+/// the methods are named `expect_Debug_fmt` and `expect_Display_fmt`.
 /// ```
 /// # use mockall_derive::mock;
+/// # use std::fmt;
 /// mock!{
-///     MyIter {}
+///     pub MyStruct {}
+///     impl fmt::Display for MyStruct;
+/// }
+/// # fn main() {
+/// let mut mock = MockMyStruct::new();
+/// mock.expect_fmt()
+///     .return_const(String::from("World"));
+/// assert_eq!("Hello, World!", format!("Hello, {}!", mock));
+/// # }
+/// ```
+/// `PartialEq` may be abbreviated too, as `impl PartialEq<Rhs> for MyStruct;`.
+/// It gets an `eq` method, which compares to `Rhs`, or to `Self` if `Rhs` is
+/// omitted or names the struct being mocked.  The same trait may be
+/// implemented with several different type arguments.  Their expectation
+/// methods include those type arguments, like `expect_PartialEq_u32_eq`.
+/// ```
+/// # use mockall_derive::mock;
+/// mock!{
+///     pub MyStruct {}
+///     impl PartialEq<u32> for MyStruct;
+///     impl PartialEq<MyStruct> for MyStruct;
+/// }
+/// # fn main() {
+/// let mut a = MockMyStruct::new();
+/// let b = MockMyStruct::new();
+/// a.expect_PartialEq_u32_eq()
+///     .withf(|x| *x == 42)
+///     .return_const(true);
+/// a.expect_PartialEq_Self_eq()
+///     .return_const(false);
+/// assert!(a == 42);
+/// assert!(a != b);
+/// # }
+/// ```
+/// No other trait's impl block may be abbreviated that way.
+/// ```compile_fail
+/// # use mockall_derive::mock;
+/// mock!{
+///     pub MyStruct {}
+///     impl Default for MyStruct;
+/// }
+/// # fn main() {}
+/// ```
+///
+/// When mocking a generic struct's implementation of a generic trait, the
+/// `impl` block's generic parameters need not have the same names as the
+/// struct's.  Mockall will rename them to match.  For example, to mock `Rc`
+/// ```
+/// # use mockall_derive::mock;
+/// mock!{
+///     pub Rc<Q: 'static> {}
+///     impl<T: 'static> AsRef<T> for Rc<T> {
+///         fn as_ref(&self) -> &T;
+///     }
+/// }
+/// # fn main() {}
+/// ```
+/// The mock structure may be given a name of your choosing.  The `impl`
+/// blocks still refer to the real structure's name.
+/// ```
+/// # use mockall_derive::mock;
+/// mock!{
+///     pub MyStruct<T: 'static> as FakeStruct {
+///         fn bar(&self) -> T;
+///     }
+///     impl<T: 'static> Clone for MyStruct<T>;
+/// }
+/// # fn main() {
+/// let mut mock = FakeStruct::<u8>::new();
+/// mock.expect_bar().return_const(7u8);
+/// # }
+/// ```
+/// Associated types can easily be mocked by specifying a concrete type in the
+/// `mock!{}` invocation.
+/// ```
+/// # use mockall_derive::mock;
+/// mock!{
+///     MyIter {}
 ///     impl Iterator for MyIter {
 ///         type Item=u32;
 ///
@@ -1409,51 +2955,106 @@ pub use mockall_derive::concretize;
 /// }
 /// # fn main() {}
 /// ```
+/// Instead of a structure, `mock!` can also accept one or more standalone
+/// functions, written without bodies.  For each function `foo` it will create a
+/// mock function named `mock_foo` and a `mock_foo_context` function to create
+/// its [`Context`](#static-methods).
+/// ```
+/// # use mockall_derive::mock;
+/// mock!{
+///     pub fn parse(input: &str) -> Option<u32>;
+/// }
+/// # fn main() {
+/// let ctx = mock_parse_context();
+/// ctx.expect().returning(|s| s.parse().ok());
+/// assert_eq!(Some(42), mock_parse("42"));
+/// # }
+/// ```
 pub use mockall_derive::mock;
 
 #[doc(hidden)]
-pub trait AnyExpectations : Any + Send + Sync {}
+pub trait AnyExpectations : Any + Send + Sync {
+    fn in_scenario(&mut self, scenario: &mut Scenario);
+    fn try_verify(&self, errors: &mut Vec<ExpectationError>);
+}
+
+/// Used by generated code to check at compile time that a mock struct is
+/// `Send` and `Sync`.
+#[doc(hidden)]
+pub fn assert_send_sync<T: Send + Sync + ?Sized>() {}
 downcast!(dyn AnyExpectations);
 
+// Default return values use autoref-based specialization.  Generated code
+// calls these methods on a `&&DefaultReturner<O>`, which will find the
+// `ReturnDefault` impl if `O: Default` is known to hold there, and fall back to
+// `ReturnNoDefault` otherwise.  With the "nightly" feature, real
+// specialization lets `ReturnDefault` apply even to generic types.
 #[doc(hidden)]
 pub trait ReturnDefault<O> {
-    fn maybe_return_default() -> Option<O>;
-    fn return_default() -> Result<O, &'static str>;
+    fn maybe_return_default(&self) -> Option<O>;
+    fn return_default(&self) -> Result<O, &'static str>;
+}
+
+#[doc(hidden)]
+pub trait ReturnNoDefault<O> {
+    fn maybe_return_default(&self) -> Option<O>;
+    fn return_default(&self) -> Result<O, &'static str>;
 }
 
-#[derive(Default)]
 #[doc(hidden)]
 pub struct DefaultReturner<O>(PhantomData<O>);
 
+impl<O> DefaultReturner<O> {
+    pub fn new() -> Self {
+        DefaultReturner(PhantomData)
+    }
+}
+
+impl<O> Default for DefaultReturner<O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O> ReturnNoDefault<O> for DefaultReturner<O> {
+    fn maybe_return_default(&self) -> Option<O> {
+        None
+    }
+
+    fn return_default(&self) -> Result<O, &'static str> {
+        Err("Can only return default values for types known to impl std::Default, unless using the \"nightly\" feature")
+    }
+}
+
 ::cfg_if::cfg_if! {
     if #[cfg(feature = "nightly")] {
-        impl<O> ReturnDefault<O> for DefaultReturner<O> {
-            default fn maybe_return_default() -> Option<O> {
+        impl<O> ReturnDefault<O> for &DefaultReturner<O> {
+            default fn maybe_return_default(&self) -> Option<O> {
                 None
             }
 
-            default fn return_default() -> Result<O, &'static str> {
+            default fn return_default(&self) -> Result<O, &'static str> {
                 Err("Can only return default values for types that impl std::Default")
             }
         }
 
-        impl<O: Default> ReturnDefault<O> for DefaultReturner<O> {
-            fn maybe_return_default() -> Option<O> {
+        impl<O: Default> ReturnDefault<O> for &DefaultReturner<O> {
+            fn maybe_return_default(&self) -> Option<O> {
                 Some(O::default())
             }
 
-            fn return_default() -> Result<O, &'static str> {
+            fn return_default(&self) -> Result<O, &'static str> {
                 Ok(O::default())
             }
         }
     } else {
-        impl<O> ReturnDefault<O> for DefaultReturner<O> {
-            fn maybe_return_default() -> Option<O> {
-                None
+        impl<O: Default> ReturnDefault<O> for &DefaultReturner<O> {
+            fn maybe_return_default(&self) -> Option<O> {
+                Some(O::default())
             }
 
-            fn return_default() -> Result<O, &'static str> {
-                Err("Returning default values requires the \"nightly\" feature")
+            fn return_default(&self) -> Result<O, &'static str> {
+                Ok(O::default())
             }
         }
     }
@@ -1484,6 +3085,57 @@ pub struct MaybeDebugger<'a, T>(pub &'a T);
     }
 }
 
+/// Why no expectation could handle a call to a mock method.
+///
+/// Its `Display` impl explains the failure, for the panic message.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct NoMatch {
+    /// Explanation of why each expectation rejected the call
+    pub msg: String,
+    /// Did some expectation match, though it had already been called as many
+    /// times as allowed?  Such a call is an error even for nice mocks.
+    pub saturated: bool
+}
+
+impl fmt::Display for NoMatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.msg)
+    }
+}
+
+/// Formats a mock method's argument for failure messages, using its `Debug`
+/// impl if it's known to have one.
+///
+/// Call it like `(&&ArgPrinter(&x)).debug_string()`, with both [`ArgDebug`] and
+/// [`ArgNoDebug`] in scope.  Method resolution will prefer `ArgDebug`, if it
+/// applies.
+#[doc(hidden)]
+pub struct ArgPrinter<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait ArgDebug {
+    fn debug_string(&self) -> String;
+}
+
+#[doc(hidden)]
+pub trait ArgNoDebug {
+    fn debug_string(&self) -> String;
+}
+
+impl<'a, T: Debug> ArgDebug for &ArgPrinter<'a, T> {
+    fn debug_string(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+impl<'a, T> ArgNoDebug for ArgPrinter<'a, T> {
+    fn debug_string(&self) -> String {
+        // Generic code can't tell whether T is Debug, except on nightly.
+        format!("{:?}", MaybeDebugger(self.0))
+    }
+}
+
 // Though it's not entirely correct, we treat usize::max_value() as
 // approximately infinity.
 #[derive(Debug)]
@@ -1531,6 +3183,7 @@ impl From<RangeInclusive<usize>> for TimesRange {
 
 impl From<RangeTo<usize>> for TimesRange {
     fn from(r: RangeTo<usize>) -> TimesRange {
+        assert!(r.end > 0, "Empty range");
         TimesRange(0..r.end)
     }
 }
@@ -1555,7 +3208,7 @@ impl Times {
         let count = self.count.fetch_add(1, Ordering::Relaxed) + 1;
         if count >= self.range.0.end {
             if self.range.0.end == 1 {
-                Err("should not have been called".to_owned())
+                Err("was called, but never() was specified".to_owned())
             } else {
                 Err(format!(
                     "called {} times which is more than the expected {}",
@@ -1568,6 +3221,18 @@ impl Times {
         }
     }
 
+    /// Require at least `n` calls, leaving the maximum unchanged
+    pub fn at_least(&mut self, n: usize) {
+        let end = self.range.0.end;
+        self.range(n..end);
+    }
+
+    /// Allow at most `n` calls, leaving the minimum unchanged
+    pub fn at_most(&mut self, n: usize) {
+        let start = self.range.0.start;
+        self.range(start..n.saturating_add(1));
+    }
+
     pub fn any(&mut self) {
         self.range.0 = 0..usize::max_value();
     }
@@ -1579,8 +3244,12 @@ impl Times {
 
     /// Has this expectation already been called the maximum allowed number of
     /// times?
+    ///
+    /// A `never` expectation is never done, so that a matching call will find
+    /// it and panic instead of falling through to a later expectation.
     pub fn is_done(&self) -> bool {
-        self.count.load(Ordering::Relaxed) >= self.range.0.end - 1
+        self.range.0.end > 1 &&
+            self.count.load(Ordering::Relaxed) >= self.range.0.end - 1
     }
 
     /// Is it required that this expectation be called an exact number of times,
@@ -1623,12 +3292,369 @@ impl Times {
 /// Non-generic keys to `GenericExpectation` internal storage
 #[doc(hidden)]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-pub struct Key(any::TypeId);
+pub struct Key(any::TypeId, &'static str);
 
 #[doc(hidden)]
 impl Key {
     pub fn new<T: 'static>() -> Self {
-        Key(any::TypeId::of::<T>())
+        Key(any::TypeId::of::<T>(), any::type_name::<T>())
+    }
+
+    /// The name of the generic parameters this key represents, for use in
+    /// error messages.
+    pub fn name(&self) -> &'static str {
+        self.1
+    }
+}
+
+/// Unexpected calls recorded by a mock object created with `new_recording`.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct UnexpectedCalls {
+    recording: bool,
+    calls: Mutex<Vec<String>>
+}
+
+#[doc(hidden)]
+impl UnexpectedCalls {
+    pub fn recording() -> Self {
+        UnexpectedCalls {
+            recording: true,
+            calls: Mutex::default()
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Record a call that no expectation matched, if recording.
+    pub fn record<F: FnOnce() -> String>(&self, f: F) {
+        if self.recording {
+            self.calls.lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(f());
+        }
+    }
+
+    pub fn take(&mut self) -> Vec<String> {
+        let mut calls = self.calls.lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        mem::take(&mut *calls)
+    }
+
+    /// Panic if any unexpected calls have been recorded, and clear them.
+    pub fn checkpoint(&mut self) {
+        report_failures(Vec::new(), self.take());
+    }
+}
+
+/// Report a single message describing every unsatisfied expectation and
+/// every unexpected call, if there are any.
+#[doc(hidden)]
+pub fn report_failures(errors: Vec<ExpectationError>, unexpected: Vec<String>)
+{
+    let msg = match (errors.len(), unexpected.len()) {
+        (0, 0) => return,
+        (1, 0) => errors[0].to_string(),
+        (0, n) => format!("{} unexpected call(s):{}", n,
+                          unexpected.iter()
+                              .map(|c| format!("\n    {}", c))
+                              .collect::<String>()),
+        (e, n) => format!("{} verification failure(s):{}{}", e + n,
+                          errors.iter()
+                              .map(|e| format!("\n    {}", e))
+                              .collect::<String>(),
+                          unexpected.iter()
+                              .map(|c| format!("\n    unexpected call: {}", c))
+                              .collect::<String>())
+    };
+    fail_verification(msg);
+}
+
+/// The kind of problem described by a [`FailureInfo`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum FailureKind {
+    /// A call that Mockall couldn't handle, because no expectation matched it,
+    /// it exceeded an expectation's call count, it was out of sequence, or its
+    /// expectation had no return value.  The mock can't return from such a
+    /// call, so Mockall panics after the failure handler returns.
+    Call,
+    /// Expectations that weren't called enough times, or unexpected calls
+    /// recorded by a recording mock, found when a mock object was
+    /// checkpointed or dropped, or a [`Scenario`] was verified.  If the failure
+    /// handler returns, Mockall carries on without panicking.
+    Verification,
+}
+
+/// Describes an expectation failure to the handler installed by
+/// [`set_failure_handler`].
+#[derive(Clone, Copy, Debug)]
+pub struct FailureInfo<'a> {
+    kind: FailureKind,
+    message: &'a str
+}
+
+impl<'a> FailureInfo<'a> {
+    /// What kind of failure this is
+    pub fn kind(&self) -> FailureKind {
+        self.kind
+    }
+
+    /// The message that Mockall would panic with
+    pub fn message(&self) -> &'a str {
+        self.message
+    }
+}
+
+impl<'a> fmt::Display for FailureInfo<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message)
+    }
+}
+
+lazy_static! {
+    static ref FAILURE_HANDLER: RwLock<Option<fn(&FailureInfo<'_>)>> =
+        RwLock::new(None);
+}
+
+thread_local! {
+    /// Set while clearing expectations whose failures were already reported
+    static SUPPRESS_VERIFICATION: Cell<bool> = Cell::new(false);
+    /// Verification failures that happened while the thread was panicking
+    static UNWINDING_FAILURES: RefCell<Vec<String>> = RefCell::new(Vec::new());
+}
+
+/// Install a function that will be told about every expectation failure, for
+/// test harnesses that have their own way of reporting them.
+///
+/// By default, Mockall panics on failure.  The handler is called first, with a
+/// [`FailureInfo`] that describes the failure.  For a
+/// [`FailureKind::Verification`] failure, the handler replaces the panic.  It
+/// can record the failure and return, and the test will carry on.  But for a
+/// [`FailureKind::Call`] failure, Mockall still panics once the handler
+/// returns, because the mock method has nothing to return.  Of course, the
+/// handler may panic or abort by itself.
+///
+/// The handler is global, so it applies to every test in the process.  Tests
+/// that run in parallel may want to route failures through a thread-local
+/// variable.
+///
+/// # Examples
+/// ```
+/// # use mockall::*;
+/// use std::cell::RefCell;
+///
+/// thread_local! {
+///     static FAILURES: RefCell<Vec<String>> = RefCell::new(Vec::new());
+/// }
+///
+/// fn record(info: &FailureInfo) {
+///     FAILURES.with(|f| f.borrow_mut().push(info.message().to_owned()));
+/// }
+///
+/// #[automock]
+/// trait Foo {
+///     fn foo(&self);
+/// }
+///
+/// set_failure_handler(record);
+/// let mut mock = MockFoo::new();
+/// mock.expect_foo().times(1).return_const(());
+/// drop(mock);    // Doesn't panic
+/// reset_failure_handler();
+/// FAILURES.with(|f| assert_eq!(
+///     "MockFoo::foo: Expectation(<anything>) called 0 time(s) which is fewer than expected 1",
+///     f.borrow()[0]));
+/// ```
+pub fn set_failure_handler(handler: fn(&FailureInfo<'_>)) {
+    *FAILURE_HANDLER.write().unwrap() = Some(handler);
+}
+
+/// Remove the handler installed by [`set_failure_handler`], so expectation
+/// failures panic again.
+pub fn reset_failure_handler() {
+    *FAILURE_HANDLER.write().unwrap() = None;
+}
+
+/// Tell the failure handler, if any, about a failure.  Return whether there
+/// was a handler.
+fn handle_failure(kind: FailureKind, message: &str) -> bool {
+    // Copy the handler out, so it may itself change the handler
+    let handler = *FAILURE_HANDLER.read().unwrap();
+    if let Some(handler) = handler {
+        handler(&FailureInfo{kind, message});
+        true
+    } else {
+        false
+    }
+}
+
+/// Report a failure during a call, which is always fatal.
+#[doc(hidden)]
+pub fn fail_call(message: String) -> ! {
+    handle_failure(FailureKind::Call, &message);
+    panic!("{}", message)
+}
+
+/// Report a verification failure.  Panic, unless a failure handler is
+/// installed.  If the thread is already panicking, just record it for
+/// [`take_unwinding_failures`], because a second panic would abort.
+#[doc(hidden)]
+pub fn fail_verification(message: String) {
+    if SUPPRESS_VERIFICATION.with(Cell::get) {
+        // Already reported
+    } else if std::thread::panicking() {
+        UNWINDING_FAILURES.with(|f| f.borrow_mut().push(message));
+    } else if !handle_failure(FailureKind::Verification, &message) {
+        panic!("{}", message)
+    }
+}
+
+/// Return, and forget, the verification failures of mock objects that were
+/// dropped on this thread while it was panicking.
+///
+/// Mockall can't panic for those failures, because a panic during unwinding
+/// aborts the process.  Nor does it call the failure handler.  Instead, it
+/// clears the mock's expectations as usual and records the failure here.
+/// That way a property-testing framework like
+/// [proptest](https://docs.rs/proptest) can catch the original panic, shrink
+/// the input, and run the next case with fresh expectations.
+///
+/// # Examples
+/// ```
+/// # use mockall::*;
+/// # use std::panic;
+/// #[automock]
+/// trait Foo {
+///     fn foo(&self, x: u32) -> u32;
+/// }
+///
+/// let r = panic::catch_unwind(|| {
+///     let mut mock = MockFoo::new();
+///     mock.expect_foo().times(2).return_const(0u32);
+///     mock.foo(1);
+///     panic!("the code under test failed");
+/// });
+/// assert!(r.is_err());
+/// let failures = take_unwinding_failures();
+/// assert_eq!(1, failures.len());
+/// assert!(failures[0].contains("called 1 time(s) which is fewer than expected 2"));
+/// assert!(take_unwinding_failures().is_empty());
+/// ```
+pub fn take_unwinding_failures() -> Vec<String> {
+    UNWINDING_FAILURES.with(|f| mem::take(&mut *f.borrow_mut()))
+}
+
+/// Run `f` without reporting verification failures.  It's used to discard
+/// expectations whose failures have already been reported.
+#[doc(hidden)]
+pub fn suppress_verification<F: FnOnce()>(f: F) {
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SUPPRESS_VERIFICATION.with(|s| s.set(self.0));
+        }
+    }
+    let _restore = Restore(SUPPRESS_VERIFICATION.with(|s| s.replace(true)));
+    f();
+}
+
+impl Drop for UnexpectedCalls {
+    fn drop(&mut self) {
+        self.checkpoint();
+    }
+}
+
+/// Converts a captured argument to its owned type.
+///
+/// Unlike a plain `ToOwned` bound, this is never trivially unsatisfied, so
+/// `capture` can be generated even for methods whose arguments can't be
+/// captured.
+#[doc(hidden)]
+pub trait CaptureArg<O> {
+    fn capture_arg(&self) -> O;
+}
+
+impl<T: ToOwned + ?Sized> CaptureArg<T::Owned> for T {
+    fn capture_arg(&self) -> T::Owned {
+        self.to_owned()
+    }
+}
+
+/// The arguments captured by an expectation's `capture` method.
+///
+/// Each call that the expectation handles appends its arguments, converted to
+/// their owned types.  For methods with more than one argument, they are
+/// captured as a tuple.  A `Captured` is cheap to clone; all clones share the
+/// same storage.
+///
+/// # Examples
+///
+/// ```
+/// # use mockall::*;
+/// #[automock]
+/// trait Foo {
+///     fn foo(&self, name: &str, x: u32);
+/// }
+///
+/// let mut mock = MockFoo::new();
+/// let calls = mock.expect_foo()
+///     .return_const(())
+///     .capture();
+/// mock.foo("a", 1);
+/// mock.foo("b", 2);
+/// assert_eq!(calls.values(), vec![("a".to_owned(), 1), ("b".to_owned(), 2)]);
+/// ```
+#[derive(Debug)]
+pub struct Captured<T>(Arc<Mutex<Vec<T>>>);
+
+impl<T> Captured<T> {
+    #[doc(hidden)]
+    pub fn push(&self, t: T) {
+        self.0.lock().unwrap().push(t);
+    }
+
+    /// Returns true if no calls have been captured.
+    pub fn is_empty(&self) -> bool {
+        self.0.lock().unwrap().is_empty()
+    }
+
+    /// The number of calls captured so far.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    /// Remove and return all of the captured arguments, in the order that
+    /// they were called.
+    pub fn take(&self) -> Vec<T> {
+        mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl<T: Clone> Captured<T> {
+    /// The arguments of the most recent call, if there has been one.
+    pub fn last(&self) -> Option<T> {
+        self.0.lock().unwrap().last().cloned()
+    }
+
+    /// Copies of all of the captured arguments, in the order that they were
+    /// called.
+    pub fn values(&self) -> Vec<T> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl<T> Clone for Captured<T> {
+    fn clone(&self) -> Self {
+        Captured(self.0.clone())
+    }
+}
+
+impl<T> Default for Captured<T> {
+    fn default() -> Self {
+        Captured(Arc::default())
     }
 }
 
@@ -1639,9 +3665,14 @@ pub struct SeqHandle {
 }
 
 impl SeqHandle {
-    /// Tell the Sequence that this expectation has been fully satisfied
-    pub fn satisfy(&self) {
-        self.inner.satisfy(self.seq);
+    /// Tell the Sequence that this expectation has been called.  `satisfied`
+    /// is whether it has now been called enough times.
+    pub fn called(&self, satisfied: bool) {
+        if self.inner.cyclic {
+            self.inner.satisfaction_level.fetch_add(1, Ordering::Relaxed);
+        } else if satisfied {
+            self.inner.satisfy(self.seq);
+        }
     }
 
     /// Verify that this handle was called in the correct order
@@ -1653,19 +3684,30 @@ impl SeqHandle {
 #[derive(Default)]
 struct SeqInner {
     satisfaction_level: AtomicUsize,
+    /// Does the sequence start over after its last expectation?
+    cyclic: bool,
+    /// How many expectations are in the sequence?
+    len: AtomicUsize,
 }
 
 impl SeqInner {
     /// Record the call identified by `seq` as fully satisfied.
     fn satisfy(&self, seq: usize) {
         let old_sl = self.satisfaction_level.fetch_add(1, Ordering::Relaxed);
-        assert_eq!(old_sl, seq, "Method sequence violation.  Was an already-satisfied method called another time?");
+        if old_sl != seq {
+            fail_call("Method sequence violation.  Was an already-satisfied method called another time?".to_owned());
+        }
     }
 
     /// Verify that the call identified by `seq` was called in the correct order
     fn verify(&self, seq: usize, desc: &str) {
-        assert_eq!(seq, self.satisfaction_level.load(Ordering::Relaxed),
-            "{}: Method sequence violation", desc)
+        let mut level = self.satisfaction_level.load(Ordering::Relaxed);
+        if self.cyclic {
+            level %= self.len.load(Ordering::Relaxed);
+        }
+        if seq != level {
+            fail_call(format!("{}: Method sequence violation", desc));
+        }
     }
 }
 
@@ -1701,6 +3743,42 @@ impl SeqInner {
 /// mock1.bar();
 /// ```
 ///
+/// A sequence may also be cyclic, created with [`Sequence::new_cyclic`].  In a
+/// cyclic sequence, each call advances the sequence by one step, and once the
+/// last expectation has been called the sequence starts over from the first.
+/// That allows verifying a repeating pattern without listing every iteration.
+/// Each expectation's call count is then the number of times the cycle should
+/// repeat.
+/// ```
+/// # use mockall::*;
+/// #[automock]
+/// trait Foo {
+///     fn poll(&self) -> u32;
+///     fn process(&self, x: u32);
+///     fn ack(&self);
+/// }
+/// let mut seq = Sequence::new_cyclic();
+///
+/// let mut mock = MockFoo::new();
+/// mock.expect_poll()
+///     .times(3)
+///     .in_sequence(&mut seq)
+///     .return_const(7u32);
+/// mock.expect_process()
+///     .times(3)
+///     .in_sequence(&mut seq)
+///     .return_const(());
+/// mock.expect_ack()
+///     .times(3)
+///     .in_sequence(&mut seq)
+///     .return_const(());
+///
+/// for _ in 0..3 {
+///     mock.process(mock.poll());
+///     mock.ack();
+/// }
+/// ```
+///
 /// It is an error to add an expectation to a `Sequence` if its call count is
 /// unspecified.
 /// ```should_panic(expected = "with an exact call count")
@@ -1728,12 +3806,341 @@ impl Sequence {
         Self::default()
     }
 
+    /// Create a new empty [`Sequence`] that starts over after its last
+    /// expectation has been called.
+    ///
+    /// Every expectation must be added to the sequence before any of them are
+    /// called.
+    pub fn new_cyclic() -> Self {
+        let inner = SeqInner {
+            cyclic: true,
+            .. SeqInner::default()
+        };
+        Sequence {
+            inner: Arc::new(inner),
+            next_seq: 0
+        }
+    }
+
     /// Not for public consumption, but it must be public so the generated code
     /// can call it.
     #[doc(hidden)]
     pub fn next_handle(&mut self) -> SeqHandle {
         let handle = SeqHandle{inner: self.inner.clone(), seq: self.next_seq};
         self.next_seq += 1;
+        self.inner.len.store(self.next_seq, Ordering::Relaxed);
         handle
     }
 }
+
+/// An expectation that hasn't been called as many times as required.
+///
+/// This is returned by every mock object's `try_verify` method.
+///
+/// # Examples
+/// ```
+/// # use mockall::*;
+/// #[automock]
+/// trait Foo {
+///     fn foo(&self);
+/// }
+///
+/// let mut mock = MockFoo::new();
+/// mock.expect_foo().times(2).return_const(());
+/// mock.foo();
+/// let errors = mock.try_verify().unwrap_err();
+/// assert_eq!(1, errors.len());
+/// assert_eq!(1, errors[0].count());
+/// assert_eq!(2, errors[0].minimum());
+/// assert_eq!(
+///     "MockFoo::foo: Expectation(<anything>) called 1 time(s) which is fewer than expected 2",
+///     errors[0].to_string());
+/// # mock.foo();
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpectationError {
+    desc: String,
+    count: usize,
+    minimum: usize
+}
+
+impl ExpectationError {
+    /// Not for public consumption, but it must be public so the generated code
+    /// can call it.
+    #[doc(hidden)]
+    pub fn new(desc: String, times: &Times) -> Self {
+        ExpectationError {
+            desc,
+            count: times.count(),
+            minimum: times.minimum()
+        }
+    }
+
+    /// How many times the expectation was called
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Describes the method and the expectation's matcher
+    pub fn description(&self) -> &str {
+        &self.desc
+    }
+
+    /// The minimum number of times that the expectation must be called
+    pub fn minimum(&self) -> usize {
+        self.minimum
+    }
+}
+
+impl fmt::Display for ExpectationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} called {} time(s) which is fewer than expected {}",
+               self.desc, self.count, self.minimum)
+    }
+}
+
+impl std::error::Error for ExpectationError {}
+
+#[derive(Debug)]
+struct ScenarioEntry {
+    desc: String,
+    count: usize,
+    minimum: usize
+}
+
+#[derive(Default)]
+struct ScenarioInner {
+    entries: Mutex<Vec<ScenarioEntry>>
+}
+
+#[doc(hidden)]
+pub struct ScenarioHandle {
+    inner: Arc<ScenarioInner>,
+    idx: usize
+}
+
+impl ScenarioHandle {
+    /// Record the current state of the expectation belonging to this handle.
+    pub fn update(&self, desc: String, times: &Times) {
+        let mut entries = self.inner.entries.lock().unwrap();
+        let entry = &mut entries[self.idx];
+        entry.desc = desc;
+        entry.count = times.count();
+        entry.minimum = times.minimum();
+    }
+}
+
+/// Groups expectations from any number of mock objects, so they can all be
+/// verified together.
+///
+/// Expectations are added to a `Scenario` with their `in_scenario` method.
+/// Such an expectation is no longer verified when its mock object is dropped
+/// or checkpointed.  Instead, [`Scenario::verify`] checks every expectation in
+/// the scenario at once, and reports all of the unsatisfied ones in a single
+/// panic message.  If `verify` is never called, the `Scenario` verifies itself
+/// when it is dropped.
+///
+/// A `Scenario` also owns a [`Sequence`], for expectations that must be called
+/// in a particular order across all of the scenario's mocks.
+///
+/// # Examples
+/// ```
+/// # use mockall::*;
+/// #[automock]
+/// trait Foo {
+///     fn foo(&self) -> u32;
+/// }
+/// #[automock]
+/// trait Bar {
+///     fn bar(&self, x: u32);
+/// }
+///
+/// let mut scenario = Scenario::new();
+/// let mut foo = MockFoo::new();
+/// let mut bar = MockBar::new();
+/// foo.expect_foo()
+///     .times(1)
+///     .in_scenario(&mut scenario)
+///     .in_sequence(scenario.sequence())
+///     .return_const(42u32);
+/// bar.expect_bar()
+///     .with(predicate::eq(42))
+///     .times(1)
+///     .in_scenario(&mut scenario)
+///     .in_sequence(scenario.sequence())
+///     .return_const(());
+///
+/// bar.bar(foo.foo());
+/// scenario.verify();
+/// ```
+///
+/// Every unsatisfied expectation is listed when verification fails.
+/// ```should_panic(expected = "2 unsatisfied expectation(s)")
+/// # use mockall::*;
+/// #[automock]
+/// trait Foo {
+///     fn foo(&self);
+///     fn bar(&self);
+/// }
+/// let mut scenario = Scenario::new();
+/// let mut mock0 = MockFoo::new();
+/// let mut mock1 = MockFoo::new();
+/// mock0.expect_foo().times(1).in_scenario(&mut scenario);
+/// mock1.expect_bar().times(2).in_scenario(&mut scenario);
+/// scenario.verify();   // panics!
+/// ```
+#[derive(Default)]
+pub struct Scenario {
+    inner: Arc<ScenarioInner>,
+    seq: Sequence,
+    /// Has `verify` been called?  If so, don't verify again on drop.
+    verified: AtomicBool
+}
+
+impl Scenario {
+    /// Create a new empty [`Scenario`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Not for public consumption, but it must be public so the generated code
+    /// can call it.
+    #[doc(hidden)]
+    pub fn next_handle(&mut self) -> ScenarioHandle {
+        let mut entries = self.inner.entries.lock().unwrap();
+        entries.push(ScenarioEntry {
+            desc: String::new(),
+            count: 0,
+            minimum: 0
+        });
+        ScenarioHandle{inner: self.inner.clone(), idx: entries.len() - 1}
+    }
+
+    /// The [`Sequence`] shared by all of this scenario's expectations.
+    pub fn sequence(&mut self) -> &mut Sequence {
+        &mut self.seq
+    }
+
+    /// Verify that every expectation in the scenario has been satisfied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any expectation was called fewer times than required.  The
+    /// panic message lists every such expectation.
+    pub fn verify(&self) {
+        self.verified.store(true, Ordering::Relaxed);
+        let failures = self.inner.entries.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter(|e| e.count < e.minimum)
+            .map(|e| {
+                let error = ExpectationError {
+                    desc: e.desc.clone(),
+                    count: e.count,
+                    minimum: e.minimum
+                };
+                format!("\n    {}", error)
+            }).collect::<Vec<_>>();
+        if !failures.is_empty() {
+            fail_verification(format!(
+                "Scenario has {} unsatisfied expectation(s):{}",
+                failures.len(), failures.concat()));
+        }
+    }
+}
+
+impl Drop for Scenario {
+    fn drop(&mut self) {
+        if !self.verified.load(Ordering::Relaxed) {
+            self.verify();
+        }
+    }
+}
+
+/// A chain of errors, each of which is the
+/// [`source`](std::error::Error::source) of the one before it.  Created by
+/// [`error_chain`].
+///
+/// The chain owns all of its errors.  Dropping it drops each of them in turn,
+/// from the outermost error to the root cause, so every mock error in the
+/// chain has its expectations verified.
+pub struct ErrorChain<E> {
+    error: E,
+    source: Option<Box<ErrorChain<E>>>
+}
+
+impl<E> ErrorChain<E> {
+    /// The outermost error of the chain
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+}
+
+impl<E: Debug> Debug for ErrorChain<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for ErrorChain<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for ErrorChain<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_deref()
+            .map(|source| source as &(dyn std::error::Error + 'static))
+    }
+}
+
+/// Link a list of errors into a chain, for testing code that walks
+/// [`Error::source`](std::error::Error::source).
+///
+/// `errors` runs from the outermost error to the root cause.  Each error's
+/// `Display` and `Debug` impls are used as-is, but its `source` method is not:
+/// the chain supplies the next error instead, and the root cause has no
+/// source.  So the errors needn't have any expectations for `source`.
+///
+/// # Panics
+///
+/// Panics if `errors` is empty.
+///
+/// # Examples
+///
+/// ```
+/// # use mockall::*;
+/// # use std::error::Error;
+/// # use std::fmt::{self, Display, Formatter};
+/// mock! {
+///     #[derive(Debug)]
+///     MyError {}
+///     impl Error for MyError {}
+///     impl Display for MyError {
+///         fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result;
+///     }
+/// }
+///
+/// # fn main() {
+/// let errors = ["outer", "inner"].iter().map(|msg| {
+///     let mut e = MockMyError::new();
+///     e.expect_fmt().returning(move |f| f.write_str(msg));
+///     e
+/// }).collect::<Vec<_>>();
+/// let e = error_chain(errors);
+/// assert_eq!("outer", e.to_string());
+/// assert_eq!("inner", e.source().unwrap().to_string());
+/// assert!(e.source().unwrap().source().is_none());
+/// # }
+/// ```
+pub fn error_chain<E>(errors: Vec<E>) -> ErrorChain<E>
+    where E: std::error::Error + 'static
+{
+    let mut chain: Option<ErrorChain<E>> = None;
+    for error in errors.into_iter().rev() {
+        let source = chain.take().map(Box::new);
+        chain = Some(ErrorChain { error, source });
+    }
+    chain.expect("error_chain requires an error")
+}
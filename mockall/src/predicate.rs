@@ -0,0 +1,316 @@
+// vim: tw=80
+//! Predicate functions for matching arguments.
+//!
+//! This module re-exports all of the predicate functions from the
+//! [`predicates`](https://docs.rs/predicates) crate, and adds a few more for
+//! argument types that they handle awkwardly.
+
+pub use predicates::prelude::predicate::*;
+
+use predicates::{
+    boolean::{AndPredicate, NotPredicate, OrPredicate},
+    function::FnPredicate,
+    reflection::PredicateReflection
+};
+use std::{borrow::Borrow, fmt, marker::PhantomData, ops::RangeBounds};
+
+use crate::{Predicate, PredicateBooleanExt};
+
+pub mod slice;
+
+/// Predicate that compares a string argument to an owned `String`.
+///
+/// This is created by [`eq_str`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StrEqPredicate {
+    expected: String,
+}
+
+impl<P: Borrow<str> + ?Sized> Predicate<P> for StrEqPredicate {
+    fn eval(&self, variable: &P) -> bool {
+        variable.borrow() == self.expected
+    }
+}
+
+impl PredicateReflection for StrEqPredicate {}
+
+impl fmt::Display for StrEqPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "var == {:?}", self.expected)
+    }
+}
+
+/// Create a predicate that matches any string type equal to `expected`.
+///
+/// Unlike [`eq`], the same predicate works for `&str`, `String`, and
+/// `Cow<str>` arguments, and `expected` needn't be `'static`, because it's
+/// copied into the predicate.
+///
+/// # Examples
+///
+/// ```
+/// # use mockall::*;
+/// # use mockall::predicate::*;
+/// #[automock]
+/// trait Foo {
+///     fn foo(&self, x: &str);
+///     fn bar(&self, x: String);
+/// }
+///
+/// let name = String::from("hello");
+/// let mut mock = MockFoo::new();
+/// mock.expect_foo()
+///     .with(eq_str(&name))
+///     .return_const(());
+/// mock.expect_bar()
+///     .with(eq_str("hello"))
+///     .return_const(());
+///
+/// mock.foo("hello");
+/// mock.bar(name);
+/// ```
+pub fn eq_str<S: AsRef<str>>(expected: S) -> StrEqPredicate {
+    StrEqPredicate { expected: expected.as_ref().to_owned() }
+}
+
+/// Predicate that checks whether a value lies within a range.
+///
+/// This is created by [`in_range`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InRangePredicate<R> {
+    range: R,
+}
+
+impl<R, T> Predicate<T> for InRangePredicate<R>
+    where R: RangeBounds<T> + fmt::Debug,
+          T: PartialOrd + ?Sized
+{
+    fn eval(&self, variable: &T) -> bool {
+        self.range.contains(variable)
+    }
+}
+
+impl<R: fmt::Debug> PredicateReflection for InRangePredicate<R> {}
+
+impl<R: fmt::Debug> fmt::Display for InRangePredicate<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "var in {:?}", self.range)
+    }
+}
+
+/// Create a predicate that matches values within `range`.
+///
+/// Any kind of range works, like `1..=10`, `..5`, or `x..`.  For one-sided
+/// comparisons, [`gt`], [`ge`], [`lt`], and [`le`] read equally well.
+///
+/// # Examples
+///
+/// ```
+/// # use mockall::*;
+/// # use mockall::predicate::*;
+/// use std::time::Duration;
+///
+/// #[automock]
+/// trait Foo {
+///     fn sleep(&self, timeout: Duration);
+/// }
+///
+/// # fn main() {
+/// let mut mock = MockFoo::new();
+/// mock.expect_sleep()
+///     .with(in_range(Duration::from_millis(100)..=Duration::from_millis(500)))
+///     .return_const(());
+///
+/// mock.sleep(Duration::from_millis(250));
+/// # }
+/// ```
+pub fn in_range<R: fmt::Debug>(range: R) -> InRangePredicate<R> {
+    InRangePredicate { range }
+}
+
+/// Create a predicate that matches one argument with a closure.
+///
+/// This is a shorter name for [`function`].  Unlike
+/// [`withf`](crate::examples::__mock_MockFoo_Foo::__foo::Expectation::withf),
+/// which checks all of a method's arguments with a single closure, `func`
+/// checks just one, so it can be mixed with other predicates in a `with` call.
+/// Give it a name with [`FnPredicate::fn_name`] to improve failure messages.
+///
+/// # Examples
+///
+/// ```
+/// # use mockall::*;
+/// # use mockall::predicate::*;
+/// #[automock]
+/// trait Foo {
+///     fn foo(&self, x: u32, name: &str);
+/// }
+///
+/// let mut mock = MockFoo::new();
+/// mock.expect_foo()
+///     .with(func(|x: &u32| x % 2 == 0).fn_name("is_even"), eq_str("bob"))
+///     .return_const(());
+///
+/// mock.foo(4, "bob");
+/// ```
+pub fn func<F, T>(f: F) -> FnPredicate<F, T>
+    where F: Fn(&T) -> bool,
+          T: ?Sized
+{
+    function(f)
+}
+
+/// Create a predicate that matches when both `a` and `b` match.
+///
+/// This is the same as [`PredicateBooleanExt::and`], but reads better when
+/// composing several conditions inside of a `with` call.
+///
+/// # Examples
+///
+/// ```
+/// # use mockall::*;
+/// # use mockall::predicate::*;
+/// #[automock]
+/// trait Foo {
+///     fn foo(&self, x: u32);
+/// }
+///
+/// let mut mock = MockFoo::new();
+/// mock.expect_foo()
+///     .with(and(gt(10), not(eq(42))))
+///     .return_const(());
+///
+/// mock.foo(41);
+/// ```
+pub fn and<A, B, Item>(a: A, b: B) -> AndPredicate<A, B, Item>
+    where A: Predicate<Item>,
+          B: Predicate<Item>,
+          Item: ?Sized
+{
+    a.and(b)
+}
+
+/// Create a predicate that matches when either `a` or `b` matches.
+///
+/// More than two alternatives can be had by nesting, or with [`in_iter`].
+///
+/// # Examples
+///
+/// ```
+/// # use mockall::*;
+/// # use mockall::predicate::*;
+/// let p = or(lt(0), or(eq(7), gt(100)));
+/// assert!(p.eval(&-1));
+/// assert!(p.eval(&7));
+/// assert!(!p.eval(&8));
+/// ```
+pub fn or<A, B, Item>(a: A, b: B) -> OrPredicate<A, B, Item>
+    where A: Predicate<Item>,
+          B: Predicate<Item>,
+          Item: ?Sized
+{
+    a.or(b)
+}
+
+/// Create a predicate that matches whenever `p` does not.
+///
+/// # Examples
+///
+/// ```
+/// # use mockall::*;
+/// # use mockall::predicate::*;
+/// let p = not(eq_str("hello"));
+/// assert!(p.eval("goodbye"));
+/// assert!(!p.eval("hello"));
+/// ```
+pub fn not<P, Item>(p: P) -> NotPredicate<P, Item>
+    where P: Predicate<Item>,
+          Item: ?Sized
+{
+    p.not()
+}
+
+/// A reusable argument matcher.
+///
+/// This is a simpler alternative to implementing [`Predicate`] directly.
+/// Wrap an `ArgMatcher` with [`matcher`] to pass it to an expectation's
+/// `with` method.
+///
+/// # Examples
+///
+/// ```
+/// # use mockall::*;
+/// # use mockall::predicate::*;
+/// /// Matches a 32-digit lowercase hex string, like a simple UUID.
+/// struct IsSimpleUuid;
+///
+/// impl ArgMatcher<str> for IsSimpleUuid {
+///     fn matches(&self, arg: &str) -> bool {
+///         arg.len() == 32 && arg.chars().all(|c| "0123456789abcdef".contains(c))
+///     }
+///
+///     fn describe(&self) -> String {
+///         String::from("var is a simple UUID")
+///     }
+/// }
+///
+/// #[automock]
+/// trait Foo {
+///     fn foo(&self, id: &str);
+/// }
+///
+/// let mut mock = MockFoo::new();
+/// mock.expect_foo()
+///     .with(matcher(IsSimpleUuid))
+///     .return_const(());
+///
+/// mock.foo("936da01f9abd4d9d80c702af85c822a8");
+/// ```
+pub trait ArgMatcher<T: ?Sized> {
+    /// Does `arg` satisfy this matcher?
+    fn matches(&self, arg: &T) -> bool;
+
+    /// Describe what this matcher accepts.  It's used in the panic message
+    /// when no expectation matches a call.
+    fn describe(&self) -> String;
+}
+
+/// Predicate that delegates to an [`ArgMatcher`].
+///
+/// This is created by [`matcher`].
+pub struct ArgMatcherPredicate<M, T: ?Sized> {
+    matcher: M,
+    _t: PhantomData<fn(&T)>
+}
+
+impl<M, T> Predicate<T> for ArgMatcherPredicate<M, T>
+    where M: ArgMatcher<T>,
+          T: ?Sized
+{
+    fn eval(&self, variable: &T) -> bool {
+        self.matcher.matches(variable)
+    }
+}
+
+impl<M, T> PredicateReflection for ArgMatcherPredicate<M, T>
+    where M: ArgMatcher<T>,
+          T: ?Sized
+{}
+
+impl<M, T> fmt::Display for ArgMatcherPredicate<M, T>
+    where M: ArgMatcher<T>,
+          T: ?Sized
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.matcher.describe())
+    }
+}
+
+/// Turn an [`ArgMatcher`] into a [`Predicate`], suitable for an expectation's
+/// `with` method.
+pub fn matcher<M, T>(matcher: M) -> ArgMatcherPredicate<M, T>
+    where M: ArgMatcher<T>,
+          T: ?Sized
+{
+    ArgMatcherPredicate { matcher, _t: PhantomData }
+}
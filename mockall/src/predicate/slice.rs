@@ -0,0 +1,153 @@
+// vim: tw=80
+//! Predicates for slice arguments, like `&[u8]`.
+//!
+//! A method's reference arguments are matched by value, so an argument of type
+//! `&[T]` needs a `Predicate<[T]>`.  These predicates implement exactly that.
+//!
+//! # Examples
+//!
+//! ```
+//! # use mockall::*;
+//! use mockall::predicate::slice;
+//!
+//! #[automock]
+//! trait Writer {
+//!     fn write(&mut self, buf: &[u8]) -> usize;
+//! }
+//!
+//! let mut mock = MockWriter::new();
+//! mock.expect_write()
+//!     .with(slice::eq(b"hello"))
+//!     .return_const(5usize);
+//! mock.expect_write()
+//!     .with(slice::contains(b'\n'))
+//!     .returning(|buf| buf.len());
+//! mock.expect_write()
+//!     .with(slice::len(0))
+//!     .return_const(0usize);
+//!
+//! assert_eq!(5, mock.write(b"hello"));
+//! assert_eq!(3, mock.write(b"a\nb"));
+//! assert_eq!(0, mock.write(b""));
+//! ```
+
+use predicates::reflection::PredicateReflection;
+use std::fmt::{self, Debug};
+
+use crate::Predicate;
+
+/// Predicate that checks a slice's contents against an expected value.
+///
+/// This is created by [`eq`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EqPredicate<T> {
+    expected: Vec<T>,
+}
+
+impl<T: Debug + PartialEq> Predicate<[T]> for EqPredicate<T> {
+    fn eval(&self, variable: &[T]) -> bool {
+        variable == &self.expected[..]
+    }
+}
+
+impl<T: Debug> PredicateReflection for EqPredicate<T> {}
+
+impl<T: Debug> fmt::Display for EqPredicate<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "var == {:?}", self.expected)
+    }
+}
+
+/// Create a predicate that matches a slice with the same contents as
+/// `expected`.
+///
+/// # Examples
+///
+/// ```
+/// # use mockall::*;
+/// use mockall::predicate::slice;
+///
+/// let p = slice::eq(&[1, 2, 3]);
+/// assert!(p.eval(&[1, 2, 3][..]));
+/// assert!(!p.eval(&[1, 2][..]));
+/// ```
+pub fn eq<T: Clone + Debug + PartialEq>(expected: &[T]) -> EqPredicate<T> {
+    EqPredicate { expected: expected.to_vec() }
+}
+
+/// Predicate that checks a slice's length.
+///
+/// This is created by [`len`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LenPredicate {
+    len: usize,
+}
+
+impl<T> Predicate<[T]> for LenPredicate {
+    fn eval(&self, variable: &[T]) -> bool {
+        variable.len() == self.len
+    }
+}
+
+impl PredicateReflection for LenPredicate {}
+
+impl fmt::Display for LenPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "var.len() == {}", self.len)
+    }
+}
+
+/// Create a predicate that matches a slice of exactly `len` elements.
+///
+/// # Examples
+///
+/// ```
+/// # use mockall::*;
+/// use mockall::predicate::slice;
+///
+/// let p = slice::len(2);
+/// assert!(p.eval(&[1, 2][..]));
+/// assert!(!p.eval(&[1, 2, 3][..]));
+/// ```
+pub fn len(len: usize) -> LenPredicate {
+    LenPredicate { len }
+}
+
+/// Predicate that checks whether a slice contains an element.
+///
+/// This is created by [`contains`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContainsPredicate<T> {
+    element: T,
+}
+
+impl<T: Debug + PartialEq> Predicate<[T]> for ContainsPredicate<T> {
+    fn eval(&self, variable: &[T]) -> bool {
+        variable.contains(&self.element)
+    }
+}
+
+impl<T: Debug> PredicateReflection for ContainsPredicate<T> {}
+
+impl<T: Debug> fmt::Display for ContainsPredicate<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "var.contains({:?})", self.element)
+    }
+}
+
+/// Create a predicate that matches a slice with at least one element equal to
+/// `element`.
+///
+/// # Examples
+///
+/// ```
+/// # use mockall::*;
+/// use mockall::predicate::slice;
+///
+/// let p = slice::contains(3);
+/// assert!(p.eval(&[1, 2, 3][..]));
+/// assert!(!p.eval(&[4, 5][..]));
+/// ```
+pub fn contains<T: Debug + PartialEq>(element: T) -> ContainsPredicate<T> {
+    ContainsPredicate { element }
+}
@@ -0,0 +1,101 @@
+// vim: tw=80
+//! Integration with [`proptest`](https://docs.rs/proptest).
+//!
+//! Property-based tests often need to check that a mock object is only called
+//! with arguments that satisfy some invariant.  Writing that invariant as a
+//! [`Predicate`](crate::Predicate) lets the same value serve as an
+//! expectation's `with` matcher and, through [`matching`], as a constraint on
+//! the generated inputs.
+//!
+//! The other direction, using an arbitrary `Strategy` as a matcher, is
+//! provided by [`generated_by`].  Because a `Strategy` can't tell whether it
+//! could have produced a given value, the matcher checks the argument against
+//! a finite sample of the strategy's values instead.
+//!
+//! Requires the **proptest** feature.
+//!
+//! # Examples
+//!
+//! ```
+//! # use mockall::*;
+//! use mockall::strategy::matching;
+//! use proptest::prelude::*;
+//!
+//! #[automock]
+//! trait Store {
+//!     fn put(&self, key: u32);
+//! }
+//!
+//! fn store_even(store: &dyn Store, x: u32) {
+//!     store.put(x * 2);
+//! }
+//!
+//! # fn main() {
+//! let even = || predicate::function(|x: &u32| x % 2 == 0);
+//! proptest!(|(x in matching(0u32..1000, even()))| {
+//!     let mut mock = MockStore::new();
+//!     mock.expect_put().with(even()).return_const(());
+//!     store_even(&mock, x);
+//! });
+//! # }
+//! ```
+
+use crate::Predicate;
+use ::proptest::{
+    strategy::{Strategy, ValueTree},
+    test_runner::TestRunner
+};
+
+/// Restrict a strategy to the values that satisfy a predicate.
+///
+/// This is just [`Strategy::prop_filter`] with the predicate's description
+/// used as the rejection reason.  As with any filter, the predicate should
+/// accept most of the base strategy's values, or else proptest will give up.
+pub fn matching<S, P>(strategy: S, predicate: P)
+    -> impl Strategy<Value = S::Value>
+    where S: Strategy,
+          P: Predicate<S::Value> + 'static
+{
+    let whence = predicate.to_string();
+    strategy.prop_filter(whence, move |v| predicate.eval(v))
+}
+
+/// Create a predicate that matches any of `samples` values generated by a
+/// strategy.
+///
+/// The values are generated once, with a deterministic [`TestRunner`], so the
+/// predicate is only suitable for strategies with a small number of possible
+/// values, like `prop_oneof!` or [`proptest::sample::select`].
+///
+/// # Panics
+///
+/// Panics if the strategy can't generate a value.
+///
+/// # Examples
+///
+/// ```
+/// # use mockall::*;
+/// use mockall::strategy::generated_by;
+/// use proptest::sample::select;
+///
+/// let p = generated_by(select(vec!["GET", "PUT"]), 64);
+/// assert!(p.eval(&"GET"));
+/// assert!(!p.eval(&"POST"));
+/// ```
+pub fn generated_by<S>(strategy: S, samples: usize)
+    -> impl Predicate<S::Value>
+    where S: Strategy,
+          S::Value: PartialEq + std::fmt::Debug + 'static
+{
+    let mut runner = TestRunner::deterministic();
+    let mut values: Vec<S::Value> = Vec::new();
+    for _ in 0..samples {
+        let v = strategy.new_tree(&mut runner)
+            .expect("Strategy failed to generate a value")
+            .current();
+        if !values.contains(&v) {
+            values.push(v);
+        }
+    }
+    crate::predicate::in_iter(values)
+}
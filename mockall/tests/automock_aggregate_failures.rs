@@ -0,0 +1,61 @@
+// vim: tw=80
+//! When several expectations fail verification, a single panic message reports
+//! all of them.
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32) -> u32;
+    fn bar(&self);
+}
+
+#[test]
+#[should_panic(expected = "2 verification failure(s):\n    MockFoo::foo: Expectation(var == 1) called 0 time(s) which is fewer than expected 1\n    MockFoo::bar: Expectation(<anything>) called 1 time(s) which is fewer than expected 2")]
+fn checkpoint() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(predicate::eq(1))
+        .times(1)
+        .return_const(0u32);
+    mock.expect_bar()
+        .times(2)
+        .return_const(());
+    mock.bar();
+    mock.checkpoint();
+}
+
+#[test]
+#[should_panic(expected = "2 verification failure(s):\n    MockFoo::foo: Expectation(<anything>) called 0 time(s) which is fewer than expected 1\n    MockFoo::bar: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+fn drop() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .times(1)
+        .return_const(0u32);
+    mock.expect_bar()
+        .times(1)
+        .return_const(());
+}
+
+#[test]
+#[should_panic(expected = "2 verification failure(s):\n    MockFoo::bar: Expectation(<anything>) called 0 time(s) which is fewer than expected 1\n    unexpected call: MockFoo::foo(5)")]
+fn unexpected_calls() {
+    let mut mock = MockFoo::new_recording();
+    mock.expect_bar()
+        .times(1)
+        .return_const(());
+    mock.foo(5);
+}
+
+/// A single failure is reported just like it always was
+#[test]
+#[should_panic(expected = "MockFoo::bar: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+fn one() {
+    let mut mock = MockFoo::new();
+    mock.expect_bar()
+        .times(1)
+        .return_const(());
+    mock.expect_foo()
+        .return_const(0u32);
+}
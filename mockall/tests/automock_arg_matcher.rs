@@ -0,0 +1,79 @@
+// vim: tw=80
+//! user-defined ArgMatchers
+#![deny(warnings)]
+
+use mockall::*;
+use mockall::predicate::*;
+
+struct Even;
+
+impl ArgMatcher<u32> for Even {
+    fn matches(&self, arg: &u32) -> bool {
+        arg % 2 == 0
+    }
+
+    fn describe(&self) -> String {
+        String::from("var is even")
+    }
+}
+
+/// A generic matcher works for any argument type that satisfies its bounds.
+struct Nonempty;
+
+impl<T: AsRef<str> + ?Sized> ArgMatcher<T> for Nonempty {
+    fn matches(&self, arg: &T) -> bool {
+        !arg.as_ref().is_empty()
+    }
+
+    fn describe(&self) -> String {
+        String::from("var is not empty")
+    }
+}
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32, y: &str);
+    fn bar(&self, s: String);
+}
+
+#[test]
+fn combined() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(not(matcher(Even)), always())
+        .return_const(());
+    mock.foo(3, "");
+}
+
+#[test]
+fn generic() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(always(), matcher(Nonempty))
+        .return_const(());
+    mock.expect_bar()
+        .with(matcher(Nonempty))
+        .return_const(());
+    mock.foo(0, "x");
+    mock.bar(String::from("x"));
+}
+
+#[test]
+fn ok() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(matcher(Even), always())
+        .return_const(());
+    mock.foo(4, "");
+}
+
+#[test]
+#[should_panic(expected =
+    "Expectation(var is even, true) rejected argument `x`")]
+fn fail() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(matcher(Even), always())
+        .return_const(());
+    mock.foo(5, "");
+}
@@ -0,0 +1,32 @@
+// vim: tw=80
+//! automock can substitute associated types used in generic methods' bounds
+//! and where clauses
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock(type Item = u32; type Key = String;)]
+pub trait Foo {
+    type Item;
+    type Key;
+    fn get<Q: Into<Self::Key> + 'static>(&self, q: Q) -> Option<Self::Item>;
+    fn put<V>(&mut self, k: Self::Key, v: V)
+        where V: Into<Self::Item> + 'static;
+}
+
+#[test]
+fn bound() {
+    let mut mock = MockFoo::new();
+    mock.expect_get::<&'static str>()
+        .returning(|q| if q == "x" { Some(5) } else { None });
+    assert_eq!(Some(5), mock.get("x"));
+}
+
+#[test]
+fn where_clause() {
+    let mut mock = MockFoo::new();
+    mock.expect_put::<u16>()
+        .withf(|k, v| k == "x" && *v == 6)
+        .return_const(());
+    mock.put("x".to_owned(), 6u16);
+}
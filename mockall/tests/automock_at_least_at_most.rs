@@ -0,0 +1,112 @@
+// vim: tw=80
+//! Expectations with only a minimum or only a maximum number of calls
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+trait Foo {
+    fn foo(&self) -> u32;
+    fn bar() -> u32;
+}
+
+#[test]
+fn at_least_ok() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .at_least(2)
+        .return_const(0u32);
+    for _ in 0..5 {
+        mock.foo();
+    }
+}
+
+#[test]
+#[should_panic(expected =
+    "MockFoo::foo: Expectation(<anything>) called 1 time(s) which is fewer than expected 2")]
+fn at_least_too_few() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .at_least(2)
+        .return_const(0u32);
+    mock.foo();
+}
+
+#[test]
+fn at_most_ok() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .at_most(2)
+        .return_const(0u32);
+    mock.foo();
+    mock.foo();
+}
+
+#[test]
+#[should_panic(expected =
+    "MockFoo::foo: Expectation(<anything>) called 3 times which is more than the expected 2")]
+fn at_most_too_many() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .at_most(2)
+        .return_const(0u32);
+    mock.foo();
+    mock.foo();
+    mock.foo();
+}
+
+#[test]
+#[should_panic(expected =
+    "MockFoo::foo: Expectation(<anything>) called 1 time(s) which is fewer than expected 2")]
+fn both_too_few() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .at_least(2)
+        .at_most(5)
+        .return_const(0u32);
+    mock.foo();
+}
+
+#[test]
+#[should_panic(expected =
+    "MockFoo::foo: Expectation(<anything>) called 6 times which is more than the expected 5")]
+fn both_too_many() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .at_most(5)
+        .at_least(2)
+        .return_const(0u32);
+    for _ in 0..6 {
+        mock.foo();
+    }
+}
+
+#[test]
+#[should_panic(expected = "Backwards range")]
+fn conflicting() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .at_least(5)
+        .at_most(2);
+}
+
+#[test]
+fn pending() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo_pending()
+        .at_least(1)
+        .at_most(2)
+        .return_const(0u32);
+    mock.foo();
+}
+
+#[test]
+fn static_method() {
+    let ctx = MockFoo::bar_context();
+    ctx.expect()
+        .at_least(1)
+        .at_most(2)
+        .return_const(42u32);
+    assert_eq!(42, MockFoo::bar());
+    ctx.checkpoint();
+}
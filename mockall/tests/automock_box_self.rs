@@ -0,0 +1,51 @@
+// vim: tw=80
+//! Trait methods with receivers like `self: Box<Self>`
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+pub trait Task {
+    fn run(self: Box<Self>, x: u32) -> u32;
+    fn cancel(self: Box<Self>);
+    fn name(&self) -> String;
+}
+
+fn run_all(tasks: Vec<Box<dyn Task>>) -> u32 {
+    tasks.into_iter().map(|t| t.run(1)).sum()
+}
+
+#[test]
+fn consume() {
+    let mut mock = MockTask::new();
+    mock.expect_run()
+        .with(predicate::eq(5))
+        .returning(|x| x + 1);
+    assert_eq!(6, Box::new(mock).run(5));
+}
+
+#[test]
+fn trait_object() {
+    let mut t0 = MockTask::new();
+    t0.expect_run()
+        .return_const(10u32);
+    let mut t1 = MockTask::new();
+    t1.expect_run()
+        .return_const(20u32);
+    assert_eq!(30, run_all(vec![Box::new(t0), Box::new(t1)]));
+}
+
+/// Consuming the mock drops it, which verifies its other expectations.
+#[test]
+#[should_panic(expected =
+    "MockTask::name: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+fn verified_on_drop() {
+    let mut mock = MockTask::new();
+    mock.expect_cancel()
+        .return_const(());
+    mock.expect_name()
+        .times(1)
+        .returning(String::new);
+    let task: Box<dyn Task> = Box::new(mock);
+    task.cancel();
+}
@@ -0,0 +1,104 @@
+// vim: tw=80
+//! A mock object's builder sets many expectations in a single statement
+#![deny(warnings)]
+
+use mockall::*;
+use mockall::predicate::*;
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32) -> u32;
+    fn bar(&self);
+    fn baz<T: 'static>(&self, t: T) -> i16;
+    fn sfoo() -> u32;
+}
+
+trait Bean {
+    fn bean(&self) -> String;
+}
+
+mock! {
+    Generic<T: 'static> {
+        fn get(&self) -> T;
+    }
+    impl Bean for Generic<u32> {
+        fn bean(&self) -> String;
+    }
+}
+
+/// The builder of `MockFoo` mustn't collide with the mock of `FooBuilder`
+#[automock]
+trait FooBuilder {
+    fn finish(&self) -> u32;
+}
+
+mock! {
+    Built {
+        fn builder() -> u32;
+        fn foo(&self);
+    }
+}
+
+#[test]
+fn builder() {
+    let mock = MockFoo::builder()
+        .expect_foo(|e| e.with(eq(1)).return_const(2u32))
+        .expect_foo(|e| e.with(eq(3)).return_const(4u32))
+        .expect_bar(|e| e.times(1).return_const(()))
+        .build();
+    assert_eq!(2, mock.foo(1));
+    assert_eq!(4, mock.foo(3));
+    mock.bar();
+}
+
+#[test]
+#[should_panic(expected =
+    "MockFoo::bar: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+fn unsatisfied() {
+    let _mock = MockFoo::builder()
+        .expect_bar(|e| e.times(1).return_const(()))
+        .build();
+}
+
+#[test]
+fn generic_method() {
+    let mock = MockFoo::builder()
+        .expect_baz::<u8, _>(|e| e.return_const(-1i16))
+        .build();
+    assert_eq!(-1, mock.baz(5u8));
+}
+
+#[test]
+fn generic_struct() {
+    let mock = MockGeneric::<u32>::builder()
+        .expect_get(|e| e.return_const(5u32))
+        .expect_bean(|e| e.returning(|| String::from("pinto")))
+        .build();
+    assert_eq!(5, mock.get());
+    assert_eq!("pinto", mock.bean());
+}
+
+#[test]
+fn builder_trait() {
+    let mock = MockFooBuilder::builder()
+        .expect_finish(|e| e.return_const(3u32))
+        .build();
+    assert_eq!(3, mock.finish());
+}
+
+/// A mock with a `builder` method of its own gets no builder
+#[test]
+fn conflicting_name() {
+    let ctx = MockBuilt::builder_context();
+    ctx.expect().return_const(42u32);
+    assert_eq!(42, MockBuilt::builder());
+}
+
+/// Static methods still use contexts
+#[test]
+fn static_method() {
+    let ctx = MockFoo::sfoo_context();
+    ctx.expect().return_const(6u32);
+    let _mock = MockFoo::builder().build();
+    assert_eq!(6, MockFoo::sfoo());
+}
@@ -0,0 +1,68 @@
+// vim: tw=80
+//! A default expectation handles any call that no other expectation does
+#![deny(warnings)]
+
+use mockall::*;
+use mockall::predicate::*;
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32) -> u32;
+    fn bar(x: u32) -> u32;
+}
+
+#[test]
+fn fallback() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .by_default()
+        .return_const(0u32);
+    mock.expect_foo()
+        .with(eq(4))
+        .times(1)
+        .return_const(42u32);
+    assert_eq!(0, mock.foo(1));
+    assert_eq!(42, mock.foo(4));
+    assert_eq!(0, mock.foo(2));
+    assert_eq!(0, mock.foo(4));
+}
+
+#[test]
+fn never_called() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .times(1)
+        .return_const(1u32);
+    mock.expect_foo()
+        .by_default()
+        .return_const(0u32);
+    assert_eq!(1, mock.foo(1));
+}
+
+#[test]
+#[should_panic(expected =
+    "MockFoo::foo: Expectation(var == 4) called 0 time(s) which is fewer than expected 1")]
+fn strict_expectation_unsatisfied() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .by_default()
+        .return_const(0u32);
+    mock.expect_foo()
+        .with(eq(4))
+        .times(1)
+        .return_const(42u32);
+    mock.foo(1);
+}
+
+#[test]
+fn static_method() {
+    let ctx = MockFoo::bar_context();
+    ctx.expect()
+        .by_default()
+        .returning(|x| x);
+    ctx.expect()
+        .with(eq(5))
+        .return_const(50u32);
+    assert_eq!(3, MockFoo::bar(3));
+    assert_eq!(50, MockFoo::bar(5));
+}
@@ -0,0 +1,108 @@
+// vim: tw=80
+//! An expectation can capture the arguments of its calls, for the test to
+//! examine afterwards
+#![deny(warnings)]
+
+use mockall::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Thing(u32);
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32) -> u32;
+    fn bar(&mut self, name: &str, thing: Thing);
+    fn baz(&self);
+    fn slice(&self, v: &[u8]);
+    fn generic<T: Clone + Send + 'static>(&self, t: T);
+}
+
+#[test]
+fn one_arg() {
+    let mut mock = MockFoo::new();
+    let captured = mock.expect_foo()
+        .return_const(0u32)
+        .capture();
+    assert!(captured.is_empty());
+    mock.foo(1);
+    mock.foo(2);
+    assert_eq!(2, captured.len());
+    assert_eq!(Some(2), captured.last());
+    assert_eq!(vec![1, 2], captured.values());
+}
+
+/// Reference arguments are captured as their owned types
+#[test]
+fn several_args() {
+    let mut mock = MockFoo::new();
+    let captured = mock.expect_bar()
+        .return_const(())
+        .capture();
+    mock.bar("a", Thing(1));
+    mock.bar("b", Thing(2));
+    assert_eq!(vec![("a".to_owned(), Thing(1)), ("b".to_owned(), Thing(2))],
+               captured.take());
+    assert!(captured.is_empty());
+}
+
+#[test]
+fn no_args() {
+    let mut mock = MockFoo::new();
+    let captured = mock.expect_baz()
+        .return_const(())
+        .capture();
+    mock.baz();
+    mock.baz();
+    assert_eq!(2, captured.len());
+}
+
+#[test]
+fn slice() {
+    let mut mock = MockFoo::new();
+    let captured = mock.expect_slice()
+        .return_const(())
+        .capture();
+    mock.slice(&[1, 2, 3]);
+    assert_eq!(Some(vec![1, 2, 3]), captured.last());
+}
+
+/// Only calls handled by the capturing expectation are captured
+#[test]
+fn only_matching() {
+    let mut mock = MockFoo::new();
+    let evens = mock.expect_foo()
+        .withf(|x| x % 2 == 0)
+        .return_const(0u32)
+        .capture();
+    let odds = mock.expect_foo()
+        .return_const(1u32)
+        .capture();
+    for i in 0..5 {
+        mock.foo(i);
+    }
+    assert_eq!(vec![0, 2, 4], evens.values());
+    assert_eq!(vec![1, 3], odds.values());
+}
+
+#[test]
+fn generic_method() {
+    let mut mock = MockFoo::new();
+    let captured = mock.expect_generic::<Thing>()
+        .return_const(())
+        .capture();
+    mock.generic(Thing(5));
+    assert_eq!(vec![Thing(5)], captured.values());
+}
+
+/// Captures can be examined from other threads
+#[test]
+fn send() {
+    let mut mock = MockFoo::new();
+    let captured = mock.expect_foo()
+        .return_const(0u32)
+        .capture();
+    mock.foo(7);
+    std::thread::spawn(move || {
+        assert_eq!(vec![7], captured.values());
+    }).join().unwrap();
+}
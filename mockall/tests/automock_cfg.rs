@@ -0,0 +1,64 @@
+// vim: tw=80
+//! Methods guarded by #[cfg()] should produce equally guarded mock methods and
+//! expectations.
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+pub trait Foo {
+    #[cfg(target_os = "multics")]
+    fn foo(&self, x: DoesNotExist) -> i64;
+    #[cfg(not(target_os = "multics"))]
+    fn bar(&self, x: u32) -> i64;
+    #[cfg(target_os = "multics")]
+    fn baz(x: DoesNotExist);
+    #[cfg(not(target_os = "multics"))]
+    fn bean(x: u32) -> u32;
+}
+
+mock! {
+    pub Bar {
+        #[cfg(target_os = "multics")]
+        fn foo(&self, x: DoesNotExist) -> i64;
+        #[cfg(not(target_os = "multics"))]
+        fn bar(&self, x: u32) -> i64;
+    }
+    impl Foo for Bar {
+        #[cfg(target_os = "multics")]
+        fn foo(&self, x: DoesNotExist) -> i64;
+        #[cfg(not(target_os = "multics"))]
+        fn bar(&self, x: u32) -> i64;
+        #[cfg(target_os = "multics")]
+        fn baz(x: DoesNotExist);
+        #[cfg(not(target_os = "multics"))]
+        fn bean(x: u32) -> u32;
+    }
+}
+
+#[test]
+fn automock_method() {
+    let mut mock = MockFoo::new();
+    mock.expect_bar()
+        .returning(i64::from);
+    assert_eq!(4, mock.bar(4));
+}
+
+#[test]
+fn automock_static_method() {
+    let ctx = MockFoo::bean_context();
+    ctx.expect()
+        .returning(|x| x + 1);
+    assert_eq!(5, MockFoo::bean(4));
+}
+
+#[test]
+fn mock_method() {
+    let mut mock = MockBar::new();
+    mock.expect_bar()
+        .returning(|x| i64::from(x) + 1);
+    mock.expect_Foo_bar()
+        .returning(|x| i64::from(x) + 2);
+    assert_eq!(5, mock.bar(4));
+    assert_eq!(6, <MockBar as Foo>::bar(&mock, 4));
+}
@@ -0,0 +1,76 @@
+// vim: tw=80
+//! checkpoint should work for every kind of method, not just the simplest
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+pub trait Foo {
+    fn foo(&self, x: u32) -> u32;
+    fn generic<T: 'static>(&self, t: T);
+    fn refer(&self) -> &u32;
+    fn refmut(&mut self) -> &mut u32;
+}
+
+#[test]
+fn phases() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(predicate::eq(1))
+        .times(1)
+        .return_const(10u32);
+    mock.foo(1);
+    mock.checkpoint();
+
+    mock.expect_foo()
+        .with(predicate::eq(2))
+        .times(2)
+        .return_const(20u32);
+    mock.foo(2);
+    mock.foo(2);
+    mock.checkpoint();
+}
+
+#[test]
+#[should_panic(expected =
+    "MockFoo::generic: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+fn generic_method() {
+    let mut mock = MockFoo::new();
+    mock.expect_generic::<u16>()
+        .times(1)
+        .return_const(());
+    mock.checkpoint();
+}
+
+#[test]
+#[should_panic(expected =
+    "MockFoo::refer: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+fn return_ref() {
+    let mut mock = MockFoo::new();
+    mock.expect_refer()
+        .times(1)
+        .return_const(5u32);
+    mock.checkpoint();
+}
+
+#[test]
+#[should_panic(expected =
+    "MockFoo::refmut: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+fn return_refmut() {
+    let mut mock = MockFoo::new();
+    mock.expect_refmut()
+        .times(1)
+        .return_var(5u32);
+    mock.checkpoint();
+}
+
+#[test]
+#[should_panic(expected = "No matching expectation found")]
+fn generic_method_clears() {
+    let mut mock = MockFoo::new();
+    mock.expect_generic::<u16>()
+        .return_const(());
+    mock.generic(1u16);
+    mock.checkpoint();
+    mock.generic(2u16);
+}
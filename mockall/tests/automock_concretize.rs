@@ -3,12 +3,23 @@
 #![deny(warnings)]
 
 use mockall::*;
+use std::fmt::Display;
 use std::path::{Path, PathBuf};
 
 #[automock]
 trait Foo {
     #[concretize]
     fn foo<P: AsRef<std::path::Path>>(&self, x: P);
+    #[concretize]
+    fn bar<P: AsRef<std::path::Path>>(&self, x: P, mode: u32);
+    #[concretize]
+    fn baz(&self, x: impl AsRef<str>);
+    #[concretize]
+    fn log(&self, msg: impl Display);
+    #[concretize]
+    fn log_ref(&self, msg: &impl Display);
+    #[concretize]
+    fn log_send<T: Display + Send>(&self, msg: T);
 }
 
 #[automock]
@@ -33,6 +44,32 @@ mod generic_arg {
     }
 }
 
+mod impl_trait {
+    use super::*;
+
+    #[test]
+    fn by_ref() {
+        let mut foo = MockFoo::new();
+        foo.expect_log_ref()
+            .withf(|msg| msg.to_string() == "42")
+            .times(2)
+            .return_const(());
+        foo.log_ref(&42);
+        foo.log_ref(&"42");
+    }
+
+    #[test]
+    fn by_value() {
+        let mut foo = MockFoo::new();
+        foo.expect_log()
+            .withf(|msg| msg.to_string() == "hello")
+            .times(2)
+            .return_const(());
+        foo.log("hello");
+        foo.log(String::from("hello"));
+    }
+}
+
 mod module {
     use super::*;
 
@@ -48,3 +85,69 @@ mod module {
         mock_mymod::bang("/tmp");
     }
 }
+
+mod multiple_bounds {
+    use super::*;
+
+    #[test]
+    fn withf() {
+        let mut foo = MockFoo::new();
+        foo.expect_log_send()
+            .withf(|msg| msg.to_string() == "7")
+            .times(2)
+            .return_const(());
+        foo.log_send(7u8);
+        foo.log_send("7");
+    }
+}
+
+mod with_as_ref {
+    use super::*;
+
+    #[test]
+    fn impl_trait() {
+        let mut foo = MockFoo::new();
+        foo.expect_baz()
+            .with_as_ref(predicate::str::starts_with("abc"))
+            .times(2)
+            .return_const(());
+        foo.baz("abcdef");
+        foo.baz(String::from("abc"));
+    }
+
+    #[test]
+    fn mixed_args() {
+        let mut foo = MockFoo::new();
+        foo.expect_bar()
+            .with_as_ref(predicate::eq(Path::new("/tmp")), predicate::eq(4))
+            .times(2)
+            .return_const(());
+        foo.expect_bar()
+            .return_const(());
+        foo.bar("/tmp", 4);
+        foo.bar(PathBuf::from("/tmp"), 4);
+        foo.bar("/tmp", 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "MockFoo::foo(?): No matching expectation found")]
+    fn no_match() {
+        let mut foo = MockFoo::new();
+        foo.expect_foo()
+            .with_as_ref(predicate::eq(Path::new("/tmp")))
+            .return_const(());
+        foo.foo("/usr");
+    }
+
+    #[test]
+    fn ok() {
+        let mut foo = MockFoo::new();
+        foo.expect_foo()
+            .with_as_ref(predicate::eq(Path::new("/tmp")))
+            .times(3)
+            .return_const(());
+        foo.foo(Path::new("/tmp"));
+        foo.foo(PathBuf::from(Path::new("/tmp")));
+        foo.foo("/tmp");
+    }
+}
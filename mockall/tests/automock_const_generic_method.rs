@@ -0,0 +1,75 @@
+// vim: tw=80
+//! Methods with const generic parameters.  Each value of the parameter gets its
+//! own expectations.
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+pub trait Reader {
+    fn read<const N: usize>(&self) -> [u8; N];
+    fn fill<T: Copy + 'static, const N: usize>(&self, t: T) -> [T; N];
+    fn sread<const N: usize>() -> [u8; N];
+}
+
+pub trait Writer {
+    fn write<const N: usize>(&mut self, buf: [u8; N]) -> usize;
+}
+
+mock! {
+    pub Device {
+        fn peek<const N: usize>(&self, offset: usize) -> [u8; N];
+    }
+    impl Writer for Device {
+        fn write<const N: usize>(&mut self, buf: [u8; N]) -> usize;
+    }
+}
+
+#[test]
+fn separate_values() {
+    let mut mock = MockReader::new();
+    mock.expect_read::<2>().return_const([1, 2]);
+    mock.expect_read::<3>().return_const([3, 4, 5]);
+    assert_eq!([1, 2], mock.read::<2>());
+    assert_eq!([3, 4, 5], mock.read::<3>());
+}
+
+#[test]
+#[should_panic(expected =
+    "No expectations were set for generic parameters `[(); 4]`")]
+fn other_value() {
+    let mut mock = MockReader::new();
+    mock.expect_read::<2>().return_const([1, 2]);
+    mock.read::<4>();
+}
+
+#[test]
+fn with_type_param() {
+    let mut mock = MockReader::new();
+    mock.expect_fill::<u16, 2>()
+        .returning(|t| [t; 2]);
+    mock.expect_fill::<u16, 3>()
+        .returning(|t| [t + 1; 3]);
+    assert_eq!([7, 7], mock.fill::<u16, 2>(7));
+    assert_eq!([8, 8, 8], mock.fill::<u16, 3>(7));
+}
+
+#[test]
+fn static_method() {
+    let ctx = MockReader::sread_context();
+    ctx.expect::<1>().return_const([9]);
+    assert_eq!([9], MockReader::sread::<1>());
+}
+
+#[test]
+fn mock_macro() {
+    let mut mock = MockDevice::new();
+    mock.expect_peek::<2>()
+        .withf(|offset| *offset == 4)
+        .return_const([0xa, 0xb]);
+    mock.expect_write::<3>()
+        .with(predicate::eq([1, 2, 3]))
+        .return_const(3usize);
+    assert_eq!([0xa, 0xb], mock.peek::<2>(4));
+    assert_eq!(3, mock.write([1, 2, 3]));
+}
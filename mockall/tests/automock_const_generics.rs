@@ -0,0 +1,69 @@
+// vim: tw=80
+//! Mock structs and traits with const generic parameters
+#![deny(warnings)]
+
+use mockall::*;
+
+pub struct Buffer<const N: usize> {
+    _data: [u8; N]
+}
+
+#[automock]
+impl<const N: usize> Buffer<N> {
+    pub fn get(&self, _i: usize) -> u8 {
+        unimplemented!()
+    }
+    pub fn capacity() -> usize {
+        N
+    }
+}
+
+#[automock]
+pub trait Block<const N: usize> {
+    fn read(&self) -> [u8; N];
+}
+
+pub trait Fill {
+    fn fill(&mut self, x: u8) -> usize;
+}
+
+mock! {
+    pub Ring<T: 'static, const N: usize> {
+        fn len(&self) -> usize;
+    }
+    impl<T: 'static, const N: usize> Fill for Ring<T, N> {
+        fn fill(&mut self, x: u8) -> usize;
+    }
+}
+
+#[test]
+fn method() {
+    let mut mock = MockBuffer::<4>::new();
+    mock.expect_get()
+        .with(predicate::eq(2))
+        .return_const(9u8);
+    assert_eq!(9, mock.get(2));
+}
+
+#[test]
+fn generic_trait() {
+    let mut mock = MockBlock::<3>::new();
+    mock.expect_read().return_const([1, 2, 3]);
+    assert_eq!([1, 2, 3], mock.read());
+}
+
+#[test]
+fn static_method() {
+    let ctx = MockBuffer::<8>::capacity_context();
+    ctx.expect().return_const(8usize);
+    assert_eq!(8, MockBuffer::<8>::capacity());
+}
+
+#[test]
+fn mock_macro() {
+    let mut mock = MockRing::<u32, 16>::new();
+    mock.expect_len().return_const(3usize);
+    mock.expect_fill().returning(|_| 16);
+    assert_eq!(3, mock.len());
+    assert_eq!(16, mock.fill(0));
+}
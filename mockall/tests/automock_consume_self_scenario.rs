@@ -0,0 +1,110 @@
+// vim: tw=80
+//! A mock object consumed by a method that takes self by value can still have
+//! its expectations verified afterwards, by handing them to a Scenario.
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+trait Writer {
+    fn write(&mut self, buf: &[u8]);
+    fn flush(&mut self);
+    fn into_inner(self) -> Vec<u8>;
+}
+
+#[automock]
+trait Gen {
+    fn gen<T: 'static>(&self, t: T);
+}
+
+mock! {
+    Buf {}
+    impl Writer for Buf {
+        fn write(&mut self, buf: &[u8]);
+        fn flush(&mut self);
+        fn into_inner(self) -> Vec<u8>;
+    }
+}
+
+fn finish<W: Writer>(mut w: W) -> Vec<u8> {
+    w.write(b"abc");
+    w.into_inner()
+}
+
+#[test]
+fn ok() {
+    let mut scenario = Scenario::new();
+    let mut mock = MockWriter::new();
+    mock.expect_write().times(1).return_const(());
+    mock.expect_into_inner().times(1).returning(|| vec![1, 2]);
+    mock.in_scenario(&mut scenario);
+    assert_eq!(vec![1, 2], finish(mock));
+    scenario.verify();
+}
+
+#[test]
+#[should_panic(expected = "Scenario has 1 unsatisfied expectation(s):\n    MockWriter::flush: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+fn unsatisfied() {
+    let mut scenario = Scenario::new();
+    let mut mock = MockWriter::new();
+    mock.expect_write().times(1).return_const(());
+    mock.expect_flush().times(1).return_const(());
+    mock.expect_into_inner().times(1).returning(Vec::new);
+    mock.in_scenario(&mut scenario);
+    finish(mock);
+    scenario.verify();
+}
+
+#[test]
+#[should_panic(expected = "Scenario has 1 unsatisfied expectation(s)")]
+fn generic_method() {
+    let mut scenario = Scenario::new();
+    let mut mock = MockGen::new();
+    mock.expect_gen::<u32>().times(2).return_const(());
+    mock.in_scenario(&mut scenario);
+    mock.gen(5u32);
+    drop(mock);
+    scenario.verify();
+}
+
+#[test]
+#[should_panic(expected = "Scenario has 1 unsatisfied expectation(s)")]
+fn mock_struct() {
+    let mut scenario = Scenario::new();
+    let mut mock = MockBuf::new();
+    mock.expect_flush().times(1).return_const(());
+    mock.expect_write().times(1).return_const(());
+    mock.expect_into_inner().times(1).returning(Vec::new);
+    mock.in_scenario(&mut scenario);
+    finish(mock);
+    scenario.verify();
+}
+
+/// Expectations already in a scenario stay in that scenario
+#[test]
+fn already_in_scenario() {
+    let mut scenario0 = Scenario::new();
+    let mut scenario1 = Scenario::new();
+    let mut mock = MockWriter::new();
+    mock.expect_flush().times(1).in_scenario(&mut scenario0).return_const(());
+    mock.expect_into_inner().times(1).returning(Vec::new);
+    mock.in_scenario(&mut scenario1);
+    mock.into_inner();
+    scenario1.verify();
+    // Forget scenario0, so it won't verify itself on drop
+    std::mem::forget(scenario0);
+}
+
+mock! {
+    Stage {
+        fn in_scenario(&self, name: &str) -> bool;
+    }
+}
+
+/// A mocked method named in_scenario replaces the generated one
+#[test]
+fn conflicting_name() {
+    let mut mock = MockStage::new();
+    mock.expect_in_scenario().return_const(true);
+    assert!(mock.in_scenario("act 1"));
+}
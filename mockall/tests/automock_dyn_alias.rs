@@ -0,0 +1,48 @@
+// vim: tw=80
+//! automock can name a mock after a type alias for a trait object
+#![deny(warnings)]
+
+use mockall::*;
+
+pub mod db {
+    use super::*;
+
+    #[automock]
+    pub trait Database {
+        fn get(&self, key: u32) -> Option<String>;
+    }
+
+    #[automock(type Value=String;)]
+    pub trait Store<K: 'static> {
+        type Value: 'static;
+        fn get(&self, key: K) -> Option<Self::Value>;
+    }
+}
+
+#[automock]
+pub type Db = dyn db::Database + Send + Sync;
+
+#[automock]
+pub type Strings = dyn db::Store<u32, Value=String> + Send;
+
+fn lookup(db: &Db, key: u32) -> Option<String> {
+    db.get(key)
+}
+
+#[test]
+fn associated_types() {
+    let mut mock = MockStrings::new();
+    mock.expect_get()
+        .return_const(Some(String::from("x")));
+    let store: Box<Strings> = Box::new(mock);
+    assert_eq!(Some(String::from("x")), store.get(1));
+}
+
+#[test]
+fn returning() {
+    let mut mock = MockDb::new();
+    mock.expect_get()
+        .with(predicate::eq(42))
+        .returning(|_| Some(String::from("answer")));
+    assert_eq!(Some(String::from("answer")), lookup(&mock, 42));
+}
@@ -0,0 +1,47 @@
+// vim: tw=80
+//! The visibility of expect_* methods may differ from the mocked methods'
+#![deny(warnings)]
+
+mod m {
+    use mockall::*;
+
+    #[automock(expect_vis(pub(crate)))]
+    pub trait Foo {
+        fn foo(&self) -> u32;
+    }
+
+    pub struct Bar {}
+
+    #[automock(expect_vis(pub(super)))]
+    impl Bar {
+        pub fn bar(&self) -> u32 {
+            unimplemented!()
+        }
+        pub fn baz() -> u32 {
+            unimplemented!()
+        }
+    }
+}
+
+use m::*;
+
+#[test]
+fn method() {
+    let mut mock = MockBar::new();
+    mock.expect_bar().return_const(3u32);
+    assert_eq!(3, mock.bar());
+}
+
+#[test]
+fn static_method() {
+    let ctx = MockBar::baz_context();
+    ctx.expect().return_const(4u32);
+    assert_eq!(4, MockBar::baz());
+}
+
+#[test]
+fn trait_method() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo().return_const(42u32);
+    assert_eq!(42, mock.foo());
+}
@@ -0,0 +1,155 @@
+// vim: tw=80
+//! A failure handler can take over reporting expectation failures
+#![deny(warnings)]
+
+use mockall::*;
+use std::{
+    cell::RefCell,
+    panic::{AssertUnwindSafe, catch_unwind}
+};
+
+thread_local! {
+    static FAILURES: RefCell<Vec<(FailureKind, String)>> =
+        RefCell::new(Vec::new());
+}
+
+fn record(info: &FailureInfo) {
+    FAILURES.with(|f|
+        f.borrow_mut().push((info.kind(), info.message().to_owned()))
+    );
+}
+
+/// Every test in this file uses the same handler, since it's global.  Each test
+/// sees only the failures from its own thread.
+fn failures() -> Vec<(FailureKind, String)> {
+    set_failure_handler(record);
+    FAILURES.with(|f| f.borrow_mut().drain(..).collect())
+}
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32) -> u32;
+    fn bar(&self);
+    fn baz() -> u32;
+}
+
+#[test]
+fn call_failure() {
+    failures();
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(predicate::eq(1))
+        .return_const(0u32);
+    let r = catch_unwind(AssertUnwindSafe(|| mock.foo(2)));
+    // The mock can't return anything, so it still panics
+    assert!(r.is_err());
+    let f = failures();
+    assert_eq!(1, f.len());
+    assert_eq!(FailureKind::Call, f[0].0);
+    assert!(f[0].1.starts_with("MockFoo::foo(2): No matching expectation found"));
+}
+
+#[test]
+fn checkpoint() {
+    failures();
+    let mut mock = MockFoo::new();
+    mock.expect_foo().times(1).return_const(0u32);
+    mock.expect_bar().times(1).return_const(());
+    mock.checkpoint();
+    assert_eq!(vec![(FailureKind::Verification, String::from(
+        "2 verification failure(s):\n    MockFoo::foo: Expectation(<anything>) called 0 time(s) which is fewer than expected 1\n    MockFoo::bar: Expectation(<anything>) called 0 time(s) which is fewer than expected 1"
+    ))], failures());
+    // The expectations were cleared
+    mock.checkpoint();
+    assert!(failures().is_empty());
+}
+
+#[test]
+fn drop() {
+    failures();
+    let mut mock = MockFoo::new();
+    mock.expect_bar().times(1).return_const(());
+    std::mem::drop(mock);
+    assert_eq!(vec![(FailureKind::Verification, String::from(
+        "MockFoo::bar: Expectation(<anything>) called 0 time(s) which is fewer than expected 1"
+    ))], failures());
+}
+
+#[test]
+fn no_failures() {
+    failures();
+    let mut mock = MockFoo::new();
+    mock.expect_bar().times(1).return_const(());
+    mock.bar();
+    std::mem::drop(mock);
+    assert!(failures().is_empty());
+}
+
+#[test]
+fn scenario() {
+    failures();
+    let mut scenario = Scenario::new();
+    let mut mock = MockFoo::new();
+    mock.expect_bar()
+        .times(1)
+        .in_scenario(&mut scenario)
+        .return_const(());
+    std::mem::drop(mock);
+    scenario.verify();
+    let f = failures();
+    assert_eq!(1, f.len());
+    assert_eq!(FailureKind::Verification, f[0].0);
+    assert!(f[0].1.starts_with("Scenario has 1 unsatisfied expectation(s)"));
+    // An already verified scenario doesn't report its failures again on drop
+    std::mem::drop(scenario);
+    assert!(failures().is_empty());
+}
+
+#[test]
+fn scenario_drop() {
+    failures();
+    let mut scenario = Scenario::new();
+    let mut mock = MockFoo::new();
+    mock.expect_bar()
+        .times(1)
+        .in_scenario(&mut scenario)
+        .return_const(());
+    std::mem::drop(mock);
+    std::mem::drop(scenario);
+    let f = failures();
+    assert_eq!(1, f.len());
+    assert!(f[0].1.starts_with("Scenario has 1 unsatisfied expectation(s)"));
+}
+
+#[test]
+fn sequence() {
+    failures();
+    let mut seq = Sequence::new();
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .times(1)
+        .in_sequence(&mut seq)
+        .return_const(0u32);
+    mock.expect_bar()
+        .times(1)
+        .in_sequence(&mut seq)
+        .return_const(());
+    let r = catch_unwind(AssertUnwindSafe(|| mock.bar()));
+    assert!(r.is_err());
+    let f = failures();
+    assert_eq!(vec![(FailureKind::Call,
+        String::from("MockFoo::bar(): Method sequence violation"))], f);
+    mock.checkpoint();
+    failures();
+}
+
+#[test]
+fn static_method() {
+    failures();
+    let ctx = MockFoo::baz_context();
+    ctx.expect().times(1).return_const(42u32);
+    std::mem::drop(ctx);
+    assert_eq!(vec![(FailureKind::Verification, String::from(
+        "MockFoo::baz: Expectation(<anything>) called 0 time(s) which is fewer than expected 1"
+    ))], failures());
+}
@@ -0,0 +1,38 @@
+// vim: tw=80
+//! Mock module functions can be passed where a bare function pointer is
+//! expected, such as a callback registry.
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+pub mod callbacks {
+    pub fn transform(_x: u32) -> u32 { unimplemented!() }
+    pub extern "C" fn c_transform(_x: u32) -> u32 { unimplemented!() }
+}
+
+fn apply(f: fn(u32) -> u32, x: u32) -> u32 {
+    f(x)
+}
+
+fn apply_c(f: extern "C" fn(u32) -> u32, x: u32) -> u32 {
+    f(x)
+}
+
+#[test]
+fn rust_abi() {
+    let ctx = mock_callbacks::transform_context();
+    ctx.expect()
+        .with(predicate::eq(4))
+        .returning(|x| x * 2);
+    assert_eq!(8, apply(mock_callbacks::transform, 4));
+}
+
+#[test]
+fn c_abi() {
+    let ctx = mock_callbacks::c_transform_context();
+    ctx.expect()
+        .returning(|x| x + 1);
+    let f: extern "C" fn(u32) -> u32 = mock_callbacks::c_transform;
+    assert_eq!(5, apply_c(f, 4));
+}
@@ -11,6 +11,8 @@ mod ffi {
         // doesn't poison other tests
         #[allow(dead_code)]
         pub(super) fn foo1(x: u32) -> i64;
+        #[allow(dead_code)]
+        pub(super) fn foo2(x: u32) -> i64;
     }
 }
 
@@ -22,12 +24,7 @@ pub fn normal_usage() {
 }
 
 #[test]
-#[cfg_attr(feature = "nightly", should_panic(
-        expected = "mock_ffi::foo1(5): No matching expectation found"
-))]
-#[cfg_attr(not(feature = "nightly"), should_panic(
-        expected = "mock_ffi::foo1(?): No matching expectation found"
-))]
+#[should_panic(expected = "mock_ffi::foo1(5): No matching expectation found")]
 fn with_no_matches() {
     let ctx = mock_ffi::foo1_context();
     ctx.expect()
@@ -36,6 +33,16 @@ fn with_no_matches() {
     unsafe{ mock_ffi::foo1(5) };
 }
 
+#[test]
+#[should_panic(expected =
+    "mock_ffi::foo2: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+fn unsatisfied() {
+    let ctx = mock_ffi::foo2_context();
+    ctx.expect()
+        .times(1)
+        .returning(i64::from);
+}
+
 #[test]
 fn returning() {
     let ctx = mock_ffi::foo_context();
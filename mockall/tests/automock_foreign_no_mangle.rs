@@ -0,0 +1,40 @@
+// vim: tw=80
+//! automock can export mock foreign functions under their original symbol
+//! names, so that code calling the original extern declarations will reach the
+//! mocks.
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock(no_mangle)]
+pub mod ffi {
+    extern "C" {
+        // These functions are deliberately not defined by any library.  Only
+        // the mocks provide them.
+        pub fn mockall_no_mangle_foo(x: u32) -> i64;
+        #[link_name = "mockall_no_mangle_bar_renamed"]
+        pub fn mockall_no_mangle_bar(x: u32, y: u32) -> u32;
+    }
+}
+
+/// Code under test, which doesn't know anything about the mock module.
+fn do_stuff(x: u32) -> i64 {
+    unsafe { ffi::mockall_no_mangle_foo(x) }
+}
+
+#[test]
+fn returning() {
+    let ctx = mock_ffi::mockall_no_mangle_foo_context();
+    ctx.expect()
+        .with(predicate::eq(42))
+        .returning(|x| i64::from(x) + 1);
+    assert_eq!(43, do_stuff(42));
+}
+
+#[test]
+fn link_name() {
+    let ctx = mock_ffi::mockall_no_mangle_bar_context();
+    ctx.expect()
+        .returning(|x, y| x + y);
+    assert_eq!(5, unsafe { ffi::mockall_no_mangle_bar(2, 3) });
+}
@@ -0,0 +1,43 @@
+// vim: tw=80
+//! Variadic foreign functions.  Expectations only see the fixed arguments.
+#![cfg_attr(feature = "nightly", feature(c_variadic))]
+#![deny(warnings)]
+
+use mockall::*;
+use std::os::raw::{c_char, c_int};
+
+#[automock]
+mod ffi {
+    use super::*;
+    extern "C" {
+        pub fn printf(fmt: *const c_char, ...) -> c_int;
+    }
+}
+
+const FMT: &[u8] = b"%d\0";
+
+// Ensure we can still use the original mocked function
+pub fn normal_usage() {
+    unsafe {
+        ffi::printf(FMT.as_ptr() as *const c_char, 42 as c_int);
+    }
+}
+
+#[test]
+fn returning() {
+    let ctx = mock_ffi::printf_context();
+    ctx.expect()
+        .withf(|fmt| !fmt.is_null())
+        .returning(|_| 5);
+    assert_eq!(5, unsafe{ mock_ffi::printf(FMT.as_ptr() as *const c_char) });
+}
+
+#[cfg(feature = "nightly")]
+#[test]
+fn variadic_args() {
+    let ctx = mock_ffi::printf_context();
+    ctx.expect().returning(|_| 2);
+    assert_eq!(2, unsafe{
+        mock_ffi::printf(FMT.as_ptr() as *const c_char, 42 as c_int)
+    });
+}
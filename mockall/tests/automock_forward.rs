@@ -0,0 +1,90 @@
+// vim: tw=80
+//! automock can implement the mocked trait for references and smart pointers
+//! to the mock struct, too.
+#![deny(warnings)]
+
+use mockall::*;
+use std::{rc::Rc, sync::Arc};
+
+#[automock(forward(&, Rc, Arc))]
+pub trait Foo {
+    const C: u32 = 42;
+    fn foo(&self, x: u32) -> u32;
+    fn bar(&self) -> u32 { 0 }
+}
+
+#[automock(type Item=u32; forward(Box))]
+pub trait Bar {
+    type Item;
+    fn bar(&self, x: &Self::Item) -> u32;
+    fn baz(&mut self, x: u32);
+    fn bean(self) -> u32;
+}
+
+#[automock(type Item=u32; forward(&mut, Box))]
+pub trait Baz {
+    type Item;
+    fn baz(&self) -> Self::Item;
+}
+
+#[automock(forward(Box))]
+pub trait Generic<T: 'static> {
+    fn generic<Q: 'static>(&self, t: T, q: Q) -> T;
+}
+
+fn use_foo<F: Foo>(f: F) -> u32 {
+    f.foo(1) + f.bar() + F::C
+}
+
+#[test]
+fn arc() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo().returning(|x| x + 1);
+    mock.expect_bar().return_const(10u32);
+    assert_eq!(54, use_foo(Arc::new(mock)));
+}
+
+#[test]
+fn boxed() {
+    let mut mock = MockBar::new();
+    mock.expect_bean().return_const(5u32);
+    mock.expect_baz().with(predicate::eq(1)).times(1).return_const(());
+    let mut b = Box::new(mock);
+    b.baz(1);
+    assert_eq!(5, b.bean());
+}
+
+#[test]
+fn generic() {
+    let mut mock = MockGeneric::<u32>::new();
+    mock.expect_generic::<i16>()
+        .returning(|t, q| t + q as u32);
+    fn use_generic<G: Generic<u32>>(g: G) -> u32 { g.generic(1, 2i16) }
+    assert_eq!(3, use_generic(Box::new(mock)));
+}
+
+#[test]
+fn rc() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo().returning(|x| x + 1);
+    mock.expect_bar().return_const(0u32);
+    assert_eq!(44, use_foo(Rc::new(mock)));
+}
+
+#[test]
+fn reference() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo().returning(|x| x + 1);
+    mock.expect_bar().return_const(0u32);
+    assert_eq!(44, use_foo(&mock));
+    assert_eq!(44, use_foo(&mock));
+}
+
+#[test]
+fn ref_mut() {
+    let mut mock = MockBaz::new();
+    mock.expect_baz().return_const(7u32);
+    let r = &mut mock;
+    fn use_baz<B: Baz<Item=u32>>(b: B) -> u32 { b.baz() }
+    assert_eq!(7, use_baz(r));
+}
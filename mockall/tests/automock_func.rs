@@ -0,0 +1,31 @@
+// vim: tw=80
+//! matching a single argument with a closure
+#![deny(warnings)]
+
+use mockall::*;
+use mockall::predicate::*;
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32, y: &str, z: u32);
+}
+
+#[test]
+fn ok() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(eq(1), func(|y: &str| y.starts_with("ab")), always())
+        .return_const(());
+    mock.foo(1, "abc", 99);
+}
+
+#[test]
+#[should_panic(expected =
+    "Expectation(var == 1, is_abbrev(var), true) rejected argument `y`")]
+fn fail() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(eq(1), func(|y: &str| y.len() < 4).fn_name("is_abbrev"), always())
+        .return_const(());
+    mock.foo(1, "abcdef", 99);
+}
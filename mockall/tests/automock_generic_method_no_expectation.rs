@@ -0,0 +1,51 @@
+// vim: tw=80
+//! Calling a generic method with type parameters that have no expectations
+//! should say which type parameters do have expectations.
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+pub trait Foo {
+    fn foo<T: 'static>(&self, t: T) -> u32;
+    fn bar<T: 'static>(t: T) -> u32;
+}
+
+#[test]
+#[should_panic(expected = ": No matching expectation found.  No expectations were set for generic parameters `u8`.  Expectations exist for `i32`, `u16`")]
+fn method() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo::<u16>().return_const(1u32);
+    mock.expect_foo::<i32>().return_const(2u32);
+    mock.foo(5u8);
+}
+
+#[test]
+#[should_panic(expected = ": No matching expectation found.  No expectations were set for generic parameters `u8`, or any others")]
+fn method_no_expectations() {
+    let mock = MockFoo::new();
+    mock.foo(5u8);
+}
+
+#[test]
+#[cfg_attr(feature = "nightly", should_panic(
+    expected = "MockFoo::foo(7): No matching expectation found"
+))]
+#[cfg_attr(not(feature = "nightly"), should_panic(
+    expected = "MockFoo::foo(?): No matching expectation found"
+))]
+fn no_match() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo::<u8>()
+        .with(predicate::eq(5))
+        .return_const(1u32);
+    mock.foo(7u8);
+}
+
+#[test]
+#[should_panic(expected = ": No matching expectation found.  No expectations were set for generic parameters `alloc::string::String`.  Expectations exist for `&str`")]
+fn static_method() {
+    let ctx = MockFoo::bar_context();
+    ctx.expect::<&'static str>().return_const(1u32);
+    MockFoo::bar(String::from("x"));
+}
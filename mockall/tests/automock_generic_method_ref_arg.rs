@@ -0,0 +1,65 @@
+// vim: tw=80
+//! Generic methods whose arguments are references to their type parameters
+#![deny(warnings)]
+
+use mockall::*;
+use std::fmt::Debug;
+
+#[automock]
+trait Map {
+    fn find<K: 'static>(&self, key: &K) -> Option<u32>;
+    #[concretize]
+    fn find_debug<K: Debug>(&self, key: &K) -> Option<u32>;
+    #[concretize]
+    fn find_any<K>(&self, key: &K, hint: u32) -> Option<u32>;
+    #[concretize]
+    fn insert_any<K>(&mut self, key: K) -> bool;
+}
+
+// With 'static type parameters, expectations are specific to each type, and
+// their predicates receive a reference to the caller's value.
+#[test]
+fn static_with() {
+    let mut mock = MockMap::new();
+    mock.expect_find::<String>()
+        .with(predicate::eq(String::from("a")))
+        .return_const(Some(1));
+    mock.expect_find::<u8>()
+        .withf(|k| *k == 2)
+        .return_const(Some(2));
+    assert_eq!(Some(1), mock.find(&String::from("a")));
+    assert_eq!(Some(2), mock.find(&2u8));
+}
+
+#[test]
+fn concretize_nonstatic() {
+    let mut mock = MockMap::new();
+    mock.expect_find_debug()
+        .withf(|k| format!("{:?}", k) == "\"a\"")
+        .return_const(Some(3));
+    let s = String::from("a");
+    assert_eq!(Some(3), mock.find_debug(&s.as_str()));
+}
+
+// Unbounded type parameters can't be inspected, but expectations can still
+// match on the other arguments.
+#[test]
+fn concretize_unbounded() {
+    let mut mock = MockMap::new();
+    mock.expect_find_any()
+        .withf(|_key, hint| *hint == 4)
+        .return_const(Some(4));
+    let x = 5u8;
+    let nonstatic = &x;
+    assert_eq!(Some(4), mock.find_any(&nonstatic, 4));
+}
+
+#[test]
+fn concretize_unbounded_by_value() {
+    let mut mock = MockMap::new();
+    mock.expect_insert_any()
+        .times(2)
+        .return_const(true);
+    assert!(mock.insert_any(vec![1u8]));
+    assert!(mock.insert_any("x"));
+}
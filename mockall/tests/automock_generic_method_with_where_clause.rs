@@ -0,0 +1,70 @@
+// vim: tw=80
+//! Generic methods whose where clauses have several bounds, or that also have
+//! lifetime parameters
+#![deny(warnings)]
+
+use mockall::*;
+use std::fmt::Debug;
+
+pub trait Serialize {
+    fn serialize(&self) -> String;
+}
+
+impl Serialize for u32 {
+    fn serialize(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[automock]
+pub trait Foo {
+    fn store<T>(&self, t: T) where T: Serialize + 'static;
+    fn load<T>(&self) -> T where T: Default + Serialize + 'static;
+    fn show<'a, T>(&self, t: &'a T) -> String where T: Debug + 'a + 'static;
+    fn sstore<T>(t: T) -> u32 where T: Serialize + Send + 'static;
+    fn both<T: Serialize, U>(&self, t: T, u: U) -> String
+        where T: 'static, U: Clone + 'static;
+}
+
+#[test]
+fn where_clause() {
+    let mut mock = MockFoo::new();
+    mock.expect_store::<u32>()
+        .withf(|t| t.serialize() == "4")
+        .times(1)
+        .return_const(());
+    mock.store(4u32);
+}
+
+#[test]
+fn where_clause_in_return() {
+    let mut mock = MockFoo::new();
+    mock.expect_load::<u32>()
+        .return_const(7u32);
+    assert_eq!(7u32, mock.load::<u32>());
+}
+
+#[test]
+fn with_lifetime() {
+    let mut mock = MockFoo::new();
+    mock.expect_show::<u32>()
+        .returning(|t| format!("{:?}", t));
+    let x = 42u32;
+    assert_eq!("42", mock.show(&x));
+}
+
+#[test]
+fn static_method() {
+    let ctx = MockFoo::sstore_context();
+    ctx.expect::<u32>()
+        .returning(|t| t * 2);
+    assert_eq!(10, MockFoo::sstore(5u32));
+}
+
+#[test]
+fn mixed_bounds() {
+    let mut mock = MockFoo::new();
+    mock.expect_both::<u32, String>()
+        .returning(|t, u| format!("{}{}", t.serialize(), u));
+    assert_eq!("1a", mock.both(1u32, String::from("a")));
+}
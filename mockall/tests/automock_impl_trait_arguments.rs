@@ -0,0 +1,49 @@
+// vim: tw=80
+//! "impl Trait" in argument position is mocked like a generic parameter
+#![deny(warnings)]
+
+use mockall::*;
+use std::fmt::Display;
+
+#[automock]
+pub trait Foo {
+    fn foo(&self, x: impl Into<u32> + 'static) -> u32;
+    fn log<T: Display + 'static>(&self, msg: impl Into<String> + 'static,
+                                 ctx: T);
+}
+
+#[automock]
+pub mod bar {
+    pub fn bar(_x: impl AsRef<str> + 'static) -> usize { unimplemented!() }
+}
+
+#[test]
+fn generic_and_impl_trait() {
+    let mut mock = MockFoo::new();
+    mock.expect_log::<u32, &'static str>()
+        .withf(|msg, ctx| *msg == "hello" && *ctx == 5)
+        .times(1)
+        .return_const(());
+    mock.expect_log::<u32, String>()
+        .times(1)
+        .return_const(());
+    mock.log("hello", 5u32);
+    mock.log(String::from("world"), 6u32);
+}
+
+#[test]
+fn impl_trait() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo::<u8>()
+        .with(predicate::eq(4))
+        .returning(|x| u32::from(x) * 2);
+    assert_eq!(8, mock.foo(4u8));
+}
+
+#[test]
+fn module() {
+    let ctx = mock_bar::bar_context();
+    ctx.expect::<&'static str>()
+        .returning(|x| x.len());
+    assert_eq!(3, mock_bar::bar("abc"));
+}
@@ -0,0 +1,65 @@
+// vim: tw=80
+//! Expectations with no return value return their type's default value
+#![deny(warnings)]
+
+use mockall::*;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Config {
+    verbose: bool
+}
+
+#[automock]
+trait Foo {
+    fn count(&self, x: u32) -> u32;
+    fn config(&self) -> Config;
+    fn name(&self) -> &String;
+    fn bounded<T: Default + 'static>(&self) -> T;
+    fn generic<T: 'static>(&self) -> T;
+}
+
+#[test]
+fn with_times() {
+    let mut mock = MockFoo::new();
+    mock.expect_count()
+        .with(predicate::eq(5))
+        .times(1);
+    assert_eq!(0, mock.count(5));
+}
+
+#[test]
+fn user_type() {
+    let mut mock = MockFoo::new();
+    mock.expect_config()
+        .times(1);
+    assert_eq!(Config::default(), mock.config());
+}
+
+#[test]
+fn reference() {
+    let mut mock = MockFoo::new();
+    mock.expect_name()
+        .times(1);
+    assert_eq!("", mock.name());
+}
+
+/// A generic return type bounded by Default works, too
+#[test]
+fn bounded() {
+    let mut mock = MockFoo::new();
+    mock.expect_bounded::<u32>()
+        .times(1);
+    assert_eq!(0, mock.bounded::<u32>());
+}
+
+/// But without specialization, Mockall can't tell whether an unbounded generic
+/// type implements Default.
+#[test]
+#[cfg_attr(not(feature = "nightly"),
+    should_panic(expected = "Can only return default values for types known to"))]
+fn generic() {
+    let mut mock = MockFoo::new();
+    mock.expect_generic::<u32>()
+        .times(1);
+    assert_eq!(0, mock.generic::<u32>());
+}
@@ -0,0 +1,71 @@
+// vim: tw=80
+//! matching arguments with in_range and the ordering predicates
+#![deny(warnings)]
+
+use mockall::*;
+use mockall::predicate::*;
+use std::time::Duration;
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: i32);
+    fn timeout(&self, t: Duration);
+}
+
+#[test]
+fn exclusive() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(in_range(1..10))
+        .times(2)
+        .return_const(());
+    mock.foo(1);
+    mock.foo(9);
+}
+
+#[test]
+#[should_panic(expected = "Expectation(var in 1..10) rejected argument `x`")]
+fn exclusive_fail() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(in_range(1..10))
+        .return_const(());
+    mock.foo(10);
+}
+
+#[test]
+fn inclusive() {
+    let mut mock = MockFoo::new();
+    mock.expect_timeout()
+        .with(in_range(Duration::from_millis(100)..=Duration::from_millis(500)))
+        .times(2)
+        .return_const(());
+    mock.timeout(Duration::from_millis(100));
+    mock.timeout(Duration::from_millis(500));
+}
+
+#[test]
+fn ordering() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(le(0))
+        .return_const(());
+    mock.expect_foo()
+        .with(gt(100))
+        .return_const(());
+    mock.foo(-5);
+    mock.foo(101);
+}
+
+#[test]
+fn unbounded() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(in_range(..0))
+        .return_const(());
+    mock.expect_foo()
+        .with(in_range(100..))
+        .return_const(());
+    mock.foo(-1);
+    mock.foo(100);
+}
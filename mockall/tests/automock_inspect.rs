@@ -0,0 +1,86 @@
+// vim: tw=80
+//! inspect runs side effects on every matching call, without changing the
+//! return value
+#![deny(warnings)]
+
+use mockall::*;
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicU32, Ordering},
+    mpsc
+};
+use std::thread;
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32) -> u32;
+    fn bar(&self, name: &str);
+}
+
+#[test]
+fn channel() {
+    let (tx, rx) = mpsc::channel();
+    let tx = Mutex::new(tx);
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .inspect(move |x| tx.lock().unwrap().send(*x).unwrap())
+        .return_const(42u32);
+    let t = thread::spawn(move || mock.foo(5));
+    assert_eq!(5, rx.recv().unwrap());
+    assert_eq!(42, t.join().unwrap());
+}
+
+#[test]
+fn doesnt_affect_return() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .returning(|x| x + 1)
+        .inspect(|_| ());
+    assert_eq!(6, mock.foo(5));
+}
+
+/// Inspectors run in the order they were added
+#[test]
+fn order() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let log1 = log.clone();
+    let log2 = log.clone();
+    let mut mock = MockFoo::new();
+    mock.expect_bar()
+        .inspect(move |name| log1.lock().unwrap().push(format!("1{}", name)))
+        .inspect(move |name| log2.lock().unwrap().push(format!("2{}", name)))
+        .return_const(());
+    mock.bar("a");
+    mock.bar("b");
+    assert_eq!(vec!["1a", "2a", "1b", "2b"], *log.lock().unwrap());
+}
+
+/// Only the matching expectation's inspectors run
+#[test]
+fn only_matching() {
+    let count = Arc::new(AtomicU32::new(0));
+    let count2 = count.clone();
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(predicate::eq(1))
+        .inspect(move |_| {count2.fetch_add(1, Ordering::Relaxed);})
+        .return_const(0u32);
+    mock.expect_foo()
+        .return_const(1u32);
+    mock.foo(1);
+    mock.foo(2);
+    mock.foo(1);
+    assert_eq!(2, count.load(Ordering::Relaxed));
+}
+
+#[test]
+fn pending() {
+    let count = Arc::new(AtomicU32::new(0));
+    let count2 = count.clone();
+    let mut mock = MockFoo::new();
+    mock.expect_foo_pending()
+        .inspect(move |_| {count2.fetch_add(1, Ordering::Relaxed);})
+        .return_const(0u32);
+    mock.foo(1);
+    assert_eq!(1, count.load(Ordering::Relaxed));
+}
@@ -0,0 +1,64 @@
+// vim: tw=80
+//! A method returning a mutable reference can inspect the value after the
+//! caller has mutated it.
+#![deny(warnings)]
+
+use mockall::*;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering}
+};
+
+#[automock]
+trait Foo {
+    fn buf(&mut self) -> &mut Vec<u32>;
+}
+
+fn fill(foo: &mut dyn Foo) {
+    foo.buf().push(1);
+    foo.buf().push(2);
+}
+
+#[test]
+fn checkpoint() {
+    let mut mock = MockFoo::new();
+    mock.expect_buf()
+        .return_var(Vec::new())
+        .inspect_var(|v| assert_eq!(&[1, 2][..], &v[..]));
+    fill(&mut mock);
+    mock.checkpoint();
+}
+
+#[test]
+#[should_panic(expected = "assertion")]
+fn drop() {
+    let mut mock = MockFoo::new();
+    mock.expect_buf()
+        .return_var(Vec::new())
+        .inspect_var(|v| assert_eq!(&[1, 2, 3][..], &v[..]));
+    fill(&mut mock);
+}
+
+#[test]
+fn not_called() {
+    let inspected = Arc::new(AtomicBool::new(false));
+    let inspected2 = inspected.clone();
+    let mut mock = MockFoo::new();
+    mock.expect_buf()
+        .return_var(Vec::new())
+        .times(0..)
+        .inspect_var(move |_| inspected2.store(true, Ordering::Relaxed));
+    mock.checkpoint();
+    assert!(!inspected.load(Ordering::Relaxed));
+}
+
+#[test]
+fn returning() {
+    let mut mock = MockFoo::new();
+    mock.expect_buf()
+        .returning(|| vec![0])
+        .times(1)
+        .inspect_var(|v| assert_eq!(&[0, 3][..], &v[..]));
+    mock.buf().push(3);
+    mock.checkpoint();
+}
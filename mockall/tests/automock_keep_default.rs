@@ -0,0 +1,53 @@
+// vim: tw=80
+//! Methods marked with #[mockall::keep_default] aren't mocked.  The mock
+//! struct uses the trait's default implementation instead.
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+pub trait Shape {
+    fn width(&self) -> u32;
+    fn height(&self) -> u32;
+    #[mockall::keep_default]
+    fn area(&self) -> u32 {
+        self.width() * self.height()
+    }
+    // Methods with default bodies are still mocked by default
+    fn name(&self) -> String {
+        String::from("shape")
+    }
+}
+
+pub trait Volume {
+    fn depth(&self) -> u32;
+    fn volume(&self) -> u32 {
+        self.depth() * 10
+    }
+}
+
+mock! {
+    pub Solid {}
+    impl Volume for Solid {
+        fn depth(&self) -> u32;
+        #[mockall::keep_default]
+        fn volume(&self) -> u32;
+    }
+}
+
+#[test]
+fn automock() {
+    let mut mock = MockShape::new();
+    mock.expect_width().return_const(3u32);
+    mock.expect_height().return_const(4u32);
+    mock.expect_name().return_const("square".to_owned());
+    assert_eq!(12, mock.area());
+    assert_eq!("square", mock.name());
+}
+
+#[test]
+fn mock_macro() {
+    let mut mock = MockSolid::new();
+    mock.expect_depth().return_const(2u32);
+    assert_eq!(20, mock.volume());
+}
@@ -0,0 +1,86 @@
+// vim: tw=80
+//! A trait that's generic only over lifetimes
+#![deny(warnings)]
+
+use mockall::*;
+
+pub struct Node(u32);
+
+#[automock]
+pub trait Visitor<'ast> {
+    fn visit(&mut self, n: &'ast Node);
+    fn visit_all(&mut self, n: &'ast [Node]) -> usize;
+    fn find(&self, x: u32) -> Option<&'ast Node>;
+}
+
+#[automock]
+pub trait Pair<'a, 'b> {
+    fn first(&self, a: &'a str, b: &'b str) -> bool;
+}
+
+pub trait Lookup<'k> {
+    fn lookup(&self, key: &'k str) -> Option<u32>;
+}
+
+mock! {
+    pub Table<'k> {}
+    impl<'k> Lookup<'k> for Table<'k> {
+        fn lookup(&self, key: &'k str) -> Option<u32>;
+    }
+}
+
+fn walk<'ast, V: Visitor<'ast>>(v: &mut V, nodes: &'ast [Node]) {
+    for n in nodes {
+        v.visit(n);
+    }
+}
+
+#[test]
+fn visit() {
+    let nodes = vec![Node(1), Node(2)];
+    let mut mock = MockVisitor::new();
+    mock.expect_visit()
+        .withf(|n| n.0 > 0)
+        .times(2)
+        .return_const(());
+    walk(&mut mock, &nodes);
+}
+
+#[test]
+fn returning() {
+    let nodes = vec![Node(1), Node(2)];
+    let mut mock = MockVisitor::new();
+    mock.expect_visit_all()
+        .returning(|n| n.len());
+    assert_eq!(2, mock.visit_all(&nodes));
+}
+
+/// Return types may use the trait's lifetime, but the expectation must return
+/// 'static values
+#[test]
+fn return_lifetime() {
+    static ROOT: Node = Node(0);
+    let mut mock = MockVisitor::new();
+    mock.expect_find()
+        .return_const(Some(&ROOT));
+    assert_eq!(0, mock.find(1).unwrap().0);
+}
+
+#[test]
+fn two_lifetimes() {
+    let mut mock = MockPair::new();
+    mock.expect_first()
+        .returning(|a, b| a < b);
+    let a = String::from("a");
+    assert!(mock.first(&a, "b"));
+}
+
+#[test]
+fn mock_macro() {
+    let mut mock = MockTable::new();
+    mock.expect_lookup()
+        .with(predicate::eq("x"))
+        .return_const(Some(5));
+    let key = String::from("x");
+    assert_eq!(Some(5), mock.lookup(&key));
+}
@@ -12,6 +12,7 @@ pub mod m {
         pub fn bar(_x: T) -> i64 {unimplemented!()}
         // We must have a separate method for every should_panic test
         pub fn bar1(_x: T) -> i64 {unimplemented!()}
+        pub fn bar2(_x: T) -> i64 {unimplemented!()}
         // Module functions should be able to use impl Trait, too
         pub fn baz() -> impl std::fmt::Debug + Send { unimplemented!()}
         // Module functions can use mutable arguments
@@ -19,12 +20,9 @@ pub mod m {
     }
 
     #[test]
-    #[cfg_attr(feature = "nightly", should_panic(
-            expected = "mock_foo::bar1(5): No matching expectation found"
-    ))]
-    #[cfg_attr(not(feature = "nightly"), should_panic(
-            expected = "mock_foo::bar1(?): No matching expectation found"
-    ))]
+    #[should_panic(
+        expected = "mock_foo::bar1(5): No matching expectation found"
+    )]
     fn with_no_matches() {
         let ctx = mock_foo::bar1_context();
         ctx.expect()
@@ -33,6 +31,17 @@ pub mod m {
         mock_foo::bar1(5);
     }
 
+    #[test]
+    #[should_panic(expected =
+        "mock_foo::bar2: Expectation(<anything>) called 1 time(s) which is fewer than expected 2")]
+    fn unsatisfied() {
+        let ctx = mock_foo::bar2_context();
+        ctx.expect()
+            .times(2)
+            .return_const(0);
+        mock_foo::bar2(5);
+    }
+
     #[test]
     fn returning() {
         let ctx = mock_foo::bar_context();
@@ -0,0 +1,56 @@
+// vim: tw=80
+//! Code that calls a module's functions directly can be tested by swapping in
+//! the mock module with #[double]
+#![deny(warnings)]
+
+use mockall::*;
+use mockall_double::double;
+
+pub mod outer {
+    use super::*;
+
+    #[automock]
+    pub mod clock {
+        pub fn now() -> u64 { unimplemented!() }
+        pub fn sleep(_secs: u64) { unimplemented!() }
+    }
+}
+
+#[double]
+use outer::clock;
+
+fn wait_until(deadline: u64) -> u64 {
+    let now = clock::now();
+    if now < deadline {
+        clock::sleep(deadline - now);
+    }
+    now
+}
+
+#[test]
+fn checkpoint() {
+    let now_ctx = clock::now_context();
+    let sleep_ctx = clock::sleep_context();
+    now_ctx.expect().return_const(10u64);
+    sleep_ctx.expect()
+        .with(predicate::eq(5))
+        .times(1)
+        .return_const(());
+    assert_eq!(10, wait_until(15));
+    clock::checkpoint();
+
+    // Both contexts were cleared by the module's checkpoint
+    now_ctx.expect().return_const(20u64);
+    sleep_ctx.expect().never();
+    assert_eq!(20, wait_until(15));
+}
+
+#[test]
+#[should_panic(expected = "mock_clock::sleep: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+fn checkpoint_unsatisfied() {
+    let sleep_ctx = clock::sleep_context();
+    sleep_ctx.expect()
+        .times(1)
+        .return_const(());
+    clock::checkpoint();
+}
@@ -0,0 +1,33 @@
+// vim: tw=80
+//! Module functions may have unboxed trait object arguments
+#![deny(warnings)]
+
+use mockall::*;
+use std::fmt::Debug;
+
+#[automock]
+pub mod foo {
+    pub fn bar(_d: &dyn std::fmt::Debug) -> u32 { unimplemented!() }
+}
+
+mock! {
+    pub fn baz(d: &dyn Debug) -> u32;
+}
+
+#[test]
+fn automocked() {
+    let ctx = mock_foo::bar_context();
+    ctx.expect()
+        .withf(|d| format!("{:?}", d) == "1")
+        .return_const(3u32);
+    assert_eq!(3, mock_foo::bar(&1));
+}
+
+#[test]
+fn mocked() {
+    let ctx = mock_baz_context();
+    ctx.expect()
+        .withf(|d| format!("{:?}", d) == "\"x\"")
+        .return_const(4u32);
+    assert_eq!(4, mock_baz(&"x"));
+}
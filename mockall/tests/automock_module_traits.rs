@@ -0,0 +1,61 @@
+// vim: tw=80
+//! automock on a module mocks every trait defined within it
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+pub mod ports {
+    pub struct Record(pub u32);
+
+    pub trait Reader {
+        fn read(&self) -> Record;
+    }
+
+    pub trait Writer {
+        fn write(&mut self, r: Record) -> bool;
+    }
+
+    // Traits that are already automocked aren't mocked twice
+    #[mockall::automock]
+    pub trait Flusher {
+        fn flush(&mut self);
+    }
+
+    pub fn check(_r: Record) -> bool { unimplemented!() }
+    pub fn version() -> u32 { unimplemented!() }
+}
+
+use ports::*;
+
+#[test]
+fn already_automocked() {
+    let mut mock = MockFlusher::new();
+    mock.expect_flush().return_const(());
+    mock.flush();
+}
+
+#[test]
+fn function() {
+    let ctx = mock_ports::version_context();
+    ctx.expect().return_const(3u32);
+    assert_eq!(3, mock_ports::version());
+}
+
+#[test]
+fn traits() {
+    let mut reader = MockReader::new();
+    let mut writer = MockWriter::new();
+    reader.expect_read().returning(|| Record(42));
+    writer.expect_write()
+        .withf(|r| r.0 == 42)
+        .return_const(true);
+    assert!(writer.write(reader.read()));
+}
+
+#[test]
+fn uses_module_types() {
+    let ctx = mock_ports::check_context();
+    ctx.expect().returning(|r| r.0 == 7);
+    assert!(mock_ports::check(Record(7)));
+}
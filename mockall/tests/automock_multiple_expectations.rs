@@ -0,0 +1,77 @@
+// vim: tw=80
+//! A method may have several expectations.  Each call is dispatched to the
+//! first one, in FIFO order, that matches and isn't yet saturated.
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32) -> u32;
+    fn bar(&mut self, x: u32) -> &u32;
+}
+
+#[test]
+fn different_matchers() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(predicate::eq(1))
+        .return_const(10u32);
+    mock.expect_foo()
+        .with(predicate::eq(2))
+        .return_const(20u32);
+    mock.expect_foo()
+        .with(predicate::gt(2))
+        .returning(|x| x * 100);
+    assert_eq!(20, mock.foo(2));
+    assert_eq!(10, mock.foo(1));
+    assert_eq!(500, mock.foo(5));
+}
+
+/// When several expectations match, the oldest wins
+#[test]
+fn fifo() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(predicate::eq(1))
+        .return_const(10u32);
+    mock.expect_foo()
+        .return_const(0u32);
+    // Added after the catch-all, so it never matches
+    mock.expect_foo()
+        .with(predicate::eq(2))
+        .return_const(20u32);
+    assert_eq!(10, mock.foo(1));
+    assert_eq!(0, mock.foo(2));
+}
+
+/// Saturated expectations are skipped
+#[test]
+fn saturated() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .times(2)
+        .return_const(1u32);
+    mock.expect_foo()
+        .times(1)
+        .return_const(2u32);
+    mock.expect_foo()
+        .return_const(3u32);
+    assert_eq!(1, mock.foo(0));
+    assert_eq!(1, mock.foo(0));
+    assert_eq!(2, mock.foo(0));
+    assert_eq!(3, mock.foo(0));
+}
+
+/// Dispatch works the same for methods returning references
+#[test]
+fn reference() {
+    let mut mock = MockFoo::new();
+    mock.expect_bar()
+        .with(predicate::eq(1))
+        .return_const(10u32);
+    mock.expect_bar()
+        .return_const(0u32);
+    assert_eq!(10, *mock.bar(1));
+    assert_eq!(0, *mock.bar(2));
+}
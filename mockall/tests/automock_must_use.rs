@@ -0,0 +1,76 @@
+// vim: tw=80
+//! Attributes like #[must_use] and #[deprecated] are forwarded to the mock
+//! methods.
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+pub trait Foo {
+    #[must_use]
+    fn foo(&self) -> u32;
+    #[must_use = "the result matters"]
+    fn bar(x: u32) -> u32;
+    #[deprecated(since = "0.1.0", note = "use foo instead")]
+    fn baz(&self) -> u32;
+}
+
+pub struct Bean {}
+#[automock]
+impl Bean {
+    #[must_use]
+    pub fn foo(&self) -> u32 {0}
+    #[deprecated(note = "use foo instead")]
+    pub fn baz(&self) -> u32 {0}
+}
+
+#[automock]
+pub mod m {
+    #[must_use]
+    pub fn foo() -> u32 {0}
+}
+
+#[test]
+fn must_use() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .returning(|| 42);
+    assert_eq!(42, mock.foo());
+}
+
+#[test]
+fn must_use_static() {
+    let ctx = MockFoo::bar_context();
+    ctx.expect()
+        .returning(|x| x + 1);
+    assert_eq!(5, MockFoo::bar(4));
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated() {
+    let mut mock = MockFoo::new();
+    mock.expect_baz()
+        .returning(|| 42);
+    assert_eq!(42, mock.baz());
+}
+
+#[test]
+#[allow(deprecated)]
+fn inherent() {
+    let mut mock = MockBean::new();
+    mock.expect_foo()
+        .returning(|| 1);
+    mock.expect_baz()
+        .returning(|| 2);
+    assert_eq!(1, mock.foo());
+    assert_eq!(2, mock.baz());
+}
+
+#[test]
+fn module() {
+    let ctx = mock_m::foo_context();
+    ctx.expect()
+        .returning(|| 42);
+    assert_eq!(42, mock_m::foo());
+}
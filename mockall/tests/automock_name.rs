@@ -0,0 +1,52 @@
+// vim: tw=80
+//! The mock struct's name may be chosen by the user
+#![deny(warnings)]
+
+use mockall::*;
+use std::sync::Arc;
+
+#[automock(name = "FakeFoo")]
+trait Foo {
+    fn foo(&self, x: u32) -> u32;
+}
+
+pub struct Bar {}
+
+#[automock(name = "FakeBar")]
+impl Bar {
+    #[allow(dead_code)]
+    fn bar(&self) -> Self {
+        unimplemented!()
+    }
+}
+
+#[automock(name = "FakeBean" forward(Arc))]
+trait Bean {
+    fn bean(&self) -> u32;
+}
+
+fn do_bean<B: Bean>(b: B) -> u32 {
+    b.bean()
+}
+
+#[test]
+fn forward() {
+    let mut mock = FakeBean::new();
+    mock.expect_bean().return_const(5u32);
+    assert_eq!(5, do_bean(Arc::new(mock)));
+}
+
+#[test]
+fn structure() {
+    let mut mock = FakeBar::new();
+    mock.expect_bar().returning(FakeBar::new);
+    let _ = mock.bar();
+}
+
+#[test]
+fn trait_() {
+    let mut mock = FakeFoo::new();
+    mock.expect_foo()
+        .returning(|x| x + 1);
+    assert_eq!(5, mock.foo(4));
+}
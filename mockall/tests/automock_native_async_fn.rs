@@ -0,0 +1,83 @@
+// vim: tw=80
+//! A trait with native async fn methods, without the async_trait crate
+#![deny(warnings)]
+#![allow(async_fn_in_trait)]
+
+use futures::executor::block_on;
+use mockall::*;
+
+#[automock]
+pub trait Foo {
+    async fn foo(&self, x: u32) -> u32;
+    async fn bar() -> u32;
+    async fn baz<T: 'static>(&self, t: T) -> T;
+    async fn set(&mut self, x: u32);
+}
+
+pub trait Store {
+    async fn get(&self, key: &str) -> Option<String>;
+}
+
+mock! {
+    pub Db {}
+    impl Store for Db {
+        async fn get(&self, key: &str) -> Option<String>;
+    }
+}
+
+async fn lookup<S: Store>(store: &S) -> String {
+    store.get("name").await.unwrap_or_default()
+}
+
+#[test]
+fn return_const() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .return_const(42u32);
+    assert_eq!(block_on(mock.foo(0)), 42);
+}
+
+#[test]
+fn returning() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(predicate::eq(5))
+        .returning(|x| x + 1);
+    assert_eq!(block_on(mock.foo(5)), 6);
+}
+
+#[test]
+fn returning_unit() {
+    let mut mock = MockFoo::new();
+    mock.expect_set()
+        .with(predicate::eq(5))
+        .times(1)
+        .return_const(());
+    block_on(mock.set(5));
+}
+
+#[test]
+fn static_method() {
+    let ctx = MockFoo::bar_context();
+    ctx.expect()
+        .return_const(42u32);
+    assert_eq!(block_on(MockFoo::bar()), 42);
+}
+
+#[test]
+fn generic_method() {
+    let mut mock = MockFoo::new();
+    mock.expect_baz::<u32>()
+        .with(predicate::eq(69u32))
+        .returning(|t| t * 2);
+    assert_eq!(block_on(mock.baz(69u32)), 138u32);
+}
+
+#[test]
+fn generic_caller() {
+    let mut mock = MockDb::new();
+    mock.expect_get()
+        .with(predicate::eq("name"))
+        .returning(|_| Some(String::from("Alice")));
+    assert_eq!(block_on(lookup(&mock)), "Alice");
+}
@@ -0,0 +1,116 @@
+// vim: tw=80
+//! A nice mock returns default values for calls that no expectation matches
+#![deny(warnings)]
+
+use mockall::*;
+
+pub struct NonDefault();
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32) -> u32;
+    fn bar(&mut self) -> Option<String>;
+    fn baz(&self);
+    fn nondefault(&self) -> NonDefault;
+    fn refer(&self) -> &u32;
+    fn generic<T: Default + 'static>(&self) -> T;
+}
+
+mock! {
+    Bar {
+        fn new(x: u32) -> Self;
+        fn bar(&self) -> Vec<u32>;
+    }
+}
+
+#[test]
+fn unconfigured() {
+    let mut mock = MockFoo::new_nice();
+    assert_eq!(0, mock.foo(5));
+    assert_eq!(None, mock.bar());
+    mock.baz();
+    assert_eq!(0u64, mock.generic::<u64>());
+}
+
+#[test]
+fn expectations_still_match() {
+    let mut mock = MockFoo::new_nice();
+    mock.expect_foo()
+        .with(predicate::eq(4))
+        .return_const(42u32);
+    assert_eq!(42, mock.foo(4));
+    assert_eq!(0, mock.foo(5));
+}
+
+#[test]
+#[should_panic(expected =
+    "MockFoo::foo: Expectation(var == 4) called 0 time(s) which is fewer than expected 1")]
+fn expectations_still_verified() {
+    let mut mock = MockFoo::new_nice();
+    mock.expect_foo()
+        .with(predicate::eq(4))
+        .times(1)
+        .return_const(42u32);
+    mock.foo(5);
+}
+
+#[test]
+#[should_panic(expected = "No matching expectation found")]
+fn nondefault() {
+    let mock = MockFoo::new_nice();
+    mock.nondefault();
+}
+
+#[test]
+#[should_panic(expected = "No matching expectation found")]
+fn reference() {
+    let mock = MockFoo::new_nice();
+    mock.refer();
+}
+
+/// new_nice is available even if the struct has its own new method
+#[test]
+fn has_new() {
+    let mock = MockBar::new_nice();
+    assert!(mock.bar().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "No matching expectation found")]
+fn not_nice() {
+    let mock = MockFoo::new();
+    mock.foo(5);
+}
+
+mock! {
+    Nicer {
+        fn new_nice() -> u32;
+    }
+}
+
+/// A mocked method named new_nice replaces the generated constructor
+#[test]
+fn conflicting_name() {
+    let ctx = MockNicer::new_nice_context();
+    ctx.expect().return_const(7u32);
+    assert_eq!(7, MockNicer::new_nice());
+}
+
+/// Calling a nice mock more times than an expectation allows is still an error
+#[test]
+#[should_panic(expected =
+    "MockFoo::foo(1): No matching expectation found.  Expectation(var == 1) was already called 1 times")]
+fn saturated() {
+    let mut mock = MockFoo::new_nice();
+    mock.expect_foo()
+        .with(predicate::eq(1))
+        .times(1)
+        .return_const(10u32);
+    mock.expect_foo()
+        .with(predicate::eq(2))
+        .times(1)
+        .return_const(20u32);
+    assert_eq!(10, mock.foo(1));
+    assert_eq!(20, mock.foo(2));
+    mock.foo(1);
+}
@@ -52,6 +52,22 @@ mod normal_method {
             .return_const(());
         mock.foo(Rc::new(42));
     }
+
+    /// The closure itself may capture non-Send state
+    #[test]
+    fn returning_st_nonsend_closure() {
+        let mut mock = MockFoo::new();
+        let calls = Rc::new(std::cell::Cell::new(0));
+        let calls2 = calls.clone();
+        mock.expect_bar()
+            .returning_st(move || {
+                calls2.set(calls2.get() + 1);
+                Rc::new(calls2.get())
+            });
+        assert_eq!(1, *mock.bar());
+        assert_eq!(2, *mock.bar());
+        assert_eq!(2, calls.get());
+    }
 }
 
 mod ref_method {
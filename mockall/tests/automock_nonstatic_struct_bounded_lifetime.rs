@@ -0,0 +1,66 @@
+// vim: tw=80
+//! Mock a struct whose lifetime parameters have bounds
+#![deny(warnings)]
+
+use mockall::*;
+
+pub struct Parser<'a, 'b: 'a> {
+    _input: &'a &'b str
+}
+
+#[automock]
+impl<'a, 'b: 'a> Parser<'a, 'b> {
+    pub fn next(&mut self) -> Option<u32> {
+        unimplemented!()
+    }
+    pub fn parse(_s: &str) -> u32 {
+        unimplemented!()
+    }
+}
+
+mock! {
+    pub Lexer<'a, 'b: 'a> {
+        fn peek(&self) -> u32;
+    }
+}
+
+#[test]
+fn normal_method() {
+    // This function serves to define named lifetimes
+    fn has_lt<'a, 'b: 'a>(_x: &'a &'b str) {
+        let mut mock = MockParser::<'a, 'b>::default();
+        mock.expect_next()
+            .return_const(Some(5));
+        assert_eq!(Some(5), mock.next());
+    }
+
+    let s = "";
+    has_lt(&s);
+}
+
+#[test]
+fn static_method() {
+    // This function serves to define named lifetimes
+    fn has_lt<'a, 'b: 'a>(_x: &'a &'b str) {
+        let ctx = MockParser::<'a, 'b>::parse_context();
+        ctx.expect()
+            .return_const(6u32);
+        assert_eq!(6, MockParser::parse("x"));
+    }
+
+    let s = "";
+    has_lt(&s);
+}
+
+#[test]
+fn mock_macro() {
+    fn has_lt<'a, 'b: 'a>(_x: &'a &'b str) {
+        let mut mock = MockLexer::<'a, 'b>::default();
+        mock.expect_peek()
+            .return_const(7u32);
+        assert_eq!(7, mock.peek());
+    }
+
+    let s = "";
+    has_lt(&s);
+}
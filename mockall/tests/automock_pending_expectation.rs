@@ -0,0 +1,78 @@
+// vim: tw=80
+//! A PendingExpectation can't take effect until it has a return value.
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32) -> u32;
+    fn bar(&self) -> String;
+}
+
+#[automock]
+trait Bar<T: 'static> {
+    fn bar(&self, x: T) -> T;
+}
+
+#[test]
+fn generic_trait() {
+    let mut mock = MockBar::<i16>::new();
+    mock.expect_bar_pending()
+        .with(predicate::eq(-1))
+        .returning(|x| x * 2);
+    assert_eq!(-2, mock.bar(-1));
+}
+
+#[test]
+fn return_const() {
+    let mut mock = MockFoo::new();
+    mock.expect_bar_pending()
+        .return_const("abc".to_owned());
+    assert_eq!("abc", mock.bar());
+}
+
+#[test]
+fn returning() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo_pending()
+        .with(predicate::eq(4))
+        .times(1)
+        .returning(|x| x + 1);
+    assert_eq!(5, mock.foo(4));
+}
+
+#[test]
+fn returns_expectation() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo_pending()
+        .return_const(42u32)
+        .times(2);
+    assert_eq!(42, mock.foo(0));
+    assert_eq!(42, mock.foo(1));
+}
+
+#[test]
+fn sequence() {
+    let mut seq = Sequence::new();
+    let mut mock = MockFoo::new();
+    mock.expect_foo_pending()
+        .once()
+        .in_sequence(&mut seq)
+        .return_const(1u32);
+    mock.expect_bar_pending()
+        .once()
+        .in_sequence(&mut seq)
+        .returning(String::new);
+    assert_eq!(1, mock.foo(0));
+    assert_eq!("", mock.bar());
+}
+
+#[test]
+#[should_panic(expected = "MockFoo::foo(0): No matching expectation found")]
+fn unfinished() {
+    let mut mock = MockFoo::new();
+    let _ = mock.expect_foo_pending()
+        .with(predicate::eq(0));
+    mock.foo(0);
+}
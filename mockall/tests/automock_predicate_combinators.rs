@@ -0,0 +1,63 @@
+// vim: tw=80
+//! composing argument matchers with and, or, and not
+#![deny(warnings)]
+
+use mockall::*;
+use mockall::predicate::*;
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32, y: &str);
+}
+
+#[test]
+fn and_ok() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(and(gt(10), lt(20)), always())
+        .return_const(());
+    mock.foo(15, "");
+}
+
+#[test]
+#[should_panic(expected = "rejected argument `x`")]
+fn and_fail() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(and(gt(10), lt(20)), always())
+        .return_const(());
+    mock.foo(20, "");
+}
+
+#[test]
+fn nested() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(or(eq(0), and(ge(100), not(eq(150)))),
+              not(or(eq_str("foo"), eq_str("bar"))))
+        .times(2)
+        .return_const(());
+    mock.foo(0, "baz");
+    mock.foo(151, "");
+}
+
+#[test]
+#[should_panic(expected = "rejected argument `y`")]
+fn not_fail() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(always(), not(eq_str("foo")))
+        .return_const(());
+    mock.foo(0, "foo");
+}
+
+#[test]
+fn or_ok() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(or(eq(1), eq(2)), always())
+        .times(2)
+        .return_const(());
+    mock.foo(1, "");
+    mock.foo(2, "");
+}
@@ -0,0 +1,89 @@
+// vim: tw=80
+//! Predicates from the predicates crate, and user-defined ones, work as
+//! argument matchers.
+#![deny(warnings)]
+
+use mockall::automock;
+use predicates::prelude::*;
+use predicates::reflection::PredicateReflection;
+use std::{fmt, path::Path};
+
+#[automock]
+trait Foo {
+    fn float(&self, x: f64);
+    fn path(&self, x: &Path);
+    fn string(&self, x: &str);
+}
+
+/// A predicate defined outside of both mockall and predicates.
+struct IsUpper;
+
+impl Predicate<str> for IsUpper {
+    fn eval(&self, variable: &str) -> bool {
+        variable.chars().all(char::is_uppercase)
+    }
+}
+
+impl PredicateReflection for IsUpper {}
+
+impl fmt::Display for IsUpper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "var is uppercase")
+    }
+}
+
+#[test]
+fn custom() {
+    let mut mock = MockFoo::new();
+    mock.expect_string()
+        .with(IsUpper)
+        .return_const(());
+    mock.string("HELLO");
+}
+
+#[test]
+#[should_panic(expected = "Expectation(var is uppercase) rejected argument `x`")]
+fn custom_fail() {
+    let mut mock = MockFoo::new();
+    mock.expect_string()
+        .with(IsUpper)
+        .return_const(());
+    mock.string("Hello");
+}
+
+#[test]
+fn float() {
+    let mut mock = MockFoo::new();
+    mock.expect_float()
+        .with(predicate::float::is_close(0.3))
+        .return_const(());
+    mock.float(0.1 + 0.2);
+}
+
+#[test]
+fn path() {
+    let mut mock = MockFoo::new();
+    mock.expect_path()
+        .with(predicate::path::is_file())
+        .return_const(());
+    mock.path(Path::new("Cargo.toml"));
+}
+
+#[test]
+fn string() {
+    let mut mock = MockFoo::new();
+    mock.expect_string()
+        .with(predicate::str::starts_with("hello").and(
+            predicate::str::contains("world")))
+        .return_const(());
+    mock.string("hello, world");
+}
+
+#[test]
+fn string_ext() {
+    let mut mock = MockFoo::new();
+    mock.expect_string()
+        .with(predicate::str::diff("hello").trim())
+        .return_const(());
+    mock.string("  hello\n");
+}
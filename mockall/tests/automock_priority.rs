@@ -0,0 +1,112 @@
+// vim: tw=80
+//! An expectation's priority overrides FIFO order when choosing among several
+//! matching expectations
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32) -> u32;
+    fn bar(&mut self, x: u32) -> &mut u32;
+    fn baz(&self, x: u32) -> &u32;
+    fn generic<T: 'static>(&self, t: T) -> u32;
+}
+
+#[test]
+fn higher_priority_wins() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .return_const(0u32);
+    mock.expect_foo()
+        .with(predicate::eq(1))
+        .priority(10)
+        .return_const(10u32);
+    assert_eq!(10, mock.foo(1));
+    assert_eq!(0, mock.foo(2));
+}
+
+/// A negative priority makes a catch-all that's only used as a last resort
+#[test]
+fn negative() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .priority(-1)
+        .return_const(0u32);
+    mock.expect_foo()
+        .with(predicate::eq(1))
+        .return_const(10u32);
+    assert_eq!(10, mock.foo(1));
+    assert_eq!(0, mock.foo(2));
+}
+
+/// Expectations of equal priority are still checked in FIFO order
+#[test]
+fn ties() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .priority(5)
+        .return_const(1u32);
+    mock.expect_foo()
+        .priority(5)
+        .return_const(2u32);
+    assert_eq!(1, mock.foo(0));
+}
+
+/// Saturated expectations are skipped regardless of priority
+#[test]
+fn saturated() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .return_const(0u32);
+    mock.expect_foo()
+        .priority(1)
+        .times(1)
+        .return_const(1u32);
+    assert_eq!(1, mock.foo(0));
+    assert_eq!(0, mock.foo(0));
+}
+
+#[test]
+fn pending() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .return_const(0u32);
+    mock.expect_foo_pending()
+        .priority(1)
+        .return_const(1u32);
+    assert_eq!(1, mock.foo(0));
+}
+
+#[test]
+fn reference() {
+    let mut mock = MockFoo::new();
+    mock.expect_baz()
+        .return_const(0u32);
+    mock.expect_baz()
+        .priority(1)
+        .return_const(1u32);
+    assert_eq!(1, *mock.baz(0));
+}
+
+#[test]
+fn refmut() {
+    let mut mock = MockFoo::new();
+    mock.expect_bar()
+        .return_var(0u32);
+    mock.expect_bar()
+        .priority(1)
+        .return_var(1u32);
+    assert_eq!(1, *mock.bar(0));
+}
+
+#[test]
+fn generic_method() {
+    let mut mock = MockFoo::new();
+    mock.expect_generic::<u8>()
+        .return_const(0u32);
+    mock.expect_generic::<u8>()
+        .priority(1)
+        .return_const(1u32);
+    assert_eq!(1, mock.generic(0u8));
+}
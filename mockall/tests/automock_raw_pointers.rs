@@ -0,0 +1,65 @@
+// vim: tw=80
+//! Raw pointers are neither Send nor Sync, but they can still be used as
+//! arguments and return values, with the help of the `_st` methods.
+#![deny(warnings)]
+
+use mockall::*;
+use std::os::raw::c_void;
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: *const u8) -> *mut c_void;
+    fn bar(&self, x: *mut c_void);
+    fn baz(&self) -> *const u8;
+}
+
+#[test]
+fn with() {
+    let x = 42u8;
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with_st(predicate::eq(&x as *const u8))
+        .returning(|_| std::ptr::null_mut());
+    assert!(mock.foo(&x).is_null());
+}
+
+#[test]
+fn return_const() {
+    let mut x = 42u32;
+    let p = &mut x as *mut u32 as *mut c_void;
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .return_const_st(p);
+    assert_eq!(p, mock.foo(std::ptr::null()));
+}
+
+#[test]
+fn returning() {
+    let x = 42u8;
+    let p = &x as *const u8;
+    let mut mock = MockFoo::new();
+    mock.expect_baz()
+        .returning_st(move || p);
+    assert_eq!(p, mock.baz());
+}
+
+#[test]
+fn withf() {
+    let mut mock = MockFoo::new();
+    mock.expect_bar()
+        .withf(|x| x.is_null())
+        .return_const(());
+    mock.bar(std::ptr::null_mut());
+}
+
+#[test]
+#[should_panic(expected = "No matching expectation found")]
+fn with_st_no_matches() {
+    let x = 42u8;
+    let y = 43u8;
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with_st(predicate::eq(&x as *const u8))
+        .returning(|_| std::ptr::null_mut());
+    mock.foo(&y);
+}
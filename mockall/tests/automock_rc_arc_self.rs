@@ -0,0 +1,46 @@
+// vim: tw=80
+//! Trait methods with `self: Rc<Self>` and `self: Arc<Self>` receivers
+#![deny(warnings)]
+
+use mockall::*;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+
+#[automock]
+pub trait Service {
+    fn handle(self: Arc<Self>, x: u32) -> u32;
+    fn local(self: Rc<Self>) -> u32;
+}
+
+#[test]
+fn arc() {
+    let mut mock = MockService::new();
+    mock.expect_handle()
+        .with(predicate::eq(4))
+        .returning(|x| x * 2);
+    assert_eq!(8, Arc::new(mock).handle(4));
+}
+
+#[test]
+fn arc_shared_between_threads() {
+    let mut mock = MockService::new();
+    mock.expect_handle()
+        .times(2)
+        .returning(|x| x + 1);
+    let service: Arc<dyn Service + Send + Sync> = Arc::new(mock);
+    let s2 = service.clone();
+    let r = thread::spawn(move || s2.handle(1)).join().unwrap();
+    assert_eq!(2, r);
+    assert_eq!(3, service.handle(2));
+}
+
+#[test]
+fn rc() {
+    let mut mock = MockService::new();
+    mock.expect_local()
+        .return_const(7u32);
+    let service = Rc::new(mock);
+    assert_eq!(7, service.clone().local());
+    assert_eq!(7, service.local());
+}
@@ -0,0 +1,125 @@
+// vim: tw=80
+//! A recording mock records unexpected calls instead of panicking immediately
+#![deny(warnings)]
+
+use mockall::*;
+
+pub struct NonDefault();
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32) -> u32;
+    fn bar(&mut self) -> Option<String>;
+    fn nondefault(&self) -> NonDefault;
+    fn refer(&self) -> &u32;
+    fn sfoo() -> u32;
+}
+
+#[test]
+fn take_unexpected() {
+    let mut mock = MockFoo::new_recording();
+    mock.expect_foo()
+        .with(predicate::eq(4))
+        .return_const(42u32);
+    assert_eq!(42, mock.foo(4));
+    assert_eq!(0, mock.foo(5));
+    assert_eq!(None, mock.bar());
+    let unexpected = mock.take_unexpected();
+    assert_eq!(2, unexpected.len());
+    assert!(unexpected[0].starts_with("MockFoo::foo("));
+    assert!(unexpected[0].contains(
+        "No matching expectation found.  Expectation(var == 4) rejected argument `x`"));
+    assert!(unexpected[1].starts_with("MockFoo::bar("));
+    assert!(mock.take_unexpected().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "2 unexpected call(s):")]
+fn checkpoint() {
+    let mut mock = MockFoo::new_recording();
+    mock.foo(1);
+    mock.foo(2);
+    mock.checkpoint();
+}
+
+#[test]
+#[should_panic(expected = "1 unexpected call(s):\n    MockFoo::bar")]
+fn drop() {
+    let mut mock = MockFoo::new_recording();
+    mock.bar();
+}
+
+#[test]
+fn nothing_unexpected() {
+    let mut mock = MockFoo::new_recording();
+    mock.expect_foo()
+        .return_const(42u32);
+    mock.foo(1);
+    mock.checkpoint();
+}
+
+/// An ordinary mock doesn't record anything
+#[test]
+fn not_recording() {
+    let mut mock = MockFoo::new_nice();
+    mock.foo(1);
+    assert!(mock.take_unexpected().is_empty());
+}
+
+mock! {
+    Recorder {
+        fn new_recording(&self) -> u32;
+        fn take_unexpected(&self) -> u32;
+    }
+}
+
+/// Mocked methods with the same names replace the generated ones
+#[test]
+fn conflicting_names() {
+    let mut mock = MockRecorder::new();
+    mock.expect_new_recording().return_const(1u32);
+    mock.expect_take_unexpected().return_const(2u32);
+    assert_eq!(1, mock.new_recording());
+    assert_eq!(2, mock.take_unexpected());
+}
+
+/// Calls that a nice mock couldn't handle either still panic immediately
+mod unrecordable {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "MockFoo::nondefault(): No matching expectation found")]
+    fn nondefault() {
+        let mock = MockFoo::new_recording();
+        mock.nondefault();
+    }
+
+    #[test]
+    #[should_panic(expected = "MockFoo::refer(): No matching expectation found")]
+    fn reference() {
+        let mock = MockFoo::new_recording();
+        mock.refer();
+    }
+
+    #[test]
+    #[should_panic(expected = "MockFoo::sfoo(): No matching expectation found")]
+    fn static_method() {
+        let _mock = MockFoo::new_recording();
+        MockFoo::sfoo();
+    }
+
+    #[test]
+    #[should_panic(expected = "was already called 1 times")]
+    fn saturated() {
+        let mut mock = MockFoo::new_recording();
+        mock.expect_foo()
+            .with(predicate::eq(1))
+            .times(1)
+            .return_const(10u32);
+        mock.expect_foo()
+            .with(predicate::eq(2))
+            .return_const(20u32);
+        mock.foo(1);
+        mock.foo(1);
+    }
+}
@@ -0,0 +1,82 @@
+// vim: tw=80
+//! A method may return a reference borrowed from one of its arguments, rather
+//! than from self.  The returning closure receives the argument and produces
+//! the borrow.
+#![deny(warnings)]
+
+use mockall::*;
+use std::collections::HashMap;
+
+type Map = HashMap<u32, String>;
+
+#[automock]
+trait Foo {
+    fn get<'a>(&self, map: &'a Map, k: u32) -> &'a String;
+    fn get_mut<'a>(&self, v: &'a mut Vec<u32>, i: usize) -> &'a mut u32;
+    fn first<'a, T: 'static>(&self, v: &'a [T]) -> &'a T;
+    fn pick<'a>(x: &'a str, y: &'a str) -> &'a str;
+}
+
+static DEFAULT: String = String::new();
+
+#[test]
+fn generic() {
+    let mut mock = MockFoo::new();
+    mock.expect_first::<u8>()
+        .returning(|v| &v[0]);
+    assert_eq!(5, *mock.first(&[5u8, 6, 7]));
+}
+
+#[test]
+fn mutable() {
+    let mut mock = MockFoo::new();
+    mock.expect_get_mut()
+        .returning(|v, i| &mut v[i]);
+    let mut v = vec![1, 2, 3];
+    *mock.get_mut(&mut v, 1) = 20;
+    assert_eq!(vec![1, 20, 3], v);
+}
+
+#[test]
+fn return_const() {
+    let mut mock = MockFoo::new();
+    mock.expect_get()
+        .return_const(&DEFAULT);
+    let m = Map::new();
+    assert_eq!("", mock.get(&m, 1));
+}
+
+#[test]
+fn returning() {
+    let mut mock = MockFoo::new();
+    mock.expect_get()
+        .returning(|map, k| &map[&k]);
+    let mut m = Map::new();
+    m.insert(1, String::from("one"));
+    assert_eq!("one", mock.get(&m, 1));
+}
+
+#[test]
+fn static_method() {
+    let ctx = MockFoo::pick_context();
+    ctx.expect()
+        .returning(|x, y| if x.len() >= y.len() { x } else { y });
+    assert_eq!("abc", MockFoo::pick("abc", "de"));
+}
+
+mock! {
+    Bar {
+        fn get<'a>(&self, map: &'a Map, k: u32) -> &'a String;
+    }
+}
+
+#[test]
+fn mock_struct() {
+    let mut mock = MockBar::new();
+    mock.expect_get()
+        .with(predicate::always(), predicate::eq(2))
+        .returning(|map, k| &map[&k]);
+    let mut m = Map::new();
+    m.insert(2, String::from("two"));
+    assert_eq!("two", mock.get(&m, 2));
+}
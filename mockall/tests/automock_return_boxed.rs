@@ -0,0 +1,89 @@
+// vim: tw=80
+//! Methods returning `Box<dyn Trait>` can use return_boxed and returning_boxed
+#![deny(warnings)]
+
+use mockall::*;
+use std::fmt::Debug;
+
+pub trait Shape {
+    fn area(&self) -> u32;
+}
+
+struct Square(u32);
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+// Deliberately not Clone
+struct Rect(u32, u32);
+impl Shape for Rect {
+    fn area(&self) -> u32 {
+        self.0 * self.1
+    }
+}
+
+#[automock]
+pub trait Factory {
+    fn make(&self, x: u32) -> Box<dyn Shape + Send>;
+    fn make_static() -> Box<dyn Shape + Send>;
+    fn debug(&self) -> impl Debug;
+}
+
+#[test]
+fn return_boxed() {
+    let mut mock = MockFactory::new();
+    mock.expect_make()
+        .return_boxed(Rect(2, 3));
+    assert_eq!(6, mock.make(0).area());
+}
+
+#[test]
+#[should_panic(expected =
+    "MockFactory::make: Expectation(<anything>) called twice, but it returns by move")]
+fn return_boxed_twice() {
+    let mut mock = MockFactory::new();
+    mock.expect_make()
+        .return_boxed(Rect(2, 3));
+    mock.make(0);
+    mock.make(0);
+}
+
+#[test]
+fn return_boxed_impl_trait() {
+    let mut mock = MockFactory::new();
+    mock.expect_debug()
+        .return_boxed(42u32);
+    assert_eq!("42", format!("{:?}", mock.debug()));
+}
+
+#[test]
+fn returning_boxed() {
+    let mut mock = MockFactory::new();
+    mock.expect_make()
+        .returning_boxed(Square);
+    assert_eq!(4, mock.make(2).area());
+    assert_eq!(9, mock.make(3).area());
+}
+
+#[test]
+fn returning_boxed_with_matcher() {
+    let mut mock = MockFactory::new();
+    mock.expect_make()
+        .with(predicate::eq(5))
+        .returning_boxed(|x| Rect(x, 2));
+    assert_eq!(10, mock.make(5).area());
+}
+
+#[test]
+fn static_method() {
+    let ctx = MockFactory::make_static_context();
+    ctx.expect()
+        .times(1)
+        .return_boxed(Square(5));
+    assert_eq!(25, MockFactory::make_static().area());
+    ctx.expect()
+        .returning_boxed(|| Rect(1, 7));
+    assert_eq!(7, MockFactory::make_static().area());
+}
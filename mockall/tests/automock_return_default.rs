@@ -0,0 +1,72 @@
+// vim: tw=80
+//! Expectations can return the default value of their return types
+#![deny(warnings)]
+
+use mockall::*;
+
+pub struct NonDefault(u32);
+
+#[automock]
+trait Foo {
+    fn foo(&self) -> u32;
+    fn bar(&self, x: u32) -> Vec<u32>;
+    fn baz(&self, x: u32);
+    // Should still be mockable, even though it can't return_default
+    fn nondefault(&self) -> NonDefault;
+    fn generic<T: Default + 'static>(&self) -> T;
+    fn stat() -> String;
+}
+
+#[test]
+fn generic_method() {
+    let mut mock = MockFoo::new();
+    mock.expect_generic::<i16>()
+        .return_default();
+    assert_eq!(0, mock.generic::<i16>());
+}
+
+#[test]
+fn nondefault() {
+    let mut mock = MockFoo::new();
+    mock.expect_nondefault()
+        .returning(|| NonDefault(5));
+    assert_eq!(5, mock.nondefault().0);
+}
+
+#[test]
+fn pending() {
+    let mut mock = MockFoo::new();
+    mock.expect_bar_pending()
+        .with(predicate::eq(5))
+        .return_default();
+    assert!(mock.bar(5).is_empty());
+}
+
+#[test]
+fn return_default() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .return_default();
+    mock.expect_bar()
+        .return_default();
+    assert_eq!(0, mock.foo());
+    assert!(mock.bar(42).is_empty());
+}
+
+#[test]
+fn static_method() {
+    let ctx = MockFoo::stat_context();
+    ctx.expect()
+        .return_default();
+    assert_eq!("", MockFoo::stat());
+}
+
+/// Methods that return () don't need an explicit return value
+#[test]
+fn unit() {
+    let mut mock = MockFoo::new();
+    mock.expect_baz()
+        .with(predicate::eq(42))
+        .times(1);
+    mock.baz(42);
+}
@@ -0,0 +1,37 @@
+// vim: tw=80
+//! Methods that return references with named lifetimes, rather than elided
+//! ones
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+trait Foo {
+    fn foo<'a>(&'a self) -> &'a u32;
+    fn bar<'a>(&'a self, x: u32) -> &'a str;
+    fn baz<'a>(&'a mut self) -> &'a mut u32;
+}
+
+#[test]
+fn return_const() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo().return_const(5u32);
+    assert_eq!(5, *mock.foo());
+}
+
+#[test]
+fn return_str() {
+    let mut mock = MockFoo::new();
+    mock.expect_bar()
+        .with(predicate::eq(4))
+        .return_const("four".to_owned());
+    assert_eq!("four", mock.bar(4));
+}
+
+#[test]
+fn return_var() {
+    let mut mock = MockFoo::new();
+    mock.expect_baz().return_var(5u32);
+    *mock.baz() += 1;
+    assert_eq!(6, *mock.baz());
+}
@@ -0,0 +1,49 @@
+// vim: tw=80
+//! Methods that return Self, including builder-style methods that consume self
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+pub trait Builder {
+    fn with_x(self, x: u32) -> Self;
+    fn duplicate(&self) -> Self;
+    fn maybe(&self) -> Option<Self> where Self: Sized;
+    fn build(&self) -> u32;
+}
+
+fn build_with<B: Builder>(b: B, x: u32) -> u32 {
+    b.with_x(x).build()
+}
+
+#[test]
+fn builder() {
+    let mut mock = MockBuilder::new();
+    mock.expect_with_x()
+        .with(predicate::eq(7))
+        .times(1)
+        .returning(|x| {
+            let mut built = MockBuilder::new();
+            built.expect_build().return_const(x * 2);
+            built
+        });
+    assert_eq!(14, build_with(mock, 7));
+}
+
+#[test]
+fn by_ref() {
+    let mut configured = MockBuilder::new();
+    configured.expect_build().return_const(3u32);
+    let mut mock = MockBuilder::new();
+    mock.expect_duplicate()
+        .return_once(move || configured);
+    assert_eq!(3, mock.duplicate().build());
+}
+
+#[test]
+fn wrapped() {
+    let mut mock = MockBuilder::new();
+    mock.expect_maybe()
+        .returning(|| None);
+    assert!(mock.maybe().is_none());
+}
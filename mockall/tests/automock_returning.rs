@@ -0,0 +1,50 @@
+// vim: tw=80
+//! returning() computes the return value from the arguments, on every call
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+trait Foo {
+    fn add(&self, x: u32, y: u32) -> u32;
+    fn concat(&self, a: String, b: &str) -> String;
+    fn next(&mut self) -> u32;
+}
+
+#[test]
+fn from_args() {
+    let mut mock = MockFoo::new();
+    mock.expect_add()
+        .returning(|x, y| x + y);
+    assert_eq!(3, mock.add(1, 2));
+    assert_eq!(30, mock.add(10, 20));
+}
+
+/// Owned arguments are moved into the closure, and references stay references
+#[test]
+fn non_copy_args() {
+    let mut mock = MockFoo::new();
+    mock.expect_concat()
+        .returning(|mut a: String, b: &str| {
+            a.push_str(b);
+            a
+        });
+    assert_eq!("foobar", mock.concat("foo".to_owned(), "bar"));
+    assert_eq!("bazqux", mock.concat("baz".to_owned(), "qux"));
+}
+
+/// The closure is FnMut, so it can keep state between calls
+#[test]
+fn stateful() {
+    let mut mock = MockFoo::new();
+    let mut count = 0;
+    mock.expect_next()
+        .times(3)
+        .returning(move || {
+            count += 1;
+            count
+        });
+    assert_eq!(1, mock.next());
+    assert_eq!(2, mock.next());
+    assert_eq!(3, mock.next());
+}
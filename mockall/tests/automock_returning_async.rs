@@ -0,0 +1,71 @@
+// vim: tw=80
+//! Methods returning `Pin<Box<dyn Future>>` can use returning_future and
+//! returning_async
+#![deny(warnings)]
+
+use futures::{Future, FutureExt, executor::block_on};
+use mockall::*;
+use std::pin::Pin;
+
+#[automock]
+pub trait Foo {
+    fn foo(&self, x: u32) -> Pin<Box<dyn Future<Output=u32> + Send>>;
+    fn bar(&self) -> Pin<Box<dyn Future<Output=String>>>;
+    fn baz(&self) -> impl Future<Output=u32>;
+    fn bean() -> Pin<Box<dyn Future<Output=u32> + Send + Sync + 'static>>;
+}
+
+#[test]
+fn returning_future() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .returning_future(42u32);
+    assert_eq!(42, block_on(mock.foo(0)));
+    assert_eq!(42, block_on(mock.foo(1)));
+}
+
+#[test]
+fn returning_future_into() {
+    let mut mock = MockFoo::new();
+    mock.expect_bar()
+        .returning_future("hello");
+    assert_eq!("hello", block_on(mock.bar()));
+}
+
+#[test]
+fn returning_future_impl_future() {
+    let mut mock = MockFoo::new();
+    mock.expect_baz()
+        .returning_future(42u32);
+    assert_eq!(42, mock.baz().now_or_never().unwrap());
+}
+
+#[test]
+fn returning_async() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .returning_async(|x| async move { x + 1 });
+    assert_eq!(5, block_on(mock.foo(4)));
+    assert_eq!(6, block_on(mock.foo(5)));
+}
+
+#[test]
+fn returning_async_with_matcher() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(predicate::eq(4))
+        .returning_async(|x| async move { x * 2 });
+    assert_eq!(8, block_on(mock.foo(4)));
+}
+
+#[test]
+fn static_method() {
+    let ctx = MockFoo::bean_context();
+    ctx.expect()
+        .times(1)
+        .returning_future(5u32);
+    assert_eq!(5, block_on(MockFoo::bean()));
+    ctx.expect()
+        .returning_async(|| async { 7 });
+    assert_eq!(7, block_on(MockFoo::bean()));
+}
@@ -0,0 +1,125 @@
+// vim: tw=80
+//! A Scenario verifies expectations from several mock objects together
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+pub trait Foo {
+    fn foo(&self) -> u32;
+}
+
+#[automock]
+pub trait Bar {
+    fn bar(&self, x: u32);
+}
+
+#[test]
+#[should_panic(expected = "Scenario has 1 unsatisfied expectation(s)")]
+fn checkpoint_defers_to_scenario() {
+    let mut scenario = Scenario::new();
+    let mut foo = MockFoo::new();
+    foo.expect_foo()
+        .times(1)
+        .in_scenario(&mut scenario)
+        .return_const(42u32);
+    foo.checkpoint();
+    // The expectation was removed from the mock, but the scenario still
+    // remembers it.
+    scenario.verify();
+}
+
+#[test]
+fn drop_mocks_first() {
+    let mut scenario = Scenario::new();
+    {
+        let mut foo = MockFoo::new();
+        foo.expect_foo()
+            .times(1)
+            .in_scenario(&mut scenario)
+            .return_const(42u32);
+        foo.foo();
+    }
+    scenario.verify();
+}
+
+#[test]
+#[should_panic(expected = "Scenario has 1 unsatisfied expectation(s)")]
+fn drop_scenario_unverified() {
+    let mut scenario = Scenario::new();
+    let mut foo = MockFoo::new();
+    foo.expect_foo()
+        .times(1)
+        .in_scenario(&mut scenario)
+        .return_const(42u32);
+}
+
+#[test]
+fn pending() {
+    let mut scenario = Scenario::new();
+    let mut foo = MockFoo::new();
+    foo.expect_foo_pending()
+        .times(1)
+        .in_scenario(&mut scenario)
+        .return_const(42u32);
+    assert_eq!(42, foo.foo());
+    scenario.verify();
+}
+
+#[test]
+fn satisfied() {
+    let mut scenario = Scenario::new();
+    let mut foo = MockFoo::new();
+    let mut bar = MockBar::new();
+    foo.expect_foo()
+        .times(1)
+        .in_scenario(&mut scenario)
+        .return_const(42u32);
+    bar.expect_bar()
+        .with(predicate::eq(42))
+        .times(1..3)
+        .in_scenario(&mut scenario)
+        .return_const(());
+    bar.bar(foo.foo());
+    scenario.verify();
+}
+
+#[test]
+#[should_panic(expected = "Method sequence violation")]
+fn sequence_violation() {
+    let mut scenario = Scenario::new();
+    let mut foo = MockFoo::new();
+    let mut bar = MockBar::new();
+    foo.expect_foo()
+        .times(1)
+        .in_scenario(&mut scenario)
+        .in_sequence(scenario.sequence())
+        .return_const(42u32);
+    bar.expect_bar()
+        .times(1)
+        .in_scenario(&mut scenario)
+        .in_sequence(scenario.sequence())
+        .return_const(());
+    bar.bar(42);
+}
+
+#[test]
+#[should_panic(expected = "Scenario has 2 unsatisfied expectation(s):
+    MockFoo::foo: Expectation(<anything>) called 0 time(s) which is fewer than expected 1
+    MockBar::bar: Expectation(var == 5) called 1 time(s) which is fewer than expected 2")]
+fn unsatisfied() {
+    let mut scenario = Scenario::new();
+    let mut foo = MockFoo::new();
+    let mut bar = MockBar::new();
+    foo.expect_foo()
+        .times(1)
+        .in_scenario(&mut scenario)
+        .return_const(42u32);
+    bar.expect_bar()
+        .with(predicate::eq(5))
+        .times(2)
+        .in_scenario(&mut scenario)
+        .return_const(());
+    bar.bar(5);
+    scenario.verify();
+}
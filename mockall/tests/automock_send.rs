@@ -1,17 +1,66 @@
 // vim: tw=80
-//! A mock object should be Send
+//! A mock object should be Send and Sync
 #![deny(warnings)]
 
 use mockall::*;
+use std::rc::Rc;
+use std::thread;
 
 #[automock]
 trait T {
     fn foo(&self) -> u32;
 }
 
+#[automock]
+pub trait U {
+    fn foo(&self) -> u32;
+    fn bar(&self, x: &u32) -> &u32;
+    fn baz<Q: 'static>(&self, q: Q) -> Q;
+    fn bean(x: u32) -> u32;
+}
+
+// Methods returning non-'static references to non-Send types make the mock
+// non-Send, but that shouldn't prevent mocking.
+#[automock]
+trait NonSendRef {
+    fn foo(&self) -> &Rc<u32>;
+}
+
 #[test]
 #[allow(clippy::unnecessary_operation)] // The cast is the whole point
 fn cast_to_send() {
     let mock = MockT::new();
     let _m = Box::new(mock) as Box<dyn T + Send>;
 }
+
+#[test]
+#[allow(clippy::unnecessary_operation)] // The cast is the whole point
+fn cast_to_send_sync() {
+    let mock = MockT::new();
+    let _m = Box::new(mock) as Box<dyn T + Send + Sync>;
+}
+
+#[test]
+fn send_sync() {
+    fn is_send_sync<X: Send + Sync>() {}
+    is_send_sync::<MockU>();
+}
+
+#[test]
+fn move_to_thread() {
+    let mut mock = MockU::new();
+    mock.expect_foo()
+        .return_const(42u32);
+    mock.expect_bar()
+        .return_const(5u32);
+    let r = thread::spawn(move || mock.foo() + mock.bar(&0)).join().unwrap();
+    assert_eq!(47, r);
+}
+
+#[test]
+fn non_send_ref() {
+    let mut mock = MockNonSendRef::new();
+    mock.expect_foo()
+        .return_const(Rc::new(42u32));
+    assert_eq!(42, **mock.foo());
+}
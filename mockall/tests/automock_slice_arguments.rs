@@ -13,12 +13,9 @@ mod withf {
     use super::*;
 
     #[test]
-    #[cfg_attr(feature = "nightly", should_panic(
-            expected = "MockFoo::foo([1, 2, 3, 4]): No matching expectation found"
-    ))]
-    #[cfg_attr(not(feature = "nightly"), should_panic(
-            expected = "MockFoo::foo(?): No matching expectation found"
-    ))]
+    #[should_panic(
+        expected = "MockFoo::foo([1, 2, 3, 4]): No matching expectation found"
+    )]
     fn fail() {
         let mut mock = MockFoo::new();
         mock.expect_foo()
@@ -38,3 +35,60 @@ mod withf {
         mock.foo(&x);
     }
 }
+
+mod slice_predicates {
+    use super::*;
+    use mockall::predicate::slice;
+
+    #[test]
+    fn contains() {
+        let mut mock = MockFoo::new();
+        mock.expect_foo()
+            .with(slice::contains(3))
+            .times(2)
+            .returning(|_| ());
+        mock.foo(&[3]);
+        mock.foo(&[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn eq() {
+        let mut mock = MockFoo::new();
+        mock.expect_foo()
+            .with(slice::eq(&[1, 2, 3]))
+            .returning(|_| ());
+        let x = vec![1, 2, 3];
+        mock.foo(&x);
+    }
+
+    #[test]
+    #[should_panic(expected =
+        "Expectation(var == [1, 2, 3]) rejected argument `x`")]
+    fn eq_fail() {
+        let mut mock = MockFoo::new();
+        mock.expect_foo()
+            .with(slice::eq(&[1, 2, 3]))
+            .returning(|_| ());
+        mock.foo(&[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn len() {
+        let mut mock = MockFoo::new();
+        mock.expect_foo()
+            .with(slice::len(4))
+            .returning(|_| ());
+        mock.foo(&[5, 6, 7, 8]);
+    }
+
+    #[test]
+    #[should_panic(expected =
+        "Expectation(var.len() == 4) rejected argument `x`")]
+    fn len_fail() {
+        let mut mock = MockFoo::new();
+        mock.expect_foo()
+            .with(slice::len(4))
+            .returning(|_| ());
+        mock.foo(&[5, 6, 7]);
+    }
+}
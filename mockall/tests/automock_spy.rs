@@ -0,0 +1,214 @@
+// vim: tw=80
+//! A spy forwards calls that no expectation matches to a real object
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+trait Counter {
+    fn get(&self) -> u32;
+    fn add(&mut self, x: u32) -> u32;
+    fn name(&self, prefix: &str) -> String;
+}
+
+#[automock(type Item=u32;)]
+trait Source {
+    type Item;
+    fn next_item(&mut self) -> Option<Self::Item>;
+}
+
+#[derive(Default)]
+struct RealCounter(u32);
+
+impl Counter for RealCounter {
+    fn get(&self) -> u32 {
+        self.0
+    }
+    fn add(&mut self, x: u32) -> u32 {
+        self.0 += x;
+        self.0
+    }
+    fn name(&self, prefix: &str) -> String {
+        format!("{}{}", prefix, self.0)
+    }
+}
+
+struct RealSource(u32);
+
+impl Source for RealSource {
+    type Item = u32;
+    fn next_item(&mut self) -> Option<u32> {
+        self.0 += 1;
+        Some(self.0)
+    }
+}
+
+#[test]
+fn forwards_unmatched_calls() {
+    let mock = MockCounter::spy(RealCounter(5));
+    assert_eq!(5, mock.get());
+    assert_eq!("n5", mock.name("n"));
+}
+
+#[test]
+fn expectations_take_precedence() {
+    let mut mock = MockCounter::spy(RealCounter(5));
+    mock.expect_get()
+        .times(1)
+        .return_const(42u32);
+    mock.expect_name()
+        .with(predicate::eq("x"))
+        .returning(|_| "mocked".to_owned());
+    assert_eq!(42, mock.get());
+    assert_eq!("mocked", mock.name("x"));
+    assert_eq!("y5", mock.name("y"));
+}
+
+/// Mutating methods change the state of the real object
+#[test]
+fn mutable() {
+    let mut mock = MockCounter::spy(RealCounter::default());
+    assert_eq!(3, mock.add(3));
+    assert_eq!(7, mock.add(4));
+    assert_eq!(7, mock.get());
+}
+
+/// Calls that are handled by expectations aren't forwarded
+#[test]
+fn mutable_intercepted() {
+    let mut mock = MockCounter::spy(RealCounter::default());
+    mock.expect_add()
+        .with(predicate::eq(100))
+        .return_const(0u32);
+    assert_eq!(0, mock.add(100));
+    assert_eq!(1, mock.add(1));
+    assert_eq!(1, mock.get());
+}
+
+/// Forwarded calls don't excuse unsatisfied expectations
+#[test]
+#[should_panic(expected = "called 0 time(s) which is fewer than expected 1")]
+fn unsatisfied() {
+    let mut mock = MockCounter::spy(RealCounter::default());
+    mock.expect_name()
+        .with(predicate::eq("x"))
+        .times(1)
+        .returning(|_| "mocked".to_owned());
+    mock.name("y");
+}
+
+#[test]
+fn associated_types() {
+    let mut mock = MockSource::spy(RealSource(0));
+    assert_eq!(Some(1), mock.next_item());
+    assert_eq!(Some(2), mock.next_item());
+}
+
+#[test]
+fn checkpoint() {
+    let mut mock = MockCounter::spy(RealCounter(1));
+    mock.expect_get()
+        .times(1)
+        .return_const(0u32);
+    assert_eq!(0, mock.get());
+    mock.checkpoint();
+    // Spying survives a checkpoint
+    assert_eq!(1, mock.get());
+}
+
+/// Forwarded calls can be verified with an explicit forwarding expectation
+#[test]
+fn forward() {
+    let mut mock = MockCounter::spy(RealCounter::default());
+    mock.expect_add()
+        .times(2)
+        .forward();
+    assert_eq!(3, mock.add(3));
+    assert_eq!(7, mock.add(4));
+    mock.checkpoint();
+}
+
+/// Forwarding counts as a return value for a pending expectation
+#[test]
+fn forward_pending() {
+    let mut mock = MockCounter::spy(RealCounter(2));
+    mock.expect_get_pending()
+        .times(1)
+        .forward();
+    assert_eq!(2, mock.get());
+    mock.checkpoint();
+}
+
+#[test]
+#[should_panic(expected =
+    "MockCounter::add: Expectation(<anything>) called 1 time(s) which is fewer than expected 2")]
+fn forward_unsatisfied() {
+    let mut mock = MockCounter::spy(RealCounter::default());
+    mock.expect_add()
+        .times(2)
+        .forward();
+    mock.add(3);
+}
+
+/// A forwarding expectation can be limited to certain arguments
+#[test]
+fn forward_with() {
+    let mut mock = MockCounter::spy(RealCounter(5));
+    mock.expect_name()
+        .with(predicate::eq("x"))
+        .times(1)
+        .forward();
+    mock.expect_name()
+        .returning(|_| "mocked".to_owned());
+    assert_eq!("x5", mock.name("x"));
+    assert_eq!("mocked", mock.name("y"));
+}
+
+#[test]
+#[should_panic(expected =
+    "MockCounter::get: Expectation(<anything>) can only forward calls for mock objects created with `spy`")]
+fn forward_without_spy() {
+    let mut mock = MockCounter::new();
+    mock.expect_get().forward();
+    mock.get();
+}
+
+/// Each instantiation of a generic trait gets its own spy expectations
+mod generic_trait {
+    use super::*;
+
+    pub trait Scale<T> {
+        fn scale(&self, t: T) -> u32;
+    }
+
+    mock! {
+        pub Scaler {}
+        impl Scale<u8> for Scaler {
+            fn scale(&self, t: u8) -> u32;
+        }
+        impl Scale<u16> for Scaler {
+            fn scale(&self, t: u16) -> u32;
+        }
+    }
+
+    struct RealScaler;
+
+    impl Scale<u8> for RealScaler {
+        fn scale(&self, t: u8) -> u32 {
+            u32::from(t) * 2
+        }
+    }
+
+    impl Scale<u16> for RealScaler {
+        fn scale(&self, t: u16) -> u32 {
+            u32::from(t) * 3
+        }
+    }
+
+    #[test]
+    fn forwards() {
+        let mock = MockScaler::spy(RealScaler);
+        assert_eq!(10, mock.scale(5u8));
+        assert_eq!(15, mock.scale(5u16));
+    }
+}
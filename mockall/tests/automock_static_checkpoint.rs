@@ -0,0 +1,65 @@
+// vim: tw=80
+//! Static methods' expectations can be checkpointed without a Context object
+#![deny(warnings)]
+
+use mockall::*;
+use std::sync::Mutex;
+
+lazy_static! {
+    static ref FOO_MTX: Mutex<()> = Mutex::new(());
+}
+
+#[automock]
+pub trait Foo {
+    fn foo(x: u32) -> u32;
+}
+
+#[automock]
+pub trait Bar<T: 'static> {
+    fn bar(x: T) -> T;
+}
+
+#[test]
+fn ok() {
+    let _m = FOO_MTX.lock().unwrap_or_else(|e| e.into_inner());
+    let ctx = MockFoo::foo_context();
+    ctx.expect()
+        .times(1)
+        .returning(|x| x + 1);
+    assert_eq!(5, MockFoo::foo(4));
+    MockFoo::foo_checkpoint();
+}
+
+#[test]
+fn clears_expectations() {
+    let _m = FOO_MTX.lock().unwrap_or_else(|e| e.into_inner());
+    let ctx = MockFoo::foo_context();
+    ctx.expect()
+        .returning(|x| x + 1);
+    MockFoo::foo_checkpoint();
+    ctx.expect()
+        .returning(|x| x + 2);
+    assert_eq!(6, MockFoo::foo(4));
+}
+
+#[test]
+#[should_panic(expected =
+    "MockFoo::foo: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+fn unsatisfied() {
+    let _m = FOO_MTX.lock().unwrap_or_else(|e| e.into_inner());
+    let ctx = MockFoo::foo_context();
+    ctx.expect()
+        .times(1)
+        .returning(|x| x + 1);
+    MockFoo::foo_checkpoint();
+}
+
+#[test]
+fn generic_struct() {
+    let ctx = MockBar::<u32>::bar_context();
+    ctx.expect()
+        .times(1)
+        .returning(|x| x + 1);
+    assert_eq!(5, MockBar::<u32>::bar(4));
+    MockBar::<u32>::bar_checkpoint();
+}
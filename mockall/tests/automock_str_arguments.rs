@@ -0,0 +1,65 @@
+// vim: tw=80
+//! matching string arguments with eq_str
+#![deny(warnings)]
+
+use mockall::*;
+use mockall::predicate::*;
+use std::borrow::Cow;
+
+#[automock]
+trait Foo {
+    fn borrowed(&self, x: &str);
+    fn cow(&self, x: Cow<'static, str>);
+    fn owned(&self, x: String);
+}
+
+#[test]
+fn borrowed() {
+    let mut mock = MockFoo::new();
+    mock.expect_borrowed()
+        .with(eq_str("hello"))
+        .return_const(());
+    mock.borrowed("hello");
+}
+
+#[test]
+#[should_panic(expected =
+    "Expectation(var == \"hello\") rejected argument `x`")]
+fn borrowed_fail() {
+    let mut mock = MockFoo::new();
+    mock.expect_borrowed()
+        .with(eq_str("hello"))
+        .return_const(());
+    mock.borrowed("goodbye");
+}
+
+#[test]
+fn cow() {
+    let mut mock = MockFoo::new();
+    mock.expect_cow()
+        .with(eq_str("hello"))
+        .times(2)
+        .return_const(());
+    mock.cow(Cow::Borrowed("hello"));
+    mock.cow(Cow::Owned(String::from("hello")));
+}
+
+/// The expected value needn't be 'static
+#[test]
+fn nonstatic() {
+    let expected = format!("{}-{}", "hello", 42);
+    let mut mock = MockFoo::new();
+    mock.expect_borrowed()
+        .with(eq_str(&expected))
+        .return_const(());
+    mock.borrowed("hello-42");
+}
+
+#[test]
+fn owned() {
+    let mut mock = MockFoo::new();
+    mock.expect_owned()
+        .with(eq_str("hello"))
+        .return_const(());
+    mock.owned(String::from("hello"));
+}
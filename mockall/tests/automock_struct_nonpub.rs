@@ -0,0 +1,62 @@
+// vim: tw=80
+//! Every method of an automocked inherent impl is mocked, whatever its
+//! visibility, and the mock methods keep the original visibilities.
+#![deny(warnings)]
+
+mod outer {
+    use mockall::automock;
+
+    pub struct Foo {}
+
+    // The real methods are never called in this test
+    #[allow(dead_code)]
+    #[automock]
+    impl Foo {
+        pub fn foo(&self) -> u32 {
+            self.helper()
+        }
+        pub(crate) fn bar(&self, x: u32) -> u32 {
+            x
+        }
+        pub(super) fn baz(x: u32) -> u32 {
+            x
+        }
+        fn helper(&self) -> u32 {
+            0
+        }
+    }
+
+    #[test]
+    fn private() {
+        let mut mock = MockFoo::new();
+        mock.expect_helper()
+            .return_const(42u32);
+        assert_eq!(42, mock.helper());
+    }
+}
+
+use outer::MockFoo;
+
+#[test]
+fn public() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .return_const(42u32);
+    assert_eq!(42, mock.foo());
+}
+
+#[test]
+fn pub_crate() {
+    let mut mock = MockFoo::new();
+    mock.expect_bar()
+        .returning(|x| x + 1);
+    assert_eq!(5, mock.bar(4));
+}
+
+#[test]
+fn pub_super() {
+    let ctx = MockFoo::baz_context();
+    ctx.expect()
+        .returning(|x| x + 2);
+    assert_eq!(6, MockFoo::baz(4));
+}
@@ -0,0 +1,85 @@
+// vim: tw=80
+//! Methods marked with #[mockall::stub] aren't mocked.  They return a default
+//! value, or the attribute's expression, instead.
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+pub trait Foo {
+    fn foo(&self, x: u32) -> u32;
+    #[mockall::stub]
+    fn name(&self) -> String;
+    #[mockall::stub(x + y)]
+    fn add(&self, x: u32, y: u32) -> u32;
+    #[mockall::stub]
+    fn sfoo() -> Option<u32>;
+    #[mockall::stub]
+    fn unit(&mut self);
+    #[mockall::stub]
+    fn ignore(&self, x: u32, y: u32) -> u32;
+}
+
+pub struct Bar {}
+#[automock]
+impl Bar {
+    #[mockall::stub(7)]
+    pub fn bar(&self) -> u32 {
+        unimplemented!()
+    }
+    pub fn baz(&self) -> u32 {
+        unimplemented!()
+    }
+}
+
+pub trait Beez {
+    fn beez(&self, x: u32) -> u32;
+    fn boo(&self) -> Vec<u32>;
+}
+
+mock! {
+    pub Bean {
+        #[mockall::stub(String::from("bean"))]
+        fn kind(&self) -> String;
+        fn weight(&self) -> u32;
+        #[mockall::stub(5)]
+        fn ignore(&self, x: u32) -> u32;
+    }
+    impl Beez for Bean {
+        #[mockall::stub(x * 10)]
+        fn beez(&self, x: u32) -> u32;
+        #[mockall::stub]
+        fn boo(&self) -> Vec<u32>;
+    }
+}
+
+#[test]
+fn automock_trait() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo().return_const(4u32);
+    assert_eq!(4, mock.foo(1));
+    assert_eq!("", mock.name());
+    assert_eq!(5, mock.add(2, 3));
+    assert_eq!(None, MockFoo::sfoo());
+    mock.unit();
+    assert_eq!(0, mock.ignore(1, 2));
+}
+
+#[test]
+fn automock_struct() {
+    let mut mock = MockBar::new();
+    mock.expect_baz().return_const(1u32);
+    assert_eq!(7, mock.bar());
+    assert_eq!(1, mock.baz());
+}
+
+#[test]
+fn mock() {
+    let mut mock = MockBean::new();
+    mock.expect_weight().return_const(2u32);
+    assert_eq!("bean", mock.kind());
+    assert_eq!(2, mock.weight());
+    assert_eq!(5, mock.ignore(1));
+    assert_eq!(30, mock.beez(3));
+    assert!(mock.boo().is_empty());
+}
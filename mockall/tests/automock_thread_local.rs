@@ -0,0 +1,117 @@
+// vim: tw=80
+//! With thread_local, each thread gets its own expectations for static methods,
+//! so tests that mock the same static method can run in parallel.
+#![deny(warnings)]
+
+use mockall::*;
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+#[automock(thread_local)]
+pub trait Foo {
+    fn foo(x: u32) -> u32;
+    fn bar<T: 'static>(x: T) -> T;
+    fn baz(&self) -> u32;
+}
+
+pub struct Bean {}
+#[automock(thread_local)]
+impl Bean {
+    pub fn bean(x: u32) -> u32 {
+        x
+    }
+}
+
+#[automock(thread_local)]
+pub mod m {
+    pub fn foo(x: u32) -> u32 {
+        x
+    }
+}
+
+/// Run `f` on several threads at once, each with a different `i`.  The
+/// barrier makes every thread set its expectations before any of them calls
+/// the mock.
+fn in_parallel<F>(f: F)
+    where F: Fn(u32, &Barrier) + Send + Sync + 'static
+{
+    const N: u32 = 4;
+    let barrier = Arc::new(Barrier::new(N as usize));
+    let f = Arc::new(f);
+    let handles = (0..N).map(|i| {
+        let barrier = barrier.clone();
+        let f = f.clone();
+        thread::spawn(move || f(i, &barrier))
+    }).collect::<Vec<_>>();
+    for h in handles {
+        h.join().unwrap();
+    }
+}
+
+#[test]
+fn static_method() {
+    in_parallel(|i, barrier| {
+        let ctx = MockFoo::foo_context();
+        ctx.expect()
+            .times(1)
+            .returning(move |x| x + i);
+        barrier.wait();
+        assert_eq!(10 + i, MockFoo::foo(10));
+    });
+}
+
+#[test]
+fn generic_static_method() {
+    in_parallel(|i, barrier| {
+        let ctx = MockFoo::bar_context();
+        ctx.expect::<u32>()
+            .times(1)
+            .returning(move |x| x * i);
+        barrier.wait();
+        assert_eq!(2 * i, MockFoo::bar(2u32));
+    });
+}
+
+#[test]
+fn struct_method() {
+    in_parallel(|i, barrier| {
+        let ctx = MockBean::bean_context();
+        ctx.expect()
+            .returning(move |x| x - i);
+        barrier.wait();
+        assert_eq!(10 - i, MockBean::bean(10));
+    });
+}
+
+#[test]
+fn module_function() {
+    in_parallel(|i, barrier| {
+        let ctx = mock_m::foo_context();
+        ctx.expect()
+            .returning(move |x| x + i);
+        barrier.wait();
+        assert_eq!(1 + i, mock_m::foo(1));
+    });
+}
+
+#[test]
+fn checkpoint() {
+    let ctx = MockFoo::foo_context();
+    ctx.expect()
+        .times(1)
+        .return_const(42u32);
+    // Another thread sees no expectations, and can't satisfy this one.
+    thread::spawn(|| {
+        let ctx = MockFoo::foo_context();
+        ctx.checkpoint();
+    }).join().unwrap();
+    assert_eq!(42, MockFoo::foo(0));
+}
+
+#[test]
+fn method() {
+    let mut mock = MockFoo::new();
+    mock.expect_baz()
+        .return_const(5u32);
+    assert_eq!(5, mock.baz());
+}
@@ -3,10 +3,15 @@
 #![deny(warnings)]
 
 use mockall::*;
+use std::{error::Error, fmt, io};
 
 #[automock]
 trait Foo {
     fn foo(&self, x: &dyn PartialEq<u32>);
+    fn call(&self, f: &dyn Fn(u32) -> u32) -> u32;
+    fn call_mut(&self, f: &mut dyn FnMut(u32));
+    fn err(&self, e: &dyn Error) -> bool;
+    fn debug(d: &dyn fmt::Debug) -> String;
 }
 
 // It's almost impossible to construct a Predicate object that will work with
@@ -40,3 +45,42 @@ fn withf() {
         .return_const(());
     mock.foo(&42u32);
 }
+
+/// Fn trait objects can be called by the expectation
+#[test]
+fn fn_arg() {
+    let mut mock = MockFoo::new();
+    mock.expect_call()
+        .withf(|f| f(1) == 2)
+        .returning(|f| f(5));
+    assert_eq!(6, mock.call(&|x| x + 1));
+}
+
+#[test]
+fn fn_mut_arg() {
+    let mut mock = MockFoo::new();
+    mock.expect_call_mut()
+        .returning(|f| f(3));
+    let mut y = 0;
+    mock.call_mut(&mut |x| y = x);
+    assert_eq!(3, y);
+}
+
+/// An argument named `e` mustn't collide with Mockall's internal lifetimes
+#[test]
+fn named_e() {
+    let mut mock = MockFoo::new();
+    mock.expect_err()
+        .withf(|e| e.to_string() == "oops")
+        .return_const(true);
+    assert!(mock.err(&io::Error::new(io::ErrorKind::Other, "oops")));
+}
+
+#[test]
+fn static_method() {
+    let ctx = MockFoo::debug_context();
+    ctx.expect()
+        .withf(|d| format!("{:?}", d) == "42")
+        .returning(|d| format!("{:?}", d));
+    assert_eq!("42", MockFoo::debug(&42));
+}
@@ -0,0 +1,59 @@
+// vim: tw=80
+//! Trait methods may return impl Trait
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+pub trait Container {
+    fn iter(&self) -> impl Iterator<Item = u32>;
+    fn iter_from(&self, start: u32) -> impl Iterator<Item = u32> + Send;
+    fn new_iter() -> impl Iterator<Item = u32>;
+}
+
+pub trait Source {
+    fn items(&self) -> impl Iterator<Item = u32>;
+}
+
+mock! {
+    pub Src {}
+    impl Source for Src {
+        fn items(&self) -> impl Iterator<Item = u32>;
+    }
+}
+
+fn total<C: Container>(c: &C) -> u32 {
+    c.iter().sum()
+}
+
+#[test]
+fn mock_macro() {
+    let mut mock = MockSrc::new();
+    mock.expect_items()
+        .returning(|| Box::new(std::iter::once(7)));
+    assert_eq!(vec![7], mock.items().collect::<Vec<_>>());
+}
+
+#[test]
+fn returning() {
+    let mut mock = MockContainer::new();
+    mock.expect_iter()
+        .returning(|| Box::new(vec![1, 2, 3].into_iter()));
+    assert_eq!(6, total(&mock));
+}
+
+#[test]
+fn static_method() {
+    let ctx = MockContainer::new_iter_context();
+    ctx.expect()
+        .returning(|| Box::new(0..2));
+    assert_eq!(vec![0, 1], MockContainer::new_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn with_args() {
+    let mut mock = MockContainer::new();
+    mock.expect_iter_from()
+        .returning(|start| Box::new(start..start + 2));
+    assert_eq!(vec![5, 6], mock.iter_from(5).collect::<Vec<_>>());
+}
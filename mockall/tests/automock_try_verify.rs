@@ -0,0 +1,140 @@
+// vim: tw=80
+//! Verifying a mock object's expectations without panicking
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32);
+    fn bar(&self);
+    fn baz<T: 'static>(&self, t: T);
+}
+
+#[automock]
+trait OnlyStatic {
+    fn sfoo();
+}
+
+mock! {
+    Bean {}
+    impl Foo for Bean {
+        fn foo(&self, x: u32);
+        fn bar(&self);
+        fn baz<T: 'static>(&self, t: T);
+    }
+}
+
+#[test]
+fn ok() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo().times(1).return_const(());
+    mock.expect_bar().times(0..).return_const(());
+    mock.foo(1);
+    assert_eq!(Ok(()), mock.try_verify());
+}
+
+#[test]
+fn unsatisfied() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(predicate::eq(4))
+        .times(2)
+        .return_const(());
+    mock.expect_bar().times(1).return_const(());
+    mock.foo(4);
+    let errors = mock.try_verify().unwrap_err();
+    assert_eq!(2, errors.len());
+    assert_eq!("MockFoo::foo: Expectation(var == 4) called 1 time(s) which is fewer than expected 2",
+               errors[0].to_string());
+    assert_eq!("MockFoo::bar: Expectation(<anything>)",
+               errors[1].description());
+    assert_eq!(0, errors[1].count());
+    assert_eq!(1, errors[1].minimum());
+
+    // try_verify doesn't clear the expectations
+    mock.foo(4);
+    mock.bar();
+    assert_eq!(Ok(()), mock.try_verify());
+}
+
+#[test]
+fn generic_method() {
+    let mut mock = MockFoo::new();
+    mock.expect_baz::<u16>().times(1).return_const(());
+    mock.expect_baz::<i8>().times(1).return_const(());
+    mock.baz(1u16);
+    let errors = mock.try_verify().unwrap_err();
+    assert_eq!(1, errors.len());
+    assert_eq!("MockFoo::baz: Expectation(<anything>)", errors[0].description());
+    mock.baz(1i8);
+}
+
+#[test]
+fn in_scenario() {
+    let mut scenario = Scenario::new();
+    let mut mock = MockFoo::new();
+    mock.expect_bar()
+        .times(1)
+        .in_scenario(&mut scenario)
+        .return_const(());
+    assert_eq!(Ok(()), mock.try_verify());
+    mock.bar();
+}
+
+#[test]
+fn mock_struct() {
+    let mut mock = MockBean::new();
+    mock.expect_bar().times(1).return_const(());
+    assert_eq!(1, mock.try_verify().unwrap_err().len());
+    mock.bar();
+    assert_eq!(Ok(()), mock.try_verify());
+}
+
+#[test]
+fn only_static() {
+    let mock = MockOnlyStatic::new();
+    assert_eq!(Ok(()), mock.try_verify());
+}
+
+mock! {
+    Verifier {
+        fn try_verify(&self) -> bool;
+        fn bar(&self);
+    }
+}
+
+/// A mocked method named try_verify replaces the generated one, but the mock
+/// object still verifies itself when dropped
+#[test]
+#[should_panic(expected =
+    "MockVerifier::bar: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+fn conflicting_name() {
+    let mut mock = MockVerifier::new();
+    mock.expect_try_verify().return_const(true);
+    mock.expect_bar().times(1).return_const(());
+    assert!(mock.try_verify());
+}
+
+/// Trait methods aren't shadowed by the generated method
+mod trait_method {
+    use super::*;
+
+    #[automock]
+    pub trait Checker {
+        fn try_verify(&self) -> bool;
+        fn in_scenario(&self) -> u32;
+        fn take_unexpected(&self) -> i8;
+    }
+
+    #[test]
+    fn conflicting_names() {
+        let mut mock = MockChecker::new();
+        mock.expect_try_verify().return_const(true);
+        mock.expect_in_scenario().return_const(5u32);
+        mock.expect_take_unexpected().return_const(-1i8);
+        assert!(mock.try_verify());
+        assert_eq!(5, mock.in_scenario());
+        assert_eq!(-1, mock.take_unexpected());
+    }
+}
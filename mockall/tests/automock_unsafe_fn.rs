@@ -0,0 +1,80 @@
+// vim: tw=80
+//! Unsafe methods and functions
+#![deny(warnings)]
+#![allow(clippy::missing_safety_doc)]
+
+use mockall::*;
+
+#[automock]
+pub trait Foo {
+    unsafe fn foo(&self, x: *const u32) -> u32;
+    unsafe fn bar() -> u32;
+    unsafe fn baz<T: 'static>(&self, t: T) -> T;
+    fn safe(&self) -> u32;
+}
+
+pub struct Bean {}
+
+#[automock]
+impl Bean {
+    pub unsafe fn deref(&self, x: *const u32) -> u32 {
+        *x
+    }
+}
+
+#[automock]
+pub mod ffi_like {
+    pub unsafe fn get(p: *const u32) -> u32 {
+        *p
+    }
+}
+
+#[test]
+fn method() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .returning(|p| unsafe { *p } + 1);
+    let x = 5u32;
+    assert_eq!(6, unsafe { mock.foo(&x) });
+}
+
+#[test]
+fn static_method() {
+    let ctx = MockFoo::bar_context();
+    ctx.expect()
+        .return_const(42u32);
+    assert_eq!(42, unsafe { MockFoo::bar() });
+}
+
+#[test]
+fn generic_method() {
+    let mut mock = MockFoo::new();
+    mock.expect_baz::<i16>()
+        .with(predicate::eq(-1))
+        .returning(|t| t * 2);
+    assert_eq!(-2, unsafe { mock.baz(-1i16) });
+}
+
+#[test]
+fn safe_method_of_safe_trait() {
+    let mut mock = MockFoo::new();
+    mock.expect_safe()
+        .return_const(3u32);
+    assert_eq!(3, mock.safe());
+}
+
+#[test]
+fn inherent_method() {
+    let mut mock = MockBean::new();
+    mock.expect_deref()
+        .return_const(9u32);
+    assert_eq!(9, unsafe { mock.deref(std::ptr::null()) });
+}
+
+#[test]
+fn module_function() {
+    let ctx = mock_ffi_like::get_context();
+    ctx.expect()
+        .return_const(7u32);
+    assert_eq!(7, unsafe { mock_ffi_like::get(std::ptr::null()) });
+}
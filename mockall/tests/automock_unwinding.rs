@@ -0,0 +1,92 @@
+// vim: tw=80
+//! Mock objects dropped while panicking record their failures instead of
+//! aborting, and don't leak expectations into the next test case, as a
+//! property-testing framework would require.
+#![deny(warnings)]
+
+use mockall::*;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32) -> u32;
+}
+
+#[automock]
+trait Leak {
+    fn leak(x: u32) -> u32;
+}
+
+#[automock]
+trait Poison {
+    fn poison(x: u32) -> u32;
+}
+
+#[test]
+fn records_failures() {
+    let r = catch_unwind(|| {
+        let mut mock = MockFoo::new();
+        mock.expect_foo().times(2).return_const(0u32);
+        mock.foo(1);
+        panic!("the code under test failed");
+    });
+    assert!(r.is_err());
+    let failures = take_unwinding_failures();
+    assert_eq!(failures, vec![String::from(
+        "MockFoo::foo: Expectation(<anything>) called 1 time(s) which is fewer than expected 2"
+    )]);
+    assert!(take_unwinding_failures().is_empty());
+}
+
+#[test]
+fn satisfied() {
+    let r = catch_unwind(|| {
+        let mut mock = MockFoo::new();
+        mock.expect_foo().times(1).return_const(0u32);
+        mock.foo(1);
+        panic!("the code under test failed");
+    });
+    assert!(r.is_err());
+    assert!(take_unwinding_failures().is_empty());
+}
+
+/// A static method's expectations must be cleared even when its Context is
+/// dropped during unwinding, or they would spill into the next case.
+#[test]
+fn static_method_doesnt_leak() {
+    for i in 0..4u32 {
+        let r = catch_unwind(|| {
+            let ctx = MockLeak::leak_context();
+            ctx.expect().times(1).returning(|x| x + 1);
+            if i % 2 == 0 {
+                panic!("case {} failed", i);
+            }
+            assert_eq!(i + 1, MockLeak::leak(i));
+        });
+        assert_eq!(i % 2 == 0, r.is_err());
+        assert_eq!(usize::from(i % 2 == 0), take_unwinding_failures().len());
+    }
+}
+
+/// A panic inside a matcher poisons a static method's expectations.  That
+/// mustn't break later cases.
+#[test]
+fn poisoned_static_method() {
+    let r = catch_unwind(|| {
+        let ctx = MockPoison::poison_context();
+        ctx.expect()
+            .withf(|_| panic!("matcher failed"))
+            .return_const(0u32);
+        MockPoison::poison(0);
+    });
+    assert!(r.is_err());
+    assert!(take_unwinding_failures().is_empty());
+
+    let r = catch_unwind(AssertUnwindSafe(|| {
+        let ctx = MockPoison::poison_context();
+        ctx.expect().return_const(5u32);
+        MockPoison::poison(0)
+    }));
+    assert_eq!(5, r.unwrap());
+    assert!(take_unwinding_failures().is_empty());
+}
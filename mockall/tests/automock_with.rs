@@ -0,0 +1,143 @@
+// vim: tw=80
+//! with() checks each argument with its own predicate, and a mismatch names the
+//! argument that was rejected.  withf() checks all arguments at once.  Either
+//! way, the panic message lists every expectation that was considered.
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32, y: u32) -> u32;
+    fn name(&self, name: &str);
+    fn join(&self, a: String, b: String) -> usize;
+}
+
+#[test]
+fn ok() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(predicate::eq(1), predicate::always())
+        .return_const(1u32);
+    mock.expect_foo()
+        .with(predicate::eq(2), predicate::gt(10))
+        .return_const(2u32);
+    assert_eq!(1, mock.foo(1, 100));
+    assert_eq!(1, mock.foo(1, 0));
+    assert_eq!(2, mock.foo(2, 11));
+}
+
+#[test]
+#[should_panic(expected =
+    "No matching expectation found.  Expectation(var == 1, var == 2) rejected argument `y`")]
+fn one_bad_arg() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(predicate::eq(1), predicate::eq(2))
+        .return_const(0u32);
+    mock.foo(1, 3);
+}
+
+#[test]
+#[should_panic(expected =
+    "rejected arguments `x`, `y`")]
+fn two_bad_args() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(predicate::eq(1), predicate::eq(2))
+        .return_const(0u32);
+    mock.foo(3, 4);
+}
+
+/// Every expectation that was considered should be explained
+#[test]
+#[should_panic(expected =
+    "Expectation(var == 1, true) rejected argument `x`; Expectation(var == 2, var == 2) rejected argument `y`")]
+fn several_expectations() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(predicate::eq(1), predicate::always())
+        .return_const(1u32);
+    mock.expect_foo()
+        .with(predicate::eq(2), predicate::eq(2))
+        .return_const(2u32);
+    mock.foo(2, 3);
+}
+
+#[test]
+#[should_panic(expected =
+    "Expectation(var == \"alice\") rejected argument `name`")]
+fn reference_arg() {
+    let mut mock = MockFoo::new();
+    mock.expect_name()
+        .with(predicate::eq("alice"))
+        .return_const(());
+    mock.name("bob");
+}
+
+/// The actual arguments are shown, too, when they're Debug.
+#[test]
+#[should_panic(expected =
+    "MockFoo::foo(3, 4): No matching expectation found.  Expectation(")]
+fn shows_args() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(predicate::eq(1), predicate::eq(2))
+        .return_const(0u32);
+    mock.foo(3, 4);
+}
+
+/// An expectation whose arguments matched, but which can't be called again,
+/// says so.
+#[test]
+#[should_panic(expected =
+    "Expectation(var == 1, true) was already called 1 times; Expectation(var == 2, true) rejected argument `x`")]
+fn saturated() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(predicate::eq(1), predicate::always())
+        .times(1)
+        .return_const(1u32);
+    mock.expect_foo()
+        .with(predicate::eq(2), predicate::always())
+        .return_const(2u32);
+    mock.foo(1, 0);
+    mock.foo(1, 0);
+}
+
+/// withf can't tell which argument was wrong, so it doesn't try.
+#[test]
+#[should_panic(expected =
+    "MockFoo::foo(3, 2): No matching expectation found.  Expectation(<function>) rejected the call")]
+fn withf() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .withf(|x, y| x < y)
+        .return_const(0u32);
+    mock.foo(3, 2);
+}
+
+/// withf's result selects which expectation handles each call
+#[test]
+fn withf_selects() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .withf(|x, y| x > y)
+        .returning(|x, y| x - y);
+    mock.expect_foo()
+        .withf(|x, y| x <= y)
+        .returning(|x, y| y - x);
+    assert_eq!(2, mock.foo(5, 3));
+    assert_eq!(4, mock.foo(3, 7));
+    assert_eq!(0, mock.foo(4, 4));
+}
+
+/// Non-Copy arguments are passed to withf by reference
+#[test]
+fn withf_non_copy() {
+    let mut mock = MockFoo::new();
+    mock.expect_join()
+        .withf(|a: &String, b: &String| a.len() == b.len())
+        .returning(|a, b| a.len() + b.len());
+    assert_eq!(6, mock.join("abc".to_owned(), "def".to_owned()));
+}
@@ -20,3 +20,42 @@ fn returning() {
         .returning(MockA::new);
     let _mock1 = mock0.clone();
 }
+
+mock! {
+    pub B {
+        fn foo(&self) -> u32;
+    }
+    impl Clone for B;
+}
+
+/// The body of a Clone impl may be omitted
+#[allow(clippy::redundant_clone)]
+#[test]
+fn shorthand() {
+    let mut mock0 = MockB::new();
+    mock0.expect_clone()
+        .times(1)
+        .returning(|| {
+            let mut mock1 = MockB::new();
+            mock1.expect_foo().return_const(1u32);
+            mock1
+        });
+    mock0.expect_foo().return_const(0u32);
+    let mock1 = mock0.clone();
+    assert_eq!(0, mock0.foo());
+    assert_eq!(1, mock1.foo());
+}
+
+mock! {
+    pub C<T: 'static> {}
+    impl<T: 'static> Clone for C<T>;
+}
+
+#[allow(clippy::redundant_clone)]
+#[test]
+fn shorthand_generic() {
+    let mut mock0 = MockC::<u32>::new();
+    mock0.expect_clone()
+        .returning(MockC::new);
+    let _mock1 = mock0.clone();
+}
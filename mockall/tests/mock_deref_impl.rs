@@ -0,0 +1,93 @@
+// vim: tw=80
+//! Mocking Deref and DerefMut, whose methods may be omitted from mock!
+#![deny(warnings)]
+
+use mockall::*;
+use std::ops::{Deref, DerefMut};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Inner(u32);
+
+impl Inner {
+    fn get(&self) -> u32 {
+        self.0
+    }
+
+    fn set(&mut self, x: u32) {
+        self.0 = x;
+    }
+}
+
+mock! {
+    pub Ptr {}
+    impl Deref for Ptr {
+        type Target = Inner;
+    }
+    impl DerefMut for Ptr {}
+}
+
+mock! {
+    pub Shorthand {}
+    impl DerefMut for Shorthand;
+    impl Deref for Shorthand {
+        type Target = Inner;
+    }
+}
+
+mock! {
+    pub Explicit {}
+    impl Deref for Explicit {
+        type Target = Inner;
+        fn deref(&self) -> &Inner;
+    }
+}
+
+mock! {
+    pub Generic<T: 'static> {}
+    impl<T: 'static> Deref for Generic<T> {
+        type Target = Vec<T>;
+    }
+}
+
+#[test]
+fn deref() {
+    let mut mock = MockPtr::new();
+    mock.expect_deref()
+        .return_const(Inner(5));
+    assert_eq!(Inner(5), *mock);
+    assert_eq!(5, mock.get());
+}
+
+#[test]
+fn deref_mut() {
+    let mut mock = MockPtr::new();
+    mock.expect_deref_mut()
+        .return_var(Inner::default());
+    mock.set(7);
+    assert_eq!(7, mock.deref_mut().get());
+}
+
+#[test]
+fn explicit() {
+    let mut mock = MockExplicit::new();
+    mock.expect_deref()
+        .return_const(Inner(3));
+    assert_eq!(3, mock.get());
+}
+
+#[test]
+fn generic() {
+    let mut mock = MockGeneric::<u8>::new();
+    mock.expect_deref()
+        .return_const(vec![1u8, 2, 3]);
+    assert_eq!(3, mock.len());
+}
+
+#[test]
+fn shorthand() {
+    let mut mock = MockShorthand::new();
+    mock.expect_deref_mut()
+        .return_var(Inner(1));
+    *mock.deref_mut() = Inner(2);
+    assert_eq!(2, mock.deref_mut().get());
+}
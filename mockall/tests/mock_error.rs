@@ -0,0 +1,79 @@
+// vim: tw=80
+//! A mock struct can implement std::error::Error, including a chain of sources
+#![deny(warnings)]
+
+use mockall::*;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+mock! {
+    #[derive(Debug)]
+    pub MyError {}
+    impl Error for MyError {
+        fn source(&self) -> Option<&'static (dyn Error + 'static)>;
+    }
+    impl Display for MyError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result;
+    }
+}
+
+fn my_error(msg: &'static str) -> MockMyError {
+    let mut e = MockMyError::new();
+    e.expect_fmt().returning(move |f| f.write_str(msg));
+    e
+}
+
+fn messages(e: &(dyn Error + 'static)) -> Vec<String> {
+    let mut v = vec![e.to_string()];
+    let mut source = e.source();
+    while let Some(s) = source {
+        v.push(s.to_string());
+        source = s.source();
+    }
+    v
+}
+
+#[test]
+fn chain() {
+    let errors = vec![my_error("a"), my_error("b"), my_error("c")];
+    let e = error_chain(errors);
+    assert_eq!(messages(&e), ["a", "b", "c"]);
+    assert_eq!("MockMyError", format!("{:?}", e));
+    assert_eq!("a", e.error().to_string());
+}
+
+#[test]
+#[should_panic(expected = "error_chain requires an error")]
+fn chain_empty() {
+    error_chain(Vec::<MockMyError>::new());
+}
+
+/// Dropping the chain verifies the errors that it contains, even the root cause
+#[test]
+#[should_panic(expected =
+    "MockMyError::fmt: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+fn chain_verifies_sources() {
+    let mut root = MockMyError::new();
+    root.expect_fmt().times(1).returning(|f| f.write_str("root"));
+    let errors = vec![my_error("a"), root];
+    let _e = error_chain(errors);
+}
+
+#[test]
+fn debug() {
+    let e = MockMyError::new();
+    assert_eq!("MockMyError", format!("{:?}", e));
+}
+
+#[test]
+fn display() {
+    let e = my_error("oops");
+    assert_eq!("oops", e.to_string());
+}
+
+#[test]
+fn no_source() {
+    let mut e = my_error("oops");
+    e.expect_source().return_const_st(None);
+    assert!(e.source().is_none());
+}
@@ -0,0 +1,75 @@
+// vim: tw=80
+//! Code that uses an extension trait can be tested by mocking the base trait.
+//! Expectations may be set on the extension trait's own methods only if it is
+//! implemented for each type, rather than through a blanket impl.
+#![deny(warnings)]
+
+use mockall::*;
+
+mod blanket {
+    use super::*;
+
+    #[automock]
+    pub trait Foo {
+        fn foo(&self) -> u32;
+    }
+
+    pub trait FooExt: Foo {
+        fn double_foo(&self) -> u32 {
+            2 * self.foo()
+        }
+    }
+
+    impl<T: Foo> FooExt for T {}
+
+    fn uses_ext<T: FooExt>(t: &T) -> u32 {
+        t.double_foo() + 1
+    }
+
+    #[test]
+    fn returning() {
+        let mut mock = MockFoo::new();
+        mock.expect_foo()
+            .times(1)
+            .return_const(5u32);
+        assert_eq!(11, uses_ext(&mock));
+    }
+}
+
+mod explicit {
+    use super::*;
+
+    pub trait Foo {
+        fn foo(&self) -> u32;
+    }
+
+    pub trait FooExt: Foo {
+        fn double_foo(&self) -> u32 {
+            2 * self.foo()
+        }
+    }
+
+    mock! {
+        pub Foo {}
+        impl Foo for Foo {
+            fn foo(&self) -> u32;
+        }
+        impl FooExt for Foo {
+            fn double_foo(&self) -> u32;
+        }
+    }
+
+    fn uses_ext<T: FooExt>(t: &T) -> u32 {
+        t.double_foo() + 1
+    }
+
+    #[test]
+    fn returning() {
+        let mut mock = MockFoo::new();
+        mock.expect_foo().never();
+        mock.expect_double_foo()
+            .times(1)
+            .return_const(10u32);
+        assert_eq!(11, uses_ext(&mock));
+    }
+}
@@ -0,0 +1,103 @@
+// vim: tw=80
+//! Mocking Display and Debug with the `impl Display for Foo;` shorthand
+#![deny(warnings)]
+
+use mockall::*;
+use std::fmt;
+
+mock! {
+    pub Name {}
+    impl fmt::Display for Name;
+}
+
+mock! {
+    pub Both {}
+    impl fmt::Debug for Both;
+    impl fmt::Display for Both;
+}
+
+// The formatter may still be mocked explicitly
+mock! {
+    pub Explicit {}
+    impl fmt::Display for Explicit {
+        fn fmt<'a>(&self, f: &mut fmt::Formatter<'a>) -> fmt::Result;
+    }
+}
+
+fn greet<T: fmt::Display>(name: &T) -> String {
+    format!("Hello, {}!", name)
+}
+
+#[test]
+fn display() {
+    let mut mock = MockName::new();
+    mock.expect_fmt()
+        .return_const(String::from("World"));
+    assert_eq!("Hello, World!", greet(&mock));
+}
+
+#[test]
+fn display_padding() {
+    let mut mock = MockName::new();
+    mock.expect_fmt()
+        .returning(|| String::from("x"));
+    // The String is written as-is, ignoring format options
+    assert_eq!("x", format!("{:>4}", mock));
+}
+
+#[test]
+fn both() {
+    let mut mock = MockBoth::new();
+    mock.expect_Debug_fmt()
+        .return_const(String::from("Both { .. }"));
+    mock.expect_Display_fmt()
+        .return_const(String::from("both"));
+    assert_eq!("Both { .. }", format!("{:?}", mock));
+    assert_eq!("both", format!("{}", mock));
+}
+
+#[test]
+fn explicit() {
+    let mut mock = MockExplicit::new();
+    mock.expect_fmt()
+        .returning(|f| write!(f, "explicit"));
+    assert_eq!("explicit", format!("{}", mock));
+}
+
+#[test]
+fn nice() {
+    let mock = MockName::new_nice();
+    assert_eq!("", format!("{}", mock));
+}
+
+#[test]
+#[should_panic(expected = "MockName::fmt(): No matching expectation found")]
+fn no_expectation() {
+    let mock = MockName::new();
+    let _ = format!("{}", mock);
+}
+
+#[test]
+fn times() {
+    let mut mock = MockName::new();
+    mock.expect_fmt()
+        .times(2)
+        .return_const(String::from("twice"));
+    assert_eq!("twice twice", format!("{} {}", mock, mock));
+}
+
+#[test]
+fn spy() {
+    struct RealName;
+    impl fmt::Display for RealName {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "real")
+        }
+    }
+
+    let mut mock = MockName::spy(RealName);
+    assert_eq!("Hello, real!", greet(&mock));
+    mock.expect_fmt()
+        .return_const(String::from("fake"));
+    assert_eq!("Hello, fake!", greet(&mock));
+}
@@ -0,0 +1,93 @@
+// vim: tw=80
+//! A generic struct implementing a trait whose associated type is one of the
+//! struct's generic parameters.
+#![deny(warnings)]
+
+use mockall::*;
+
+mock! {
+    pub Wrapper<T: 'static> {}
+    impl<T: 'static> Iterator for Wrapper<T> {
+        type Item = T;
+        fn next(&mut self) -> Option<T>;
+    }
+}
+
+mock! {
+    pub SelfItem<T: Clone + 'static> {}
+    impl<T: Clone + 'static> Iterator for SelfItem<T> {
+        type Item = Vec<T>;
+        fn next(&mut self) -> Option<<Self as Iterator>::Item>;
+    }
+}
+
+// The impl block's generic parameter has a different name than the struct's
+mock! {
+    pub Renamed<T: 'static> {}
+    impl<U: 'static> Iterator for Renamed<U> {
+        type Item = U;
+        fn next(&mut self) -> Option<U>;
+    }
+}
+
+#[automock(type Output=T;)]
+pub trait Getter<T: 'static> {
+    type Output;
+    fn get(&self) -> Self::Output;
+}
+
+#[allow(deprecated)]
+mod old_syntax {
+    use super::*;
+
+    mock! {
+        pub Wrapper<T: 'static> {}
+        trait Iterator {
+            type Item = T;
+            fn next(&mut self) -> Option<T>;
+        }
+    }
+
+    #[test]
+    fn returning() {
+        let mut mock = MockWrapper::<u32>::new();
+        mock.expect_next().return_const(Some(3));
+        assert_eq!(Some(3), mock.next());
+    }
+}
+
+#[test]
+fn automock_generic_trait() {
+    let mut mock = MockGetter::<i16>::new();
+    mock.expect_get().return_const(-3i16);
+    assert_eq!(-3, mock.get());
+}
+
+#[test]
+fn qualified_self_item() {
+    let mut mock = MockSelfItem::<u8>::new();
+    mock.expect_next().returning(|| Some(vec![1, 2]));
+    assert_eq!(Some(vec![1, 2]), mock.next());
+}
+
+#[test]
+fn renamed_generic() {
+    let mut mock = MockRenamed::<u32>::new();
+    mock.expect_next().return_const(Some(7));
+    assert_eq!(Some(7), mock.next());
+}
+
+#[test]
+fn returning() {
+    let mut mock = MockWrapper::<u32>::new();
+    let mut seq = Sequence::new();
+    mock.expect_next()
+        .times(1)
+        .in_sequence(&mut seq)
+        .return_const(Some(5));
+    mock.expect_next()
+        .times(1)
+        .in_sequence(&mut seq)
+        .return_const(None);
+    assert_eq!(vec![5], mock.collect::<Vec<_>>());
+}
@@ -0,0 +1,69 @@
+// vim: tw=80
+//! Generic structs with where clauses that have multiple bounds, including
+//! lifetime bounds
+#![deny(warnings)]
+
+use mockall::*;
+
+pub trait Store<T> where T: Clone + Send {
+    fn put(&self, t: T);
+    fn count() -> usize;
+}
+
+mock! {
+    pub Foo<T> where T: Clone + Send + 'static {
+        fn foo(&self, t: T) -> T;
+    }
+    impl<T> Store<T> for Foo<T> where T: Clone + Send + 'static {
+        fn put(&self, t: T);
+        fn count() -> usize;
+    }
+}
+
+mock! {
+    pub Bar<'a, T> where T: Clone + Send + 'a {
+        fn bar(&self, t: T) -> u32;
+    }
+}
+
+mock! {
+    pub Baz<'a, T: Send + 'a> where T: 'static {
+        fn baz(&self, t: T) -> u32;
+    }
+}
+
+#[test]
+fn multiple_bounds() {
+    let mut mock = MockFoo::<u32>::new();
+    mock.expect_foo()
+        .returning(|t| t + 1);
+    mock.expect_put()
+        .with(predicate::eq(4))
+        .return_const(());
+    assert_eq!(5, mock.foo(4));
+    mock.put(4);
+}
+
+#[test]
+fn static_method() {
+    let ctx = MockFoo::<u32>::count_context();
+    ctx.expect()
+        .return_const(2usize);
+    assert_eq!(2, MockFoo::<u32>::count());
+}
+
+#[test]
+fn lifetime_bound_in_where_clause() {
+    let mut mock = MockBar::<'static, u32>::new();
+    mock.expect_bar()
+        .returning(|t| t * 2);
+    assert_eq!(6, mock.bar(3));
+}
+
+#[test]
+fn lifetime_bound_in_params() {
+    let mut mock = MockBaz::<'static, u32>::new();
+    mock.expect_baz()
+        .returning(|t| t * 3);
+    assert_eq!(9, mock.baz(3));
+}
@@ -30,3 +30,67 @@ fn returning() {
     mock.foo();
     mock.bar();
 }
+
+trait C<T> {
+    fn baz(&self, t: T) -> T;
+    fn make() -> T where Self: Sized;
+}
+
+trait D: C<u32> + Send {
+    fn bean(&self) -> u32 {
+        self.baz(1) + 1
+    }
+}
+
+mock!{
+    D {}
+    impl C<u32> for D {
+        fn baz(&self, t: u32) -> u32;
+        fn make() -> u32 where Self: Sized;
+    }
+    impl D for D {
+        fn bean(&self) -> u32;
+    }
+}
+
+fn use_subtrait<T: B>(t: &T) {
+    t.foo();
+    t.bar();
+}
+
+#[test]
+fn generic_caller() {
+    let mut mock = MockB::new();
+    mock.expect_foo().times(1).returning(|| ());
+    mock.expect_bar().times(1).returning(|| ());
+    use_subtrait(&mock);
+}
+
+#[test]
+#[should_panic(expected =
+    "MockB::foo: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+fn checkpoint_covers_supertrait() {
+    let mut mock = MockB::new();
+    mock.expect_foo().times(1).returning(|| ());
+    mock.expect_bar().returning(|| ());
+    mock.checkpoint();
+}
+
+#[test]
+fn generic_supertrait() {
+    let mut mock = MockD::new();
+    mock.expect_baz()
+        .with(predicate::eq(4))
+        .returning(|t| t * 2);
+    mock.expect_bean().return_const(5u32);
+    let d: &dyn D = &mock;
+    assert_eq!(8, d.baz(4));
+    assert_eq!(5, d.bean());
+}
+
+#[test]
+fn static_supertrait_method() {
+    let ctx = MockD::make_context();
+    ctx.expect().return_const(42u32);
+    assert_eq!(42, <MockD as C<u32>>::make());
+}
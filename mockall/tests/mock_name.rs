@@ -0,0 +1,41 @@
+// vim: tw=80
+//! mock! can choose the mock struct's name
+#![deny(warnings)]
+
+use mockall::*;
+
+trait Foo {
+    fn foo(&self) -> u32;
+}
+
+mock! {
+    pub Foo as FakeFoo {}
+    impl Foo for Foo {
+        fn foo(&self) -> u32;
+    }
+}
+
+mock! {
+    pub Bar<T: 'static> as FakeBar where T: Clone {
+        fn bar(&self, t: T) -> Self;
+    }
+    impl<T: Clone + 'static> Clone for Bar<T>;
+}
+
+#[test]
+fn generic() {
+    let mut mock = FakeBar::<u8>::new();
+    mock.expect_bar()
+        .returning(|_| FakeBar::new());
+    mock.expect_clone()
+        .returning(FakeBar::new);
+    let _ = mock.bar(1);
+    let _ = mock.clone();
+}
+
+#[test]
+fn simple() {
+    let mut mock = FakeFoo::new();
+    mock.expect_foo().return_const(42u32);
+    assert_eq!(42, mock.foo());
+}
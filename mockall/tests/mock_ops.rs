@@ -0,0 +1,140 @@
+// vim: tw=80
+//! Mocking the operator traits from std::ops
+#![deny(warnings)]
+
+use mockall::*;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Not};
+
+mock! {
+    pub Num {}
+    impl Add for Num {
+        type Output = Self;
+        fn add(self, rhs: Self) -> Self;
+    }
+    impl Mul<f64> for Num {
+        type Output = f64;
+        fn mul(self, rhs: f64) -> f64;
+    }
+    impl Neg for Num {
+        type Output = i64;
+        fn neg(self) -> i64;
+    }
+    impl Not for Num {
+        type Output = bool;
+        fn not(self) -> bool;
+    }
+    impl AddAssign<u32> for Num {
+        fn add_assign(&mut self, rhs: u32);
+    }
+    impl Index<usize> for Num {
+        type Output = u32;
+        fn index(&self, i: usize) -> &u32;
+    }
+    impl IndexMut<usize> for Num {
+        fn index_mut(&mut self, i: usize) -> &mut u32;
+    }
+}
+
+mock! {
+    pub Vector<T: 'static> {}
+    impl<T: 'static> Add for Vector<T> {
+        type Output = T;
+        fn add(self, rhs: Self) -> T;
+    }
+}
+
+// When the same operator trait is implemented for multiple right-hand sides,
+// the expectation methods are qualified with the trait's generic arguments.
+mock! {
+    pub Money {}
+    impl Add<u32> for Money {
+        type Output = u32;
+        fn add(self, rhs: u32) -> u32;
+    }
+    impl Add<f64> for Money {
+        type Output = f64;
+        fn add(self, rhs: f64) -> f64;
+    }
+}
+
+#[test]
+fn add() {
+    let mut lhs = MockNum::new();
+    lhs.expect_add()
+        .returning(|_rhs| {
+            let mut sum = MockNum::new();
+            sum.expect_neg().return_const(-3i64);
+            sum
+        });
+    assert_eq!(-3, -(lhs + MockNum::new()));
+}
+
+#[test]
+fn add_assign() {
+    let mut mock = MockNum::new();
+    mock.expect_add_assign()
+        .with(predicate::eq(5))
+        .times(1)
+        .return_const(());
+    mock += 5;
+}
+
+#[test]
+fn generic() {
+    let mut lhs = MockVector::<u32>::new();
+    lhs.expect_add()
+        .return_const(42u32);
+    assert_eq!(42, lhs + MockVector::new());
+}
+
+#[test]
+fn index() {
+    let mut mock = MockNum::new();
+    mock.expect_index()
+        .with(predicate::eq(2))
+        .return_const(7u32);
+    assert_eq!(7, mock[2]);
+}
+
+#[test]
+fn index_mut() {
+    let mut mock = MockNum::new();
+    mock.expect_index_mut()
+        .return_var(0u32);
+    mock[1] = 9;
+    assert_eq!(9, *mock.index_mut(1));
+}
+
+#[test]
+fn mul() {
+    let mut mock = MockNum::new();
+    mock.expect_mul()
+        .returning(|rhs| rhs * 2.0);
+    assert_eq!(3.0, mock * 1.5);
+}
+
+#[test]
+fn neg() {
+    let mut mock = MockNum::new();
+    mock.expect_neg()
+        .return_const(-5i64);
+    assert_eq!(-5, -mock);
+}
+
+#[test]
+fn not() {
+    let mut mock = MockNum::new();
+    mock.expect_not()
+        .return_const(true);
+    assert!(!mock);
+}
+
+#[test]
+fn overloaded() {
+    let mut mock = MockMoney::new();
+    mock.expect_Add_u32_add()
+        .returning(|rhs| rhs + 1);
+    mock.expect_Add_f64_add()
+        .returning(|rhs| rhs + 0.5);
+    assert_eq!(3, mock + 2u32);
+}
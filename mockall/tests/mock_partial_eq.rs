@@ -0,0 +1,46 @@
+// vim: tw=80
+//! PartialEq may be mocked with the shorthand syntax, even for several
+//! different right-hand types at once.
+#![deny(warnings)]
+
+use mockall::*;
+
+mock! {
+    Foo {}
+    impl PartialEq<u32> for Foo;
+    impl PartialEq<&'static str> for Foo;
+    impl PartialEq<Foo> for Foo;
+}
+
+mock! {
+    Bar {}
+    impl PartialEq for Bar;
+}
+
+#[test]
+fn default_rhs() {
+    let mut a = MockBar::new();
+    let b = MockBar::new();
+    a.expect_eq()
+        .return_const(true);
+    assert!(a == b);
+}
+
+#[test]
+fn several_rhs() {
+    let mut a = MockFoo::new();
+    let b = MockFoo::new();
+    a.expect_PartialEq_u32_eq()
+        .withf(|x| *x == 5)
+        .return_const(true);
+    a.expect_PartialEq_u32_eq()
+        .return_const(false);
+    a.expect_PartialEq_static_str_eq()
+        .return_const(true);
+    a.expect_PartialEq_Self_eq()
+        .return_const(false);
+    assert!(a == 5u32);
+    assert!(a != 6u32);
+    assert!(a == "hello");
+    assert!(a != b);
+}
@@ -0,0 +1,82 @@
+// vim: tw=80
+//! Methods with `self: Pin<&mut Self>` receivers, like `Future::poll`
+#![deny(warnings)]
+
+use futures::{executor::block_on, Stream, StreamExt};
+use mockall::*;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll}
+};
+
+mock! {
+    pub Fut {}
+    impl Future for Fut {
+        type Output = u32;
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u32>;
+    }
+}
+
+mock! {
+    pub Numbers {}
+    impl Stream for Numbers {
+        type Item = u32;
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>)
+            -> Poll<Option<u32>>;
+    }
+}
+
+#[automock]
+pub trait Resettable {
+    fn reset(self: Pin<&mut Self>, x: u32) -> u32;
+    fn peek(self: Pin<&Self>) -> u32;
+}
+
+#[test]
+fn future() {
+    let mut mock = MockFut::new();
+    let mut seq = Sequence::new();
+    mock.expect_poll()
+        .times(1)
+        .in_sequence(&mut seq)
+        .returning(|cx| {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        });
+    mock.expect_poll()
+        .times(1)
+        .in_sequence(&mut seq)
+        .return_const(Poll::Ready(42));
+    assert_eq!(42, block_on(mock));
+}
+
+#[test]
+fn stream() {
+    let mut mock = MockNumbers::new();
+    let mut seq = Sequence::new();
+    for x in 0..3 {
+        mock.expect_poll_next()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(move |_| Poll::Ready(Some(x)));
+    }
+    mock.expect_poll_next()
+        .times(1)
+        .in_sequence(&mut seq)
+        .returning(|_| Poll::Ready(None));
+    let v: Vec<u32> = block_on(mock.collect());
+    assert_eq!(vec![0, 1, 2], v);
+}
+
+#[test]
+fn pin_ref() {
+    let mut mock = MockResettable::new();
+    mock.expect_reset()
+        .with(predicate::eq(5))
+        .returning(|x| x + 1);
+    mock.expect_peek()
+        .return_const(3u32);
+    assert_eq!(6, Pin::new(&mut mock).reset(5));
+    assert_eq!(3, Pin::new(&mock).peek());
+}
@@ -0,0 +1,95 @@
+// vim: tw=80
+//! Methods returning `Option<T>`, like `Iterator::next`, can use return_items
+#![deny(warnings)]
+
+use mockall::*;
+
+mock! {
+    pub Iter {}
+    impl Iterator for Iter {
+        type Item = u32;
+        fn next(&mut self) -> Option<u32>;
+    }
+}
+
+mock! {
+    pub SelfItem {}
+    impl Iterator for SelfItem {
+        type Item = String;
+        fn next(&mut self) -> Option<<Self as Iterator>::Item>;
+    }
+}
+
+#[automock]
+pub trait Source {
+    fn get(&self, key: u32) -> Option<String>;
+    fn get_static() -> Option<u32>;
+}
+
+#[test]
+fn iterator() {
+    let mut mock = MockIter::new();
+    mock.expect_next()
+        .return_items(vec![1, 2, 3]);
+    assert_eq!(vec![1, 2, 3], mock.by_ref().collect::<Vec<_>>());
+    assert_eq!(None, mock.next());
+}
+
+#[test]
+fn iterator_self_item() {
+    let mut mock = MockSelfItem::new();
+    mock.expect_next()
+        .return_items(vec![String::from("a"), String::from("b")]);
+    assert_eq!("ab", mock.collect::<String>());
+}
+
+#[test]
+fn empty() {
+    let mut mock = MockIter::new();
+    mock.expect_next()
+        .return_items(Vec::new());
+    assert_eq!(0, mock.count());
+}
+
+#[test]
+fn range() {
+    let mut mock = MockIter::new();
+    mock.expect_next()
+        .return_items(5..8);
+    assert_eq!(18u32, mock.sum());
+}
+
+#[test]
+fn with_matcher() {
+    let mut mock = MockSource::new();
+    mock.expect_get()
+        .with(predicate::eq(1))
+        .return_items(vec![String::from("x")]);
+    mock.expect_get()
+        .with(predicate::eq(2))
+        .return_const(None);
+    assert_eq!(Some(String::from("x")), mock.get(1));
+    assert_eq!(None, mock.get(2));
+    assert_eq!(None, mock.get(1));
+}
+
+#[test]
+fn static_method() {
+    let ctx = MockSource::get_static_context();
+    ctx.expect()
+        .return_items(vec![4, 2]);
+    assert_eq!(Some(4), MockSource::get_static());
+    assert_eq!(Some(2), MockSource::get_static());
+    assert_eq!(None, MockSource::get_static());
+}
+
+#[test]
+fn pending() {
+    let mut mock = MockSource::new();
+    mock.expect_get_pending()
+        .times(3)
+        .return_items(vec![String::from("y")]);
+    assert_eq!(Some(String::from("y")), mock.get(0));
+    assert_eq!(None, mock.get(0));
+    assert_eq!(None, mock.get(0));
+}
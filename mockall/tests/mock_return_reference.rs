@@ -16,7 +16,7 @@ mod never {
 
     #[test]
     #[should_panic(expected =
-        "MockFoo::bar: Expectation(<anything>) should not have been called")]
+        "MockFoo::bar: Expectation(<anything>) was called, but never() was specified")]
     fn fail() {
         let mut mock = MockFoo::new();
         mock.expect_bar()
@@ -42,8 +42,6 @@ fn return_const() {
 }
 
 #[test]
-#[cfg_attr(not(feature = "nightly"),
-    should_panic(expected = "MockFoo::foo: Expectation(<anything>) Returning default values requires"))]
 #[cfg_attr(not(feature = "nightly"), allow(unused_must_use))]
 fn return_default() {
     let mut mock = MockFoo::new();
@@ -67,10 +65,7 @@ mod sequence {
     }
 
     #[test]
-    #[cfg_attr(feature = "nightly",
-        should_panic(expected = "MockFoo::foo(4): Method sequence violation"))]
-    #[cfg_attr(not(feature = "nightly"),
-        should_panic(expected = "MockFoo::foo(?): Method sequence violation"))]
+    #[should_panic(expected = "MockFoo::foo(4): Method sequence violation")]
     fn fail() {
         let mut seq = Sequence::new();
         let mut mock = MockFoo::new();
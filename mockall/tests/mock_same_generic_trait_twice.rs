@@ -0,0 +1,52 @@
+// vim: tw=80
+//! A mock struct may implement several instantiations of one generic trait
+#![deny(warnings)]
+
+use mockall::*;
+
+pub trait Conv<T> {
+    fn conv(&self, t: T) -> u32;
+}
+
+mock! {
+    pub Foo {}
+    impl From<u32> for Foo {
+        fn from(x: u32) -> Self;
+    }
+    impl From<String> for Foo {
+        fn from(x: String) -> Self;
+    }
+    impl Conv<u32> for Foo {
+        fn conv(&self, t: u32) -> u32;
+    }
+    impl Conv<Vec<u8>> for Foo {
+        fn conv(&self, t: Vec<u8>) -> u32;
+    }
+}
+
+#[test]
+fn methods() {
+    let mut mock = MockFoo::new();
+    mock.expect_Conv_u32_conv()
+        .with(predicate::eq(5))
+        .return_const(1u32);
+    mock.expect_Conv_Vec_u8_conv()
+        .with(predicate::eq(vec![6u8]))
+        .return_const(2u32);
+    assert_eq!(1, mock.conv(5u32));
+    assert_eq!(2, mock.conv(vec![6u8]));
+}
+
+#[test]
+fn static_methods() {
+    let ctx_u32 = MockFoo::From_u32_from_context();
+    ctx_u32.expect()
+        .with(predicate::eq(42))
+        .returning(|_| MockFoo::default());
+    let ctx_string = MockFoo::From_String_from_context();
+    ctx_string.expect()
+        .with(predicate::eq(String::from("42")))
+        .returning(|_| MockFoo::default());
+    let _ = MockFoo::from(42u32);
+    let _ = MockFoo::from(String::from("42"));
+}
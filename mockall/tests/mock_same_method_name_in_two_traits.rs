@@ -0,0 +1,76 @@
+// vim: tw=80
+//! A mock struct that implements two traits with identically named methods
+#![deny(warnings)]
+
+use mockall::*;
+
+pub trait A {
+    fn len(&self) -> usize;
+    fn make() -> u32;
+    fn only_a(&self) -> u32;
+}
+
+pub trait B {
+    fn len(&self) -> usize;
+    fn make() -> u32;
+}
+
+mock! {
+    pub Foo {
+        fn len(&self) -> usize;
+    }
+    impl A for Foo {
+        fn len(&self) -> usize;
+        fn make() -> u32;
+        fn only_a(&self) -> u32;
+    }
+    impl B for Foo {
+        fn len(&self) -> usize;
+        fn make() -> u32;
+    }
+}
+
+#[test]
+fn independent_expectations() {
+    let mut mock = MockFoo::new();
+    mock.expect_len()
+        .return_const(1usize);
+    mock.expect_A_len()
+        .return_const(2usize);
+    mock.expect_B_len()
+        .return_const(3usize);
+    assert_eq!(1, mock.len());
+    assert_eq!(2, A::len(&mock));
+    assert_eq!(3, B::len(&mock));
+}
+
+#[test]
+fn static_methods() {
+    let actx = MockFoo::A_make_context();
+    actx.expect()
+        .return_const(4u32);
+    let bctx = MockFoo::B_make_context();
+    bctx.expect()
+        .return_const(5u32);
+    assert_eq!(4, <MockFoo as A>::make());
+    assert_eq!(5, <MockFoo as B>::make());
+}
+
+#[test]
+fn unique_names_are_unqualified() {
+    let mut mock = MockFoo::new();
+    mock.expect_only_a()
+        .return_const(6u32);
+    assert_eq!(6, mock.only_a());
+}
+
+#[test]
+#[should_panic(expected =
+    "MockFoo::len: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+fn checkpoint() {
+    let mut mock = MockFoo::new();
+    mock.expect_B_len()
+        .times(1)
+        .return_const(3usize);
+    mock.checkpoint();
+}
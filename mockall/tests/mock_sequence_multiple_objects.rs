@@ -0,0 +1,95 @@
+// vim: tw=80
+//! A Sequence may order calls to different mock objects, even of different
+//! types, and even including static methods.
+#![deny(warnings)]
+
+use mockall::*;
+
+#[automock]
+pub trait Db {
+    fn begin(&self) -> u32;
+}
+
+#[automock]
+pub trait Cache {
+    fn invalidate(&mut self, key: u32);
+}
+
+mock! {
+    Clock {
+        fn now() -> u64;
+    }
+}
+
+#[test]
+fn ok() {
+    let mut seq = Sequence::new();
+    let mut db = MockDb::new();
+    let mut cache = MockCache::new();
+    db.expect_begin()
+        .times(1)
+        .in_sequence(&mut seq)
+        .return_const(42u32);
+    cache.expect_invalidate()
+        .with(predicate::eq(42))
+        .times(1)
+        .in_sequence(&mut seq)
+        .return_const(());
+
+    let txn = db.begin();
+    cache.invalidate(txn);
+}
+
+#[test]
+#[should_panic(expected = "Method sequence violation")]
+fn fail() {
+    let mut seq = Sequence::new();
+    let mut db = MockDb::new();
+    let mut cache = MockCache::new();
+    db.expect_begin()
+        .times(1)
+        .in_sequence(&mut seq)
+        .return_const(42u32);
+    cache.expect_invalidate()
+        .times(1)
+        .in_sequence(&mut seq)
+        .return_const(());
+
+    cache.invalidate(42);
+}
+
+#[test]
+fn static_method() {
+    let mut seq = Sequence::new();
+    let mut db = MockDb::new();
+    let ctx = MockClock::now_context();
+    ctx.expect()
+        .times(1)
+        .in_sequence(&mut seq)
+        .return_const(1000u64);
+    db.expect_begin()
+        .times(1)
+        .in_sequence(&mut seq)
+        .return_const(1u32);
+
+    MockClock::now();
+    db.begin();
+}
+
+#[test]
+#[should_panic(expected = "MockClock::now(): Method sequence violation")]
+fn static_method_fail() {
+    let mut seq = Sequence::new();
+    let mut db = MockDb::new();
+    db.expect_begin()
+        .times(1)
+        .in_sequence(&mut seq)
+        .return_const(1u32);
+    let ctx = MockClock::now_context();
+    ctx.expect()
+        .times(1)
+        .in_sequence(&mut seq)
+        .return_const(1000u64);
+
+    MockClock::now();
+}
@@ -0,0 +1,80 @@
+// vim: tw=80
+//! An expectation may belong to several sequences at once
+#![deny(warnings)]
+
+use mockall::*;
+
+mock! {
+    Foo {
+        fn init(&self);
+        fn read(&self);
+        fn write(&self);
+        fn shutdown(&self);
+    }
+}
+
+/// init precedes both read and write; write precedes shutdown.
+fn setup(mock: &mut MockFoo, seq_a: &mut Sequence, seq_b: &mut Sequence) {
+    mock.expect_init()
+        .times(1)
+        .in_sequence(seq_a)
+        .in_sequence(seq_b)
+        .return_const(());
+    mock.expect_read()
+        .times(1)
+        .in_sequence(seq_a)
+        .return_const(());
+    mock.expect_write()
+        .times(1)
+        .in_sequence(seq_b)
+        .return_const(());
+    mock.expect_shutdown()
+        .times(1)
+        .in_sequence(seq_b)
+        .return_const(());
+}
+
+#[test]
+fn ok() {
+    let mut seq_a = Sequence::new();
+    let mut seq_b = Sequence::new();
+    let mut mock = MockFoo::new();
+    setup(&mut mock, &mut seq_a, &mut seq_b);
+    mock.init();
+    mock.write();
+    mock.read();
+    mock.shutdown();
+}
+
+#[test]
+fn ok_other_order() {
+    let mut seq_a = Sequence::new();
+    let mut seq_b = Sequence::new();
+    let mut mock = MockFoo::new();
+    setup(&mut mock, &mut seq_a, &mut seq_b);
+    mock.init();
+    mock.write();
+    mock.shutdown();
+    mock.read();
+}
+
+#[test]
+#[should_panic(expected = "MockFoo::read(): Method sequence violation")]
+fn violates_first_sequence() {
+    let mut seq_a = Sequence::new();
+    let mut seq_b = Sequence::new();
+    let mut mock = MockFoo::new();
+    setup(&mut mock, &mut seq_a, &mut seq_b);
+    mock.read();
+}
+
+#[test]
+#[should_panic(expected = "MockFoo::shutdown(): Method sequence violation")]
+fn violates_second_sequence() {
+    let mut seq_a = Sequence::new();
+    let mut seq_b = Sequence::new();
+    let mut mock = MockFoo::new();
+    setup(&mut mock, &mut seq_a, &mut seq_b);
+    mock.init();
+    mock.shutdown();
+}
@@ -0,0 +1,78 @@
+// vim: tw=80
+//! mock! can mock standalone functions, outside of any struct
+#![deny(warnings)]
+
+use mockall::*;
+use mockall_double::double;
+
+#[derive(Debug, PartialEq)]
+pub struct Config(u32);
+
+#[derive(Debug, PartialEq)]
+pub struct Error;
+
+mod parser {
+    use super::*;
+
+    #[allow(dead_code)]
+    pub fn parse(_input: &str) -> Result<Config, Error> {
+        unimplemented!()
+    }
+
+    mock! {
+        pub fn parse(input: &str) -> Result<Config, Error>;
+        pub fn generic<T: 'static>(t: T) -> T;
+        pub(crate) fn no_args();
+    }
+}
+
+#[double]
+use parser::parse;
+
+fn load(input: &str) -> u32 {
+    parse(input).map(|c| c.0).unwrap_or(0)
+}
+
+mock! {
+    fn private(x: u32) -> u32;
+}
+
+#[test]
+fn returning() {
+    let ctx = parser::mock_parse_context();
+    ctx.expect()
+        .withf(|s| s == "5")
+        .returning(|_| Ok(Config(5)));
+    ctx.expect()
+        .returning(|_| Err(Error));
+    assert_eq!(5, load("5"));
+    assert_eq!(0, load("x"));
+}
+
+#[test]
+fn generic() {
+    let ctx = parser::mock_generic_context();
+    ctx.expect::<u32>()
+        .returning(|t| t + 1);
+    assert_eq!(2, parser::mock_generic(1u32));
+}
+
+#[test]
+#[should_panic(expected =
+    "no_args: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+fn checkpoint() {
+    let ctx = parser::mock_no_args_context();
+    ctx.expect()
+        .times(1)
+        .return_const(());
+    ctx.checkpoint();
+}
+
+#[test]
+fn private_fn() {
+    let ctx = mock_private_context();
+    ctx.expect()
+        .with(predicate::eq(3))
+        .return_const(4u32);
+    assert_eq!(4, mock_private(3));
+}
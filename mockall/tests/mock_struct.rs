@@ -64,12 +64,7 @@ mod checkpoint {
     }
 
     #[test]
-    #[cfg_attr(feature = "nightly", should_panic(
-            expected = "MockFoo::foo(0): No matching expectation found"
-    ))]
-    #[cfg_attr(not(feature = "nightly"), should_panic(
-            expected = "MockFoo::foo(?): No matching expectation found"
-    ))]
+    #[should_panic(expected = "MockFoo::foo(0): No matching expectation found")]
     fn removes_old_expectations() {
         let mut mock = MockFoo::new();
         mock.expect_foo()
@@ -123,12 +118,7 @@ mod r#match {
     }
 
     #[test]
-    #[cfg_attr(feature = "nightly", should_panic(
-            expected = "MockFoo::bar(5): No matching expectation found"
-    ))]
-    #[cfg_attr(not(feature = "nightly"), should_panic(
-            expected = "MockFoo::bar(?): No matching expectation found"
-    ))]
+    #[should_panic(expected = "MockFoo::bar(5): No matching expectation found")]
     fn with_no_matches() {
         let mut mock = MockFoo::new();
         mock.expect_bar()
@@ -156,12 +146,7 @@ mod r#match {
     }
 
     #[test]
-    #[cfg_attr(feature = "nightly", should_panic(
-            expected = "MockFoo::bar(5): No matching expectation found"
-    ))]
-    #[cfg_attr(not(feature = "nightly"), should_panic(
-            expected = "MockFoo::bar(?): No matching expectation found"
-    ))]
+    #[should_panic(expected = "MockFoo::bar(5): No matching expectation found")]
     fn withf_no_matches() {
         let mut mock = MockFoo::new();
         mock.expect_bar()
@@ -177,7 +162,7 @@ mod never {
 
     #[test]
     #[should_panic(expected =
-        "MockFoo::bar: Expectation(<anything>) should not have been called")]
+        "MockFoo::bar: Expectation(<anything>) was called, but never() was specified")]
     fn fail() {
         let mut mock = MockFoo::new();
         mock.expect_bar()
@@ -192,6 +177,30 @@ mod never {
         mock.expect_foo()
             .never();
     }
+
+    /// A `never` expectation with a matcher only forbids matching calls
+    #[test]
+    fn with() {
+        let mut mock = MockFoo::new();
+        mock.expect_bar()
+            .with(predicate::eq(4))
+            .never();
+        mock.expect_bar()
+            .returning(|_| ());
+        mock.bar(5);
+    }
+
+    #[test]
+    #[should_panic(expected = "was called, but never() was specified")]
+    fn with_fail() {
+        let mut mock = MockFoo::new();
+        mock.expect_bar()
+            .with(predicate::eq(4))
+            .never();
+        mock.expect_bar()
+            .returning(|_| ());
+        mock.bar(4);
+    }
 }
 
 #[test]
@@ -202,8 +211,6 @@ fn return_const() {
     assert_eq!(42, mock.foo(5));
 }
 
-#[cfg_attr(not(feature = "nightly"),
-    should_panic(expected = "MockFoo::foo: Expectation(<anything>) Returning default values requires"))]
 #[cfg_attr(not(feature = "nightly"), allow(unused_must_use))]
 #[test]
 fn return_default() {
@@ -235,6 +242,47 @@ mod sequence {
         mock.baz();
     }
 
+    #[test]
+    fn cyclic() {
+        let mut seq = Sequence::new_cyclic();
+        let mut mock = MockFoo::new();
+        mock.expect_baz()
+            .times(2)
+            .returning(|| ())
+            .in_sequence(&mut seq);
+
+        mock.expect_bar()
+            .times(2)
+            .returning(|_| ())
+            .in_sequence(&mut seq);
+
+        mock.baz();
+        mock.bar(0);
+        mock.baz();
+        mock.bar(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "MockFoo::baz(): Method sequence violation")]
+    fn cyclic_fail() {
+        let mut seq = Sequence::new_cyclic();
+        let mut mock = MockFoo::new();
+        mock.expect_baz()
+            .times(3)
+            .returning(|| ())
+            .in_sequence(&mut seq);
+
+        mock.expect_bar()
+            .times(3)
+            .returning(|_| ())
+            .in_sequence(&mut seq);
+
+        mock.baz();
+        mock.bar(0);
+        mock.baz();
+        mock.baz();
+    }
+
     #[test]
     #[should_panic(expected = "MockFoo::baz(): Method sequence violation")]
     fn fail() {
@@ -312,6 +360,17 @@ mod times {
         mock.baz();
     }
 
+    #[test]
+    #[should_panic(expected =
+        "MockFoo::baz: Expectation(<anything>) was called, but never() was specified")]
+    fn zero() {
+        let mut mock = MockFoo::new();
+        mock.expect_baz()
+            .returning(|| ())
+            .times(0);
+        mock.baz();
+    }
+
     #[test]
     #[should_panic(expected =
         "MockFoo::bar: Expectation(var == 5) called 1 time(s) which is fewer than expected 2")]
@@ -408,6 +467,38 @@ mod times {
         mock.baz();
     }
 
+    #[test]
+    #[should_panic(expected = "Empty range")]
+    fn rangeto_empty() {
+        let mut mock = MockFoo::new();
+        mock.expect_baz()
+            .returning(|| ())
+            .times(..0);
+    }
+
+    #[test]
+    fn rangetoinclusive_ok() {
+        let mut mock = MockFoo::new();
+        mock.expect_bar()
+            .returning(|_| ())
+            .times(..=2);
+        mock.bar(0);
+        mock.bar(0);
+    }
+
+    #[test]
+    #[should_panic(expected =
+        "MockFoo::baz: Expectation(<anything>) called 3 times which is more than the expected 2")]
+    fn rangetoinclusive_too_many() {
+        let mut mock = MockFoo::new();
+        mock.expect_baz()
+            .returning(|| ())
+            .times(..=2);
+        mock.baz();
+        mock.baz();
+        mock.baz();
+    }
+
     #[test]
     fn rangeinclusive_ok() {
         let mut mock = MockFoo::new();
@@ -420,6 +511,32 @@ mod times {
         mock.bar(0);
     }
 
+    #[test]
+    #[should_panic(expected =
+        "MockFoo::bar: Expectation(<anything>) called 0 time(s) which is fewer than expected 1")]
+    fn rangeinclusive_too_few() {
+        let mut mock = MockFoo::new();
+        mock.expect_bar()
+            .returning(|_| ())
+            .times(1..=5);
+    }
+
+    #[test]
+    #[should_panic(expected =
+        "MockFoo::bar: Expectation(<anything>) called 6 times which is more than the expected 5")]
+    fn rangeinclusive_too_many() {
+        let mut mock = MockFoo::new();
+        mock.expect_bar()
+            .returning(|_| ())
+            .times(1..=5);
+        for _ in 0..5 {
+            mock.bar(0);
+        }
+        mock.bar(0);
+        // Verify that we panic quickly and don't reach code below this point.
+        panic!("Shouldn't get here!");
+    }
+
     #[test]
     fn rangefrom_ok() {
         let mut mock = MockFoo::new();
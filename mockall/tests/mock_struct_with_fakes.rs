@@ -0,0 +1,76 @@
+// vim: tw=80
+//! Methods given a body in mock! aren't mocked.  The body is used as-is, while
+//! the other methods remain mockable.
+#![deny(warnings)]
+
+use mockall::*;
+
+pub trait Shape {
+    fn sides(&self) -> u32;
+    fn name(&self) -> String;
+    fn scale(&self, factor: u32) -> u32;
+}
+
+mock! {
+    pub Widget {
+        fn id(&self) -> u32 { 42 }
+        fn double(&self, mut x: u32) -> u32 {
+            x *= 2;
+            x
+        }
+        fn label() -> &'static str { "widget" }
+        fn size(&self) -> u32;
+    }
+    impl Shape for Widget {
+        fn sides(&self) -> u32 { 4 }
+        fn name(&self) -> String {
+            format!("{}-sided", self.sides())
+        }
+        fn scale(&self, factor: u32) -> u32;
+    }
+}
+
+#[test]
+fn inherent() {
+    let mut mock = MockWidget::new();
+    mock.expect_size().return_const(7u32);
+    assert_eq!(42, mock.id());
+    assert_eq!(6, mock.double(3));
+    assert_eq!(7, mock.size());
+}
+
+#[test]
+fn static_method() {
+    assert_eq!("widget", MockWidget::label());
+}
+
+#[test]
+fn trait_methods() {
+    let mut mock = MockWidget::new();
+    mock.expect_scale()
+        .returning(|factor| factor * 10);
+    assert_eq!(4, mock.sides());
+    assert_eq!("4-sided", mock.name());
+    assert_eq!(30, mock.scale(3));
+}
+
+/// A fake `new` replaces the generated one
+mod fake_new {
+    use super::*;
+
+    mock! {
+        pub Counter {
+            fn new() -> Self {
+                let mut mock = Self::default();
+                mock.expect_get().return_const(1u32);
+                mock
+            }
+            fn get(&self) -> u32;
+        }
+    }
+
+    #[test]
+    fn new() {
+        assert_eq!(1, MockCounter::new().get());
+    }
+}
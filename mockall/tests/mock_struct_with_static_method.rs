@@ -10,6 +10,8 @@ mock!{
         // We must have a separate method for every should_panic test
         fn bar2(x: u32) -> u64;
         fn bar3(x: u32) -> u64;
+        fn bar4(x: u32) -> u64;
+        fn bar5(x: u32) -> u64;
     }
 }
 
@@ -46,12 +48,7 @@ fn ctx_checkpoint() {
 
 // Expectations should be cleared when a context object drops
 #[test]
-#[cfg_attr(feature = "nightly", should_panic(
-        expected = "MockFoo::bar3(42): No matching expectation found"
-))]
-#[cfg_attr(not(feature = "nightly"), should_panic(
-        expected = "MockFoo::bar3(?): No matching expectation found"
-))]
+#[should_panic(expected = "MockFoo::bar3(42): No matching expectation found")]
 fn ctx_hygiene() {
     {
         let ctx0 = MockFoo::bar3_context();
@@ -61,6 +58,34 @@ fn ctx_hygiene() {
     MockFoo::bar3(42);
 }
 
+// Dropping the context object should verify the call count
+#[test]
+#[should_panic(expected =
+    "MockFoo::bar4: Expectation(<anything>) called 2 time(s) which is fewer than expected 3")]
+fn ctx_drop_times() {
+    let ctx = MockFoo::bar4_context();
+    ctx.expect()
+        .return_const(0u64)
+        .times(3);
+    MockFoo::bar4(0);
+    MockFoo::bar4(0);
+    drop(ctx);
+    panic!("Shouldn't get here!");
+}
+
+#[test]
+#[should_panic(expected =
+    "MockFoo::bar5: Expectation(<anything>) called 2 times which is more than the expected 1")]
+fn times_too_many() {
+    let ctx = MockFoo::bar5_context();
+    ctx.expect()
+        .return_const(0u64)
+        .times(1);
+    MockFoo::bar5(0);
+    MockFoo::bar5(0);
+    panic!("Shouldn't get here!");
+}
+
 #[test]
 fn return_const() {
     let _m = BAR_MTX.lock().unwrap();
@@ -23,3 +23,27 @@ fn return_const() {
 
     assert_eq!(42, mock.bar());
 }
+
+pub trait Baz {
+    unsafe fn baz(&self, x: u32) -> u32;
+}
+
+mock! {
+    pub Qux {}
+    unsafe impl Bar for Qux {
+        fn bar(&self) -> i32;
+    }
+    impl Baz for Qux {
+        unsafe fn baz(&self, x: u32) -> u32;
+    }
+}
+
+#[test]
+fn unsafe_method() {
+    let mut mock = MockQux::new();
+    mock.expect_baz()
+        .with(predicate::eq(4))
+        .returning(|x| x + 1);
+
+    assert_eq!(5, unsafe { mock.baz(4) });
+}
@@ -0,0 +1,55 @@
+// vim: tw=80
+//! Using Mockall's predicates with proptest strategies
+#![cfg(feature = "proptest")]
+#![deny(warnings)]
+
+use mockall::*;
+use mockall::strategy::{generated_by, matching};
+use proptest::prelude::*;
+
+#[automock]
+trait Foo {
+    fn foo(&self, x: u32);
+}
+
+fn small() -> impl Predicate<u32> {
+    predicate::lt(100u32)
+}
+
+proptest! {
+    #[test]
+    fn matching_strategy(x in matching(0u32..200, small())) {
+        let mut mock = MockFoo::new();
+        mock.expect_foo()
+            .with(small())
+            .times(1)
+            .return_const(());
+        mock.foo(x);
+    }
+}
+
+#[test]
+fn generated_by_match() {
+    let p = generated_by(prop_oneof![Just(1u32), Just(5u32)], 64);
+    assert!(p.eval(&1));
+    assert!(p.eval(&5));
+}
+
+#[test]
+fn generated_by_no_match() {
+    let p = generated_by(prop_oneof![Just(1u32), Just(5u32)], 64);
+    assert!(!p.eval(&2));
+}
+
+#[test]
+#[should_panic(expected =
+    "MockFoo::foo(2): No matching expectation found.  Expectation(var in values) rejected argument `x`")]
+fn with_generated_by() {
+    let mut mock = MockFoo::new();
+    mock.expect_foo()
+        .with(generated_by(prop_oneof![Just(1u32), Just(5u32)], 64))
+        .return_const(());
+    mock.foo(1);
+    mock.foo(5);
+    mock.foo(2);
+}
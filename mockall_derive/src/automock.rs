@@ -3,11 +3,18 @@ use super::*;
 use std::collections::HashMap;
 use syn::parse::{Parse, ParseStream};
 
+use crate::forward::Forward;
+
 /// A single automock attribute
 // This enum is very short-lived, so it's fine not to box it.
 #[allow(clippy::large_enum_variant)]
 enum Attr {
+    ExpectVis(Visibility),
+    Forward(Vec<Forward>),
     Mod(ItemMod),
+    Name(Ident),
+    NoMangle,
+    ThreadLocal,
     Type(TraitItemType),
 }
 
@@ -18,6 +25,30 @@ impl Parse for Attr {
             input.parse().map(Attr::Mod)
         } else if lookahead.peek(Token![type]) {
             input.parse().map(Attr::Type)
+        } else if lookahead.peek(Ident) {
+            let ident: Ident = input.parse()?;
+            if ident == "expect_vis" {
+                let content;
+                parenthesized!(content in input);
+                content.parse().map(Attr::ExpectVis)
+            } else if ident == "forward" {
+                let content;
+                parenthesized!(content in input);
+                let forwards = Punctuated::<Forward, Token![,]>
+                    ::parse_terminated(&content)?;
+                Ok(Attr::Forward(forwards.into_iter().collect()))
+            } else if ident == "name" {
+                input.parse::<Token![=]>()?;
+                let lit: LitStr = input.parse()?;
+                lit.parse().map(Attr::Name)
+            } else if ident == "no_mangle" {
+                Ok(Attr::NoMangle)
+            } else if ident == "thread_local" {
+                Ok(Attr::ThreadLocal)
+            } else {
+                Err(parse::Error::new(ident.span(),
+                    "Unknown automock attribute"))
+            }
         } else {
             Err(lookahead.error())
         }
@@ -28,7 +59,20 @@ impl Parse for Attr {
 #[derive(Debug, Default)]
 pub(crate) struct Attrs {
     pub attrs: HashMap<Ident, Type>,
-    pub modname: Option<Ident>
+    /// Visibility of the mock struct's `expect_*` methods, overriding the
+    /// visibility of the original methods
+    pub expect_vis: Option<Visibility>,
+    /// Wrapper types that should implement the mocked trait, too
+    pub forwards: Vec<Forward>,
+    pub modname: Option<Ident>,
+    /// Name of the generated mock struct, overriding the default of
+    /// `Mock<Name>`
+    pub name: Option<Ident>,
+    /// Export the mock versions of foreign functions under their original
+    /// symbol names
+    pub no_mangle: bool,
+    /// Give each thread its own expectations for static methods
+    pub thread_local: bool
 }
 
 impl Attrs {
@@ -51,15 +95,38 @@ impl Attrs {
         let trait_ident = find_ident_from_path(trait_path).0;
         for item in item_impl.items.iter_mut() {
             if let ImplItem::Method(method) = item {
-                let sig = &mut method.sig;
-                for fn_arg in sig.inputs.iter_mut() {
-                    if let FnArg::Typed(arg) = fn_arg {
-                        self.substitute_type(&mut arg.ty, &trait_ident);
+                self.substitute_sig(&mut method.sig, &trait_ident);
+            }
+        }
+    }
+
+    /// Substitute types in a method's arguments, return type, generic bounds,
+    /// and where clause
+    fn substitute_sig(&self, sig: &mut Signature, traitname: &Ident) {
+        for fn_arg in sig.inputs.iter_mut() {
+            if let FnArg::Typed(arg) = fn_arg {
+                self.substitute_type(&mut arg.ty, traitname);
+            }
+        }
+        if let ReturnType::Type(_, ref mut ty) = &mut sig.output {
+            self.substitute_type(ty, traitname);
+        }
+        for param in sig.generics.type_params_mut() {
+            for bound in param.bounds.iter_mut() {
+                self.substitute_type_param_bound(bound, traitname);
+            }
+            if let Some(ty) = param.default.as_mut() {
+                self.substitute_type(ty, traitname);
+            }
+        }
+        if let Some(wc) = sig.generics.where_clause.as_mut() {
+            for pred in wc.predicates.iter_mut() {
+                if let WherePredicate::Type(pt) = pred {
+                    self.substitute_type(&mut pt.bounded_ty, traitname);
+                    for bound in pt.bounds.iter_mut() {
+                        self.substitute_type_param_bound(bound, traitname);
                     }
                 }
-                if let ReturnType::Type(_, ref mut ty) = &mut sig.output {
-                    self.substitute_type(ty, &trait_ident);
-                }
             }
         }
     }
@@ -69,8 +136,13 @@ impl Attrs {
         match &mut seg.arguments {
             PathArguments::None => /* nothing to do */(),
             PathArguments::Parenthesized(p) => {
-                compile_error(p.span(),
-                    "Mockall does not support mocking Fn objects.  See https://github.com/asomers/mockall/issues/139");
+                // Fn-like traits, as in `&dyn Fn(u32) -> u32`
+                for input in p.inputs.iter_mut() {
+                    self.substitute_type(input, traitname);
+                }
+                if let ReturnType::Type(_, ref mut ty) = &mut p.output {
+                    self.substitute_type(ty, traitname);
+                }
             },
             PathArguments::AngleBracketed(abga) => {
                 for arg in abga.args.iter_mut() {
@@ -228,21 +300,22 @@ impl Attrs {
                     }
                 },
                 TraitItem::Method(method) => {
-                    let sig = &mut method.sig;
-                    for fn_arg in sig.inputs.iter_mut() {
-                        if let FnArg::Typed(arg) = fn_arg {
-                            self.substitute_type(&mut arg.ty, &item.ident);
-                        }
-                    }
-                    if let ReturnType::Type(_, ref mut ty) = &mut sig.output {
-                        self.substitute_type(ty, &item.ident);
-                    }
+                    self.substitute_sig(&mut method.sig, &item.ident);
                 },
                 _ => {
                     // Nothing to do
                 }
             }
         }
+        for ident in self.attrs.keys() {
+            let found = item.items.iter().any(|ti| matches!(ti,
+                TraitItem::Type(tity) if tity.ident == *ident));
+            if !found {
+                compile_error(ident.span(),
+                    &format!("Trait {} has no associated type {}", item.ident,
+                             ident));
+            }
+        }
         output
     }
 }
@@ -250,10 +323,21 @@ impl Attrs {
 impl Parse for Attrs {
     fn parse(input: ParseStream) -> parse::Result<Self> {
         let mut attrs = HashMap::new();
+        let mut expect_vis = None;
+        let mut forwards = Vec::new();
         let mut modname = None;
+        let mut name = None;
+        let mut no_mangle = false;
+        let mut thread_local = false;
         while !input.is_empty() {
             let attr: Attr = input.parse()?;
             match attr {
+                Attr::ExpectVis(vis) => {
+                    expect_vis = Some(vis);
+                },
+                Attr::Forward(f) => {
+                    forwards.extend(f);
+                },
                 Attr::Mod(item_mod) => {
                     if let Some((br, _)) = item_mod.content {
                         compile_error(br.span,
@@ -261,6 +345,15 @@ impl Parse for Attrs {
                     }
                     modname = Some(item_mod.ident.clone());
                 },
+                Attr::Name(ident) => {
+                    name = Some(ident);
+                },
+                Attr::NoMangle => {
+                    no_mangle = true;
+                },
+                Attr::ThreadLocal => {
+                    thread_local = true;
+                },
                 Attr::Type(trait_item_type) => {
                     let ident = trait_item_type.ident.clone();
                     if let Some((_, ty)) = trait_item_type.default {
@@ -272,7 +365,8 @@ impl Parse for Attrs {
                 }
             }
         }
-        Ok(Attrs{attrs, modname})
+        Ok(Attrs{attrs, expect_vis, forwards, modname, name, no_mangle,
+                 thread_local})
     }
 }
 
@@ -295,6 +389,66 @@ mod t {
         assert_eq!(in_ty, expect_ty);
     }
 
+    #[test]
+    fn expect_vis() {
+        let attrs: super::Attrs = parse2(quote!(expect_vis(pub(crate))))
+            .unwrap();
+        let expected: Visibility = parse2(quote!(pub(crate))).unwrap();
+        assert_eq!(attrs.expect_vis, Some(expected));
+        let attrs: super::Attrs = parse2(quote!(expect_vis())).unwrap();
+        assert_eq!(attrs.expect_vis, Some(Visibility::Inherited));
+    }
+
+    #[test]
+    fn forward() {
+        use crate::forward::Forward;
+        let attrs: super::Attrs = parse2(quote!(forward(&, &mut, Box, Rc, Arc)))
+            .unwrap();
+        assert_eq!(attrs.forwards, vec![Forward::Ref, Forward::RefMut,
+            Forward::Box, Forward::Rc, Forward::Arc]);
+    }
+
+    #[test]
+    fn forward_unknown() {
+        let e = parse2::<super::Attrs>(quote!(forward(Cow))).unwrap_err();
+        assert_eq!(e.to_string(),
+            "Can only forward through &, &mut, Box, Rc, or Arc");
+    }
+
+    #[test]
+    fn name() {
+        let attrs: super::Attrs = parse2(quote!(name = "FakeFoo")).unwrap();
+        assert_eq!(attrs.name, Some(format_ident!("FakeFoo")));
+    }
+
+    #[test]
+    fn name_invalid() {
+        let e = parse2::<super::Attrs>(quote!(name = "Fake Foo")).unwrap_err();
+        assert_eq!(e.to_string(), "unexpected token");
+    }
+
+    #[test]
+    fn no_mangle() {
+        let attrs: super::Attrs = parse2(quote!(no_mangle)).unwrap();
+        assert!(attrs.no_mangle);
+        let attrs: super::Attrs = parse2(quote!(type T = u32;)).unwrap();
+        assert!(!attrs.no_mangle);
+    }
+
+    #[test]
+    fn thread_local() {
+        let attrs: super::Attrs = parse2(quote!(thread_local)).unwrap();
+        assert!(attrs.thread_local);
+        let attrs: super::Attrs = parse2(quote!(no_mangle)).unwrap();
+        assert!(!attrs.thread_local);
+    }
+
+    #[test]
+    fn unknown_attribute() {
+        let e = parse2::<super::Attrs>(quote!(no_such_thing)).unwrap_err();
+        assert_eq!(e.to_string(), "Unknown automock attribute");
+    }
+
     #[test]
     fn qself() {
         check_substitute_type(quote!(type T = u32;),
@@ -320,4 +474,37 @@ mod t {
                               format_ident!("Foo"),
                               quote!(u32));
     }
+
+    #[test]
+    fn substitute_trait_generics() {
+        let attrs: super::Attrs = parse2(quote!(type T = u32;)).unwrap();
+        let item: ItemTrait = parse2(quote!(
+            trait Foo {
+                type T;
+                fn foo<Q: Into<Self::T>>(&self, q: Q);
+                fn bar<Q>(&self, q: Q) where Q: From<Self::T>;
+            }
+        )).unwrap();
+        let expected: ItemTrait = parse2(quote!(
+            trait Foo {
+                type T = u32;
+                fn foo<Q: Into<u32>>(&self, q: Q);
+                fn bar<Q>(&self, q: Q) where Q: From<u32>;
+            }
+        )).unwrap();
+        assert_eq!(attrs.substitute_trait(&item), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "Trait Foo has no associated type Q")]
+    fn substitute_trait_unknown() {
+        let attrs: super::Attrs = parse2(quote!(type T = u32; type Q = i32;))
+            .unwrap();
+        let item: ItemTrait = parse2(quote!(
+            trait Foo {
+                type T;
+            }
+        )).unwrap();
+        attrs.substitute_trait(&item);
+    }
 }
@@ -0,0 +1,267 @@
+// vim: tw=80
+//! Implement a mocked trait for smart pointers and references to the mock
+//! struct, by forwarding every method to the mock struct itself.
+use super::*;
+
+use syn::parse::{Parse, ParseStream};
+
+/// A type that wraps a mock struct, and can implement the mocked trait by
+/// forwarding.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Forward {
+    Arc,
+    Box,
+    Rc,
+    Ref,
+    RefMut
+}
+
+impl Forward {
+    /// Can this wrapper forward methods with the given receiver?
+    fn allows(&self, receiver: &Receiver) -> bool {
+        matches!((self, &receiver.reference, &receiver.mutability),
+            (_, Some(_), None) |
+            (Forward::Box, Some(_), Some(_)) |
+            (Forward::RefMut, Some(_), Some(_)) |
+            (Forward::Box, None, _)
+        )
+    }
+
+    /// The wrapper's type, given the mock struct's type
+    fn wrap(&self, ty: &TokenStream) -> TokenStream {
+        match self {
+            Forward::Arc => quote!(::std::sync::Arc<#ty>),
+            Forward::Box => quote!(::std::boxed::Box<#ty>),
+            Forward::Rc => quote!(::std::rc::Rc<#ty>),
+            Forward::Ref => quote!(&'__mockall_fwd #ty),
+            Forward::RefMut => quote!(&'__mockall_fwd mut #ty),
+        }
+    }
+}
+
+impl Parse for Forward {
+    fn parse(input: ParseStream) -> parse::Result<Self> {
+        if input.peek(Token![&]) {
+            input.parse::<Token![&]>()?;
+            if input.peek(Token![mut]) {
+                input.parse::<Token![mut]>()?;
+                Ok(Forward::RefMut)
+            } else {
+                Ok(Forward::Ref)
+            }
+        } else {
+            let ident: Ident = input.parse()?;
+            if ident == "Arc" {
+                Ok(Forward::Arc)
+            } else if ident == "Box" {
+                Ok(Forward::Box)
+            } else if ident == "Rc" {
+                Ok(Forward::Rc)
+            } else {
+                Err(parse::Error::new(ident.span(),
+                    "Can only forward through &, &mut, Box, Rc, or Arc"))
+            }
+        }
+    }
+}
+
+/// Does this type refer to `Self`, other than through an associated type like
+/// `Self::Item`?
+fn uses_self(ty: &Type) -> bool {
+    struct SelfFinder(bool);
+    impl SelfFinder {
+        fn check_path(&mut self, path: &Path) {
+            if path.is_ident("Self") {
+                self.0 = true;
+            }
+            for seg in path.segments.iter() {
+                if let PathArguments::AngleBracketed(abga) = &seg.arguments {
+                    for arg in abga.args.iter() {
+                        match arg {
+                            GenericArgument::Type(ty) => self.check(ty),
+                            GenericArgument::Binding(b) => self.check(&b.ty),
+                            _ => ()
+                        }
+                    }
+                }
+            }
+        }
+
+        fn check(&mut self, ty: &Type) {
+            match ty {
+                Type::Array(a) => self.check(&a.elem),
+                Type::Group(g) => self.check(&g.elem),
+                Type::Paren(p) => self.check(&p.elem),
+                Type::Path(tp) => {
+                    match &tp.qself {
+                        Some(qself) => match qself.ty.as_ref() {
+                            // Like <Self as Foo>::Item.  That's fine.
+                            Type::Path(qtp) if qtp.path.is_ident("Self") => (),
+                            qty => self.check(qty)
+                        },
+                        None => self.check_path(&tp.path)
+                    }
+                },
+                Type::Ptr(p) => self.check(&p.elem),
+                Type::Reference(r) => self.check(&r.elem),
+                Type::Slice(s) => self.check(&s.elem),
+                Type::Tuple(t) => t.elems.iter().for_each(|e| self.check(e)),
+                Type::ImplTrait(it) => {
+                    for bound in it.bounds.iter() {
+                        if let TypeParamBound::Trait(tb) = bound {
+                            self.check_path(&tb.path);
+                        }
+                    }
+                },
+                Type::TraitObject(to) => {
+                    for bound in to.bounds.iter() {
+                        if let TypeParamBound::Trait(tb) = bound {
+                            self.check_path(&tb.path);
+                        }
+                    }
+                },
+                _ => ()
+            }
+        }
+    }
+
+    let mut finder = SelfFinder(false);
+    finder.check(ty);
+    finder.0
+}
+
+/// Generate a forwarding method for one trait method.
+///
+/// Returns `None` if the method can't be forwarded by this wrapper.
+fn forward_method(fwd: Forward, meth: &TraitItemMethod, mock_ty: &TokenStream,
+    trait_path: &TokenStream) -> Option<TokenStream>
+{
+    let mut sig = meth.sig.clone();
+    let ident = &sig.ident;
+    let mut receiver = None;
+    let mut argnames = Vec::new();
+    for (i, fn_arg) in sig.inputs.iter_mut().enumerate() {
+        match fn_arg {
+            FnArg::Receiver(r) => {
+                if !fwd.allows(r) {
+                    return None;
+                }
+                receiver = Some(match (&r.reference, &r.mutability) {
+                    (Some(_), None) => quote!(&**self),
+                    (Some(_), Some(_)) => quote!(&mut **self),
+                    (None, _) => quote!(*self)
+                });
+            },
+            FnArg::Typed(pt) => {
+                if uses_self(&pt.ty) {
+                    return None;
+                }
+                let argname = format_ident!("__mockall_arg{}", i);
+                *pt.pat = Pat::Ident(PatIdent {
+                    attrs: Vec::new(),
+                    by_ref: None,
+                    mutability: None,
+                    ident: argname.clone(),
+                    subpat: None
+                });
+                argnames.push(argname);
+            }
+        }
+    }
+    if let ReturnType::Type(_, ty) = &sig.output {
+        if uses_self(ty) {
+            return None;
+        }
+    }
+    let turbofish = if sig.generics.type_params().next().is_some() {
+        let tps = sig.generics.type_params().map(|tp| &tp.ident);
+        quote!(::<#(#tps),*>)
+    } else {
+        quote!()
+    };
+    let dotawait = if sig.asyncness.is_some() {
+        quote!(.await)
+    } else {
+        quote!()
+    };
+    let attrs = AttrFormatter::new(&meth.attrs)
+        .doc(false)
+        .format();
+    let call_args = receiver.into_iter()
+        .chain(argnames.iter().map(|a| quote!(#a)));
+    Some(quote!(
+        #(#attrs)*
+        #sig {
+            <#mock_ty as #trait_path>::#ident #turbofish(#(#call_args),*)
+                #dotawait
+        }
+    ))
+}
+
+/// Implement the trait for each of the requested wrappers of the mock struct.
+pub(crate) fn forward_impls(
+    forwards: &[Forward],
+    mock_ident: &Ident,
+    item_trait: &ItemTrait) -> TokenStream
+{
+    let mut output = TokenStream::new();
+    let trait_ident = &item_trait.ident;
+    let (_, tg, _) = item_trait.generics.split_for_impl();
+    let mock_ty = quote!(#mock_ident #tg);
+    let trait_path = quote!(#trait_ident #tg);
+    let unsafety = &item_trait.unsafety;
+    let async_trait = item_trait.attrs.iter()
+        .filter(|attr| {
+            attr.path.segments.iter().any(|seg| seg.ident == "async_trait")
+        }).collect::<Vec<_>>();
+    let cfgs = item_trait.attrs.iter()
+        .filter(|attr| attr.path.is_ident("cfg"))
+        .collect::<Vec<_>>();
+    for fwd in forwards.iter() {
+        let mut generics = item_trait.generics.clone();
+        if *fwd == Forward::Ref || *fwd == Forward::RefMut {
+            let lt = Lifetime::new("'__mockall_fwd", Span::call_site());
+            generics.params.insert(0,
+                GenericParam::Lifetime(LifetimeDef::new(lt)));
+        }
+        let (ig, _, wc) = generics.split_for_impl();
+        let self_ty = fwd.wrap(&mock_ty);
+        let mut items = Vec::new();
+        for item in item_trait.items.iter() {
+            match item {
+                TraitItem::Const(tic) => {
+                    let ident = &tic.ident;
+                    let ty = &tic.ty;
+                    items.push(quote!(
+                        const #ident: #ty = <#mock_ty as #trait_path>::#ident;
+                    ));
+                },
+                TraitItem::Method(meth) => {
+                    if let Some(ts) = forward_method(*fwd, meth, &mock_ty,
+                                                     &trait_path)
+                    {
+                        items.push(ts);
+                    } else {
+                        compile_error(meth.sig.span(),
+                            "This method can't be forwarded through the requested wrapper type.  It may have an incompatible receiver, or use Self");
+                    }
+                },
+                TraitItem::Type(tit) => {
+                    let ident = &tit.ident;
+                    items.push(quote!(
+                        type #ident = <#mock_ty as #trait_path>::#ident;
+                    ));
+                },
+                _ => ()
+            }
+        }
+        quote!(
+            #(#cfgs)*
+            #(#async_trait)*
+            #unsafety impl #ig #trait_path for #self_ty #wc {
+                #(#items)*
+            }
+        ).to_tokens(&mut output);
+    }
+    output
+}
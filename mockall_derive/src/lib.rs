@@ -9,7 +9,7 @@
 #![cfg_attr(test, deny(warnings))]
 
 use cfg_if::cfg_if;
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
 use quote::{ToTokens, format_ident, quote};
 use std::{
     env,
@@ -22,6 +22,7 @@ use syn::{
 };
 
 mod automock;
+mod forward;
 mod mock_function;
 mod mock_item;
 mod mock_item_struct;
@@ -34,6 +35,13 @@ use crate::mock_item::MockItem;
 use crate::mock_item_struct::MockItemStruct;
 use crate::mockable_item::MockableItem;
 
+/// Traits that may be added to any trait object
+const AUTO_TRAITS: [&str; 5] =
+    ["RefUnwindSafe", "Send", "Sync", "Unpin", "UnwindSafe"];
+
+/// Formatting traits whose `fmt` method may be mocked as returning a `String`
+const FORMAT_TRAITS: [&str; 2] = ["Debug", "Display"];
+
 // Define deterministic aliases for these common types.
 type HashMap<K, V> = std::collections::HashMap<K, V, BuildHasherDefault<std::collections::hash_map::DefaultHasher>>;
 type HashSet<K> = std::collections::HashSet<K, BuildHasherDefault<std::collections::hash_map::DefaultHasher>>;
@@ -62,38 +70,64 @@ fn concretize_args(gen: &Generics, args: &Punctuated<FnArg, Token![,]>) ->
     let mut hm = HashMap::default();
 
     let mut save_types = |ident: &Ident, tpb: &Punctuated<TypeParamBound, Token![+]>| {
-        if !tpb.is_empty() {
-            if let Ok(newty) = parse2::<Type>(quote!(&dyn #tpb)) {
-                // substitute T arguments
-                let subst_ty: Type = parse2(quote!(#ident)).unwrap();
-                hm.insert(subst_ty, (newty.clone(), None));
-
-                // substitute &T arguments
-                let subst_ty: Type = parse2(quote!(&#ident)).unwrap();
-                hm.insert(subst_ty, (newty, None));
-            } else {
-                compile_error(tpb.span(),
-                    "Type cannot be made into a trait object");
-            }
+        // `?Sized` is meaningless for a trait object
+        let mut tpb = tpb.iter()
+            .filter(|b| !matches!(b, TypeParamBound::Trait(tb)
+                if matches!(tb.modifier, TraitBoundModifier::Maybe(_))))
+            .cloned()
+            .collect::<Punctuated<TypeParamBound, Token![+]>>();
+        if tpb.is_empty() {
+            // An unbounded type can't be inspected, but it can still be
+            // turned into a trait object.
+            tpb.push(parse2(quote!(::mockall::Opaque)).unwrap());
+        }
+        let principals = tpb.iter()
+            .filter(|b| matches!(b, TypeParamBound::Trait(tb)
+                if !AUTO_TRAITS.iter()
+                    .any(|at| tb.path.segments.last().unwrap().ident == at)
+            )).count();
+        if principals > 1 {
+            compile_error(tpb.span(),
+                "Type cannot be made into a trait object");
+            return;
+        }
+        // A trait object with several bounds must be parenthesized when
+        // it's behind a reference.
+        let dyn_ty = if tpb.len() > 1 {
+            quote!((dyn #tpb))
+        } else {
+            quote!(dyn #tpb)
+        };
+        if let Ok(newty) = parse2::<Type>(quote!(&#dyn_ty)) {
+            // substitute T arguments
+            let subst_ty: Type = parse2(quote!(#ident)).unwrap();
+            hm.insert(subst_ty, (newty.clone(), None));
+
+            // substitute &T arguments
+            let subst_ty: Type = parse2(quote!(&#ident)).unwrap();
+            hm.insert(subst_ty, (newty, None));
+        } else {
+            compile_error(tpb.span(),
+                "Type cannot be made into a trait object");
+        }
 
-            if let Ok(newty) = parse2::<Type>(quote!(&mut dyn #tpb)) {
-                // substitute &mut T arguments
-                let subst_ty: Type = parse2(quote!(&mut #ident)).unwrap();
-                hm.insert(subst_ty, (newty, None));
-            } else {
-                compile_error(tpb.span(),
-                    "Type cannot be made into a trait object");
-            }
+        if let Ok(newty) = parse2::<Type>(quote!(&mut #dyn_ty)) {
+            // substitute &mut T arguments
+            let subst_ty: Type = parse2(quote!(&mut #ident)).unwrap();
+            hm.insert(subst_ty, (newty, None));
+        } else {
+            compile_error(tpb.span(),
+                "Type cannot be made into a trait object");
+        }
 
-            // I wish we could substitute &[T] arguments.  But there's no way
-            // for the mock method to turn &[T] into &[&dyn T].
-            if let Ok(newty) = parse2::<Type>(quote!(&[&dyn #tpb])) {
-                let subst_ty: Type = parse2(quote!(&[#ident])).unwrap();
-                hm.insert(subst_ty, (newty, Some(tpb.clone())));
-            } else {
-                compile_error(tpb.span(),
-                    "Type cannot be made into a trait object");
-            }
+        // I wish we could substitute &[T] arguments.  But there's no way
+        // for the mock method to turn &[T] into &[&dyn T].
+        if let Ok(newty) = parse2::<Type>(quote!(&[&#dyn_ty])) {
+            let subst_ty: Type = parse2(quote!(&[#ident])).unwrap();
+            hm.insert(subst_ty, (newty, Some(tpb)));
+        } else {
+            compile_error(tpb.span(),
+                "Type cannot be made into a trait object");
         }
     };
 
@@ -161,9 +195,13 @@ fn concretize_args(gen: &Generics, args: &Punctuated<FnArg, Token![,]>) ->
                         if let Type::Slice(_ts) = &*tr.elem {
                             // Assume _ts is the generic type or we wouldn't be
                             // here
+                            let dyn_ty = match newbound {
+                                Some(nb) if nb.len() > 1 => quote!((dyn #nb)),
+                                _ => quote!(dyn #newbound)
+                            };
                             Some(quote!(
                                 &(0..#pat.len())
-                                .map(|__mockall_i| &#pat[__mockall_i] as &dyn #newbound)
+                                .map(|__mockall_i| &#pat[__mockall_i] as &#dyn_ty)
                                 .collect::<Vec<_>>()
                             ))
                         } else {
@@ -383,6 +421,89 @@ fn declosurefy(gen: &Generics, args: &Punctuated<FnArg, Token![,]>) ->
     (outg, outargs, callargs)
 }
 
+/// Replace any "impl trait" arguments with new generic type parameters.
+///
+/// Returns the names of the new parameters.  They're appended to the
+/// signature's existing generic parameters.
+fn deimplify_args(sig: &mut Signature) -> Vec<Ident> {
+    let mut new_params = Vec::new();
+    for fa in sig.inputs.iter_mut() {
+        if let FnArg::Typed(pt) = fa {
+            // Look through a reference, like `&impl Trait`
+            let ty = match pt.ty.as_mut() {
+                Type::Reference(tr) => tr.elem.as_mut(),
+                ty => ty
+            };
+            if let Type::ImplTrait(tit) = ty {
+                let ident = format_ident!("MockallImpl{}", new_params.len());
+                let bounds = &tit.bounds;
+                let tp: TypeParam = parse2(quote!(#ident: #bounds)).unwrap();
+                sig.generics.params.push(GenericParam::Type(tp));
+                *ty = parse2(quote!(#ident)).unwrap();
+                new_params.push(ident);
+            }
+        }
+    }
+    if !new_params.is_empty() {
+        sig.generics.lt_token.get_or_insert_with(Default::default);
+        sig.generics.gt_token.get_or_insert_with(Default::default);
+    }
+    new_params
+}
+
+/// Give a name to each anonymous lifetime `'_` in the argument types, like
+/// `f: &mut Formatter<'_>`.
+///
+/// The new lifetimes are added to the signature's generic parameters, because
+/// anonymous lifetimes aren't allowed in the expectation's types.
+fn name_anonymous_lifetimes(sig: &mut Signature) {
+    fn rename(ts: TokenStream, new_lts: &mut Vec<Lifetime>) -> TokenStream {
+        let mut output = Vec::new();
+        for tt in ts.into_iter() {
+            match tt {
+                TokenTree::Ident(ident) if ident == "_" &&
+                    matches!(output.last(),
+                        Some(TokenTree::Punct(p)) if p.as_char() == '\'') =>
+                {
+                    let lt = Lifetime::new(
+                        &format!("'__mockall_anon{}", new_lts.len()),
+                        ident.span());
+                    output.push(TokenTree::Ident(lt.ident.clone()));
+                    new_lts.push(lt);
+                },
+                TokenTree::Group(group) => {
+                    let mut new = Group::new(group.delimiter(),
+                        rename(group.stream(), new_lts));
+                    new.set_span(group.span());
+                    output.push(TokenTree::Group(new));
+                },
+                tt => output.push(tt)
+            }
+        }
+        output.into_iter().collect()
+    }
+
+    let mut new_lts = Vec::new();
+    for fa in sig.inputs.iter_mut() {
+        if let FnArg::Typed(pt) = fa {
+            let ty = &pt.ty;
+            let n = new_lts.len();
+            let ts = rename(quote!(#ty), &mut new_lts);
+            if new_lts.len() > n {
+                *pt.ty = parse2(ts).unwrap();
+            }
+        }
+    }
+    if !new_lts.is_empty() {
+        for (i, lt) in new_lts.into_iter().enumerate() {
+            sig.generics.params.insert(i,
+                GenericParam::Lifetime(LifetimeDef::new(lt)));
+        }
+        sig.generics.lt_token.get_or_insert_with(Default::default);
+        sig.generics.gt_token.get_or_insert_with(Default::default);
+    }
+}
+
 /// Replace any "impl trait" types with "Box<dyn trait>" or equivalent.
 fn deimplify(rt: &mut ReturnType) {
     if let ReturnType::Type(_, ty) = rt {
@@ -654,6 +775,22 @@ fn find_lifetimes_in_path(path: &Path) -> HashSet<Lifetime> {
 fn find_lifetimes(ty: &Type) -> HashSet<Lifetime> {
     match ty {
         Type::Array(ta) => find_lifetimes(ta.elem.as_ref()),
+        Type::BareFn(tbf) => {
+            let mut ret = HashSet::default();
+            for arg in tbf.inputs.iter() {
+                ret.extend(find_lifetimes(&arg.ty));
+            }
+            if let ReturnType::Type(_, ty) = &tbf.output {
+                ret.extend(find_lifetimes(ty));
+            }
+            // Lifetimes bound by a `for<'x>` clause aren't free
+            if let Some(bl) = &tbf.lifetimes {
+                for ltd in bl.lifetimes.iter() {
+                    ret.remove(&ltd.lifetime);
+                }
+            }
+            ret
+        },
         Type::Group(tg) => find_lifetimes(tg.elem.as_ref()),
         Type::Infer(_ti) => HashSet::default(),
         Type::Never(_tn) => HashSet::default(),
@@ -707,6 +844,7 @@ struct AttrFormatter<'a>{
     attrs: &'a [Attribute],
     async_trait: bool,
     doc: bool,
+    fn_only: bool,
 }
 
 impl<'a> AttrFormatter<'a> {
@@ -714,7 +852,8 @@ impl<'a> AttrFormatter<'a> {
         Self {
             attrs,
             async_trait: true,
-            doc: true
+            doc: true,
+            fn_only: false
         }
     }
 
@@ -728,36 +867,53 @@ impl<'a> AttrFormatter<'a> {
         self
     }
 
+    /// Allow attributes like `#[must_use]` and `#[deprecated]`, which only make
+    /// sense on the mock function itself, not on its expectations.
+    fn fn_only(&mut self, allowed: bool) -> &mut Self {
+        self.fn_only = allowed;
+        self
+    }
+
     // XXX This logic requires that attributes are imported with their
     // standard names.
-    #[allow(clippy::needless_bool)]
-    #[allow(clippy::if_same_then_else)]
     fn format(&mut self) -> Vec<Attribute> {
         self.attrs.iter()
-            .cloned()
             .filter(|attr| {
-                let i = attr.path.segments.last().map(|ps| &ps.ident);
-                if i.is_none() {
-                    false
-                } else if *i.as_ref().unwrap() == "derive" {
+                let i = if let Some(ps) = attr.path.segments.last() {
+                    ps.ident.to_string()
+                } else {
+                    return false;
+                };
+                match i.as_str() {
                     // We can't usefully derive any traits.  Ignore them
-                    false
-                } else if *i.as_ref().unwrap() == "doc" {
-                    self.doc
-                } else if *i.as_ref().unwrap() == "async_trait" {
-                    self.async_trait
-                } else if *i.as_ref().unwrap() == "instrument" {
+                    "derive" => false,
+                    "doc" => self.doc,
+                    "async_trait" => self.async_trait,
+                    "must_use" | "deprecated" => self.fn_only,
                     // We can't usefully instrument the mock method, so just
                     // ignore this attribute.
                     // https://docs.rs/tracing/0.1.23/tracing/attr.instrument.html
-                    false
-                } else if *i.as_ref().unwrap() == "concretize" {
+                    "instrument" => false,
                     // Internally used attribute.  Never emit.
-                    false
-                } else {
-                    true
+                    "concretize" => false,
+                    // Only valid on foreign items, which the mock functions
+                    // are not.
+                    "link_name" => false,
+                    _ => true
                 }
-            }).collect()
+            }).cloned()
+            .collect()
+    }
+}
+
+/// Does this method have a body, rather than ending with a semicolon?  In
+/// `mock!`, such a method is a fake: its body is used as-is, and it gets no
+/// expectations.
+fn is_fake(meth: &ImplItemMethod) -> bool {
+    // syn represents a missing body as a block holding only the semicolon
+    match &meth.block.stmts[..] {
+        [Stmt::Item(Item::Verbatim(ts))] => ts.to_string() != ";",
+        _ => true
     }
 }
 
@@ -924,16 +1080,31 @@ fn gen_keyid(g: &Generics) -> impl ToTokens {
     match g.params.len() {
         0 => quote!(<()>),
         1 => {
-            let (_, tg, _) = g.split_for_impl();
-            quote!(#tg)
+            if let Some(cp) = g.const_params().next() {
+                let ident = &cp.ident;
+                quote!(<[(); #ident]>)
+            } else {
+                let (_, tg, _) = g.split_for_impl();
+                quote!(#tg)
+            }
         },
         _ => {
             // Rust doesn't support variadic Generics, so mockall::Key must
             // always have exactly one generic type.  We need to add parentheses
-            // around whatever type generics the caller passes.
-            let tps = g.type_params()
-            .map(|tp| tp.ident.clone())
-            .collect::<Punctuated::<Ident, Token![,]>>();
+            // around whatever type generics the caller passes.  Const
+            // parameters aren't types, but arrays of their length are.
+            let tps = g.params.iter()
+            .filter_map(|gp| match gp {
+                GenericParam::Type(tp) => {
+                    let ident = &tp.ident;
+                    Some(quote!(#ident))
+                },
+                GenericParam::Const(cp) => {
+                    let ident = &cp.ident;
+                    Some(quote!([(); #ident]))
+                },
+                GenericParam::Lifetime(_) => None
+            }).collect::<Punctuated::<TokenStream, Token![,]>>();
             quote!(<(#tps)>)
         }
     }
@@ -1066,6 +1237,19 @@ fn lifetimes_to_generics(lv: &Punctuated<LifetimeDef, Token![,]>)-> Generics {
     }
 }
 
+/// Remove any bounds that refer to the given lifetimes
+fn strip_lifetime_bounds(
+    bounds: &mut Punctuated<TypeParamBound, Token![+]>,
+    lifetimes: &HashSet<Lifetime>)
+{
+    *bounds = bounds.iter()
+        .filter(|tpb| match tpb {
+            TypeParamBound::Lifetime(lt) => !lifetimes.contains(lt),
+            _ => true
+        }).cloned()
+        .collect();
+}
+
 /// Split a generics list into three: one for type generics and where predicates
 /// that relate to the signature, one for lifetimes that relate to the arguments
 /// only, and one for lifetimes that relate to the return type only.
@@ -1101,6 +1285,9 @@ fn split_lifetimes(
         rlts.extend(find_lifetimes(ty));
     }
 
+    let lifetimes = generics.lifetimes()
+        .map(|ltd| ltd.lifetime.clone())
+        .collect::<HashSet<_>>();
     let mut tv = Punctuated::new();
     let mut alv = Punctuated::new();
     let mut rlv = Punctuated::new();
@@ -1114,19 +1301,45 @@ fn split_lifetimes(
                 // Probably a lifetime parameter from the impl block that isn't
                 // used by this particular method
             },
-            GenericParam::Type(_) => tv.push(p),
-            _ => (),
+            GenericParam::Type(mut tp) => {
+                strip_lifetime_bounds(&mut tp.bounds, &lifetimes);
+                tv.push(GenericParam::Type(tp))
+            },
+            GenericParam::Const(_) => tv.push(p),
         }
     }
 
     let tg = if tv.is_empty() {
         Generics::default()
     } else {
+        // The type generics won't have any lifetime parameters, so their where
+        // clause mustn't refer to any.
+        let where_clause = generics.where_clause.and_then(|mut wc| {
+            wc.predicates = wc.predicates.into_iter()
+                .filter_map(|wp| match wp {
+                    WherePredicate::Type(mut pt) => {
+                        strip_lifetime_bounds(&mut pt.bounds, &lifetimes);
+                        if pt.bounds.is_empty() {
+                            None
+                        } else {
+                            Some(WherePredicate::Type(pt))
+                        }
+                    },
+                    WherePredicate::Lifetime(pl)
+                        if lifetimes.contains(&pl.lifetime) => None,
+                    wp => Some(wp)
+                }).collect();
+            if wc.predicates.is_empty() {
+                None
+            } else {
+                Some(wc)
+            }
+        });
         Generics {
             lt_token: generics.lt_token,
             gt_token: generics.gt_token,
             params: tv,
-            where_clause: generics.where_clause
+            where_clause
         }
     };
 
@@ -1204,7 +1417,7 @@ fn mock_it<M: Into<MockableItem>>(inputs: M) -> TokenStream
 
 fn do_mock_once(input: TokenStream) -> TokenStream
 {
-    let item: MockableStruct = match syn::parse2(input) {
+    let item: MockableItem = match syn::parse2(input) {
         Ok(mock) => mock,
         Err(err) => {
             return err.to_compile_error();
@@ -1235,6 +1448,26 @@ pub fn concretize(
     input
 }
 
+#[proc_macro_attribute]
+pub fn keep_default(
+    _attrs: proc_macro::TokenStream,
+    input: proc_macro::TokenStream) -> proc_macro::TokenStream
+{
+    // Do nothing.  This "attribute" is processed as text by the real proc
+    // macros.
+    input
+}
+
+#[proc_macro_attribute]
+pub fn stub(
+    _attrs: proc_macro::TokenStream,
+    input: proc_macro::TokenStream) -> proc_macro::TokenStream
+{
+    // Do nothing.  This "attribute" is processed as text by the real proc
+    // macros.
+    input
+}
+
 #[proc_macro]
 pub fn mock(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     do_mock(input.into()).into()
@@ -1249,6 +1482,39 @@ pub fn automock(attrs: proc_macro::TokenStream, input: proc_macro::TokenStream)
     do_automock(attrs, input).into()
 }
 
+/// Mock every trait defined in an automocked module, placing each mock struct
+/// in the module alongside its trait.  Traits that are already automocked on
+/// their own are skipped.
+///
+/// Returns true if the module was modified.
+fn mock_module_traits(item_mod: &mut ItemMod) -> bool {
+    let content = match &mut item_mod.content {
+        Some((_, content)) => content,
+        None => return false
+    };
+    let is_automock = |attr: &Attribute| {
+        if attr.path.is_ident("cfg_attr") {
+            attr.tokens.to_string().contains("automock")
+        } else {
+            attr.path.segments.last().unwrap().ident == "automock"
+        }
+    };
+    let mocks = content.iter()
+        .filter_map(|item| match item {
+            Item::Trait(item_trait)
+                if !item_trait.attrs.iter().any(is_automock) =>
+            {
+                let mockable = (Attrs::default(),
+                                Item::Trait(item_trait.clone()));
+                Some(Item::Verbatim(mock_it(mockable)))
+            },
+            _ => None
+        }).collect::<Vec<_>>();
+    let modified = !mocks.is_empty();
+    content.extend(mocks);
+    modified
+}
+
 fn do_automock_once(attrs: TokenStream, input: TokenStream) -> TokenStream {
     let mut output = input.clone();
     let attrs: Attrs = match parse2(attrs) {
@@ -1263,6 +1529,23 @@ fn do_automock_once(attrs: TokenStream, input: TokenStream) -> TokenStream {
             return err.to_compile_error();
         }
     };
+    if let Item::Mod(item_mod) = &item {
+        let mut item_mod = item_mod.clone();
+        if mock_module_traits(&mut item_mod) {
+            output = item_mod.into_token_stream();
+        }
+    }
+    if !attrs.forwards.is_empty() {
+        if let Item::Trait(item_trait) = &item {
+            let mock_ident = attrs.name.clone()
+                .unwrap_or_else(|| gen_mock_ident(&item_trait.ident));
+            output.extend(forward::forward_impls(&attrs.forwards, &mock_ident,
+                                                 item_trait));
+        } else {
+            compile_error(item.span(),
+                "forward can only be used when mocking traits");
+        }
+    }
     output.extend(mock_it((attrs, item)));
     output
 }
@@ -1406,6 +1689,38 @@ mod automock {
         assert_contains(&output, quote!(pub ( in super :: x ) fn expect_bean));
     }
 
+    #[test]
+    fn expect_vis() {
+        let code = r#"
+        impl Foo {
+            pub fn foo(&self) {}
+            pub fn bar() {}
+        }"#;
+        let ts = proc_macro2::TokenStream::from_str(code).unwrap();
+        let attrs_ts = proc_macro2::TokenStream::from_str(
+            "expect_vis(pub(crate))").unwrap();
+        let output = do_automock(attrs_ts, ts).to_string();
+        assert_contains(&output, quote!(pub fn foo));
+        assert_contains(&output, quote!(pub ( crate ) fn expect_foo));
+        assert_contains(&output, quote!(pub fn bar));
+        assert_contains(&output, quote!(pub ( crate ) fn bar_context));
+    }
+
+    #[test]
+    fn expect_vis_trait() {
+        let code = r#"
+        pub trait Foo {
+            fn foo(&self);
+        }"#;
+        let ts = proc_macro2::TokenStream::from_str(code).unwrap();
+        let attrs_ts = proc_macro2::TokenStream::from_str(
+            "expect_vis(pub(crate))").unwrap();
+        let output = do_automock(attrs_ts, ts).to_string();
+        assert_contains(&output, quote!(pub struct MockFoo));
+        assert_contains(&output, quote!(pub ( crate ) fn expect_foo));
+        assert_not_contains(&output, quote!(pub fn expect_foo));
+    }
+
     #[test]
     #[should_panic(expected = "can only mock inline modules")]
     fn external_module() {
@@ -1472,6 +1787,15 @@ mod concretize_args {
         );
     }
 
+    #[test]
+    fn multi_bounds_auto_trait() {
+        check_concretize(
+            quote!(fn foo<P: AsRef<Path> + Send>(p: P)),
+            &[quote!(p: &(dyn AsRef<Path> + Send))],
+            &[quote!(&p)]
+        );
+    }
+
     #[test]
     fn mutable_reference_arg() {
         check_concretize(
@@ -1528,6 +1852,88 @@ mod concretize_args {
     }
 }
 
+mod deimplify_args {
+    use super::*;
+
+    fn check_deimplify_args(orig_ts: TokenStream, expected_ts: TokenStream,
+                            expected_params: &[&str])
+    {
+        let mut orig: Signature = parse2(orig_ts).unwrap();
+        let expected: Signature = parse2(expected_ts).unwrap();
+        let params = deimplify_args(&mut orig);
+        assert_eq!(quote!(#orig).to_string(), quote!(#expected).to_string());
+        assert_eq!(params, expected_params);
+    }
+
+    #[test]
+    fn generic() {
+        check_deimplify_args(
+            quote!(fn log<T: Display>(&self, msg: impl Into<String>, ctx: T)),
+            quote!(fn log<T: Display, MockallImpl0: Into<String>>(&self,
+                msg: MockallImpl0, ctx: T)),
+            &["MockallImpl0"]
+        );
+    }
+
+    #[test]
+    fn no_impl_trait() {
+        check_deimplify_args(
+            quote!(fn foo(&self, x: u32)),
+            quote!(fn foo(&self, x: u32)),
+            &[]
+        );
+    }
+
+    #[test]
+    fn non_generic() {
+        check_deimplify_args(
+            quote!(fn foo(x: impl Foo + Send, y: impl Bar)),
+            quote!(fn foo<MockallImpl0: Foo + Send, MockallImpl1: Bar>(
+                x: MockallImpl0, y: MockallImpl1)),
+            &["MockallImpl0", "MockallImpl1"]
+        );
+    }
+}
+
+mod name_anonymous_lifetimes {
+    use super::*;
+
+    fn check_name_anonymous_lifetimes(orig_ts: TokenStream,
+                                      expected_ts: TokenStream)
+    {
+        let mut orig: Signature = parse2(orig_ts).unwrap();
+        let expected: Signature = parse2(expected_ts).unwrap();
+        name_anonymous_lifetimes(&mut orig);
+        assert_eq!(quote!(#orig).to_string(), quote!(#expected).to_string());
+    }
+
+    #[test]
+    fn formatter() {
+        check_name_anonymous_lifetimes(
+            quote!(fn fmt(&self, f: &mut Formatter<'_>) -> Result),
+            quote!(fn fmt<'__mockall_anon0>(&self,
+                f: &mut Formatter<'__mockall_anon0>) -> Result)
+        );
+    }
+
+    #[test]
+    fn generic() {
+        check_name_anonymous_lifetimes(
+            quote!(fn foo<'a, T>(&self, x: Foo<'_, 'a, T>, y: &Bar<'_>)),
+            quote!(fn foo<'__mockall_anon0, '__mockall_anon1, 'a, T>(&self,
+                x: Foo<'__mockall_anon0, 'a, T>, y: &Bar<'__mockall_anon1>))
+        );
+    }
+
+    #[test]
+    fn named() {
+        check_name_anonymous_lifetimes(
+            quote!(fn foo<'a>(&self, x: &'a u32) -> &'_ u32),
+            quote!(fn foo<'a>(&self, x: &'a u32) -> &'_ u32)
+        );
+    }
+}
+
 mod deimplify {
     use super::*;
 
@@ -1683,6 +2089,30 @@ mod dewhereselfify {
     }
 }
 
+mod is_fake {
+    use super::*;
+
+    #[test]
+    fn bodiless() {
+        let meth: ImplItemMethod = parse2(quote!(fn foo(&self);)).unwrap();
+        assert!(!is_fake(&meth));
+    }
+
+    #[test]
+    fn empty_body() {
+        let meth: ImplItemMethod = parse2(quote!(fn foo(&self) {})).unwrap();
+        assert!(is_fake(&meth));
+    }
+
+    #[test]
+    fn with_body() {
+        let meth: ImplItemMethod = parse2(quote!(
+                fn foo(&self) -> u32 { 42 }
+        )).unwrap();
+        assert!(is_fake(&meth));
+    }
+}
+
 mod gen_keyid {
     use super::*;
 
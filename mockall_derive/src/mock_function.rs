@@ -152,11 +152,14 @@ pub(crate) struct Builder<'a> {
     attrs: &'a [Attribute],
     call_levels: Option<usize>,
     concretize: bool,
+    expect_vis: Option<&'a Visibility>,
+    format: bool,
     levels: usize,
     parent: Option<&'a Ident>,
     sig: &'a Signature,
     struct_: Option<&'a Ident>,
     struct_generics: Option<&'a Generics>,
+    thread_local: bool,
     trait_: Option<&'a Ident>,
     vis: &'a Visibility
 }
@@ -183,11 +186,15 @@ impl<'a> Builder<'a> {
         let mut predty = Vec::new();
         let mut refpredty = Vec::new();
 
+        let mut deimplified_sig = self.sig.clone();
+        let impl_params = deimplify_args(&mut deimplified_sig);
+        name_anonymous_lifetimes(&mut deimplified_sig);
         let (mut declosured_generics, declosured_inputs, call_exprs) =
             if self.concretize {
-                concretize_args(&self.sig.generics, &self.sig.inputs)
+                concretize_args(&deimplified_sig.generics,
+                                &deimplified_sig.inputs)
             } else {
-                declosurefy(&self.sig.generics, &self.sig.inputs)
+                declosurefy(&deimplified_sig.generics, &deimplified_sig.inputs)
             };
         // TODO: make concretize and declosurefy work for the same function
 
@@ -227,7 +234,20 @@ impl<'a> Builder<'a> {
                 is_static = false;
             }
         }
-        let (output, boxed) = match self.sig.output {
+        // A reference borrowed from an argument, rather than from self, can be
+        // returned by an ordinary returning closure.
+        let arg_lifetimes = declosured_inputs.iter()
+            .filter_map(|fa| match fa {
+                FnArg::Typed(pt) if !pat_is_self(&pt.pat) =>
+                    Some(find_lifetimes(&pt.ty)),
+                _ => None
+            }).flatten()
+            .collect::<HashSet<_>>();
+        let from_arg = matches!(&self.sig.output,
+            ReturnType::Type(_, ty) if matches!(ty.as_ref(),
+                Type::Reference(TypeReference{lifetime: Some(lt), ..})
+                    if arg_lifetimes.contains(lt)));
+        let (mut output, boxed) = match self.sig.output {
             ReturnType::Default => (
                 Type::Tuple(TypeTuple {
                     paren_token: token::Paren::default(),
@@ -237,7 +257,9 @@ impl<'a> Builder<'a> {
             ),
             ReturnType::Type(_, ref ty) => {
                 let mut output_ty = supersuperfy(ty, self.levels);
-                destrify(&mut output_ty);
+                if !from_arg {
+                    destrify(&mut output_ty);
+                }
                 let boxed = dedynify(&mut output_ty);
                 (output_ty, boxed)
             }
@@ -246,14 +268,19 @@ impl<'a> Builder<'a> {
         let owned_output = ownify(&output);
         let mut return_ref = false;
         let mut return_refmut = false;
-        if let Type::Reference(ref tr) = &output {
-            if tr.lifetime.as_ref().map_or(true, |lt| lt.ident != "static")
+        if let Type::Reference(ref mut tr) = &mut output {
+            if !from_arg &&
+                tr.lifetime.as_ref().map_or(true, |lt| lt.ident != "static")
             {
                 if tr.mutability.is_none() {
                     return_ref = true;
                 } else {
                     return_refmut = true;
                 }
+                // The returned reference borrows from the Expectation, so
+                // whatever its lifetime is named, it can live no longer than
+                // self.
+                tr.lifetime = None;
             }
         };
         if is_static && (return_ref || return_refmut) {
@@ -291,6 +318,9 @@ impl<'a> Builder<'a> {
             .map(|tp| tp.ident.clone())
             .collect();
         let call_levels = self.call_levels.unwrap_or(self.levels);
+        let call_vis = expectation_visibility(self.vis, call_levels);
+        let expect_vis = self.expect_vis.cloned()
+            .unwrap_or_else(|| call_vis.clone());
 
         MockFunction {
             alifetimes,
@@ -299,11 +329,15 @@ impl<'a> Builder<'a> {
             attrs: self.attrs.to_vec(),
             call_exprs,
             call_generics,
-            call_vis: expectation_visibility(self.vis, call_levels),
+            call_vis,
             concretize: self.concretize,
             egenerics,
             cgenerics,
+            expect_name: self.sig.ident.clone(),
+            expect_vis,
             fn_params,
+            format: self.format,
+            impl_params,
             is_static,
             mod_ident: self.parent.unwrap_or(&Ident::new("FIXME", Span::call_site())).clone(),
             output,
@@ -315,8 +349,10 @@ impl<'a> Builder<'a> {
             return_ref,
             return_refmut,
             sig: self.sig.clone(),
+            spy_obj: None,
             struct_: self.struct_.cloned(),
             struct_generics,
+            thread_local: self.thread_local,
             trait_: self.trait_.cloned(),
             type_generics,
             privmod_vis: expectation_visibility(self.vis, self.levels)
@@ -330,6 +366,21 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Visibility of the `expect_*` and `*_context` methods, if it should
+    /// differ from the visibility of the mock function itself.
+    pub fn expect_vis(&mut self, vis: Option<&'a Visibility>) -> &mut Self {
+        self.expect_vis = vis;
+        self
+    }
+
+    /// Mock a formatting trait's `fmt` method, declared as `fn fmt(&self) ->
+    /// String`.  The mock method will have the trait's real signature, and
+    /// will write the expectation's `String` to the `Formatter`.
+    pub fn format(&mut self, format: bool) -> &mut Self {
+        self.format = format;
+        self
+    }
+
     /// How many levels of modules beneath the original function this one's
     /// private module is nested.
     pub fn levels(&mut self, levels: usize) -> &mut Self {
@@ -345,12 +396,15 @@ impl<'a> Builder<'a> {
         Builder {
             attrs: &[],
             concretize: false,
+            expect_vis: None,
+            format: false,
             levels: 0,
             call_levels: None,
             parent: None,
             sig,
             struct_: None,
             struct_generics: None,
+            thread_local: false,
             trait_: None,
             vis
         }
@@ -374,6 +428,13 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Give each thread its own expectations for static methods, instead of
+    /// sharing them through a global Mutex.
+    pub fn thread_local(&mut self, thread_local: bool) -> &mut Self {
+        self.thread_local = thread_local;
+        self
+    }
+
     /// Supply the name of the method's trait, if any
     pub fn trait_(&mut self, ident: &'a Ident) -> &mut Self {
         self.trait_ = Some(ident);
@@ -404,8 +465,20 @@ pub(crate) struct MockFunction {
     egenerics: Generics,
     /// Generics of the Common object
     cgenerics: Generics,
+    /// Name used for the `expect_*` and `*_context` methods.  Usually the same
+    /// as the function's name, but qualified by the trait's name if another
+    /// method of the mock struct has the same name.
+    expect_name: Ident,
+    /// Visibility of the `expect_*` and `*_context` methods
+    expect_vis: Visibility,
     /// The mock function's generic types as a list of types
     fn_params: Vec<Ident>,
+    /// Is this a formatting trait's `fmt` method, whose expectation returns a
+    /// `String`?
+    format: bool,
+    /// Generic types that were created from "impl trait" arguments.  They
+    /// can't be named at the call site, so they must be inferred.
+    impl_params: Vec<Ident>,
     /// Is this for a static method or free function?
     is_static: bool,
     /// name of the function's parent module
@@ -432,10 +505,15 @@ pub(crate) struct MockFunction {
     refpredty: Vec<Type>,
     /// The signature of the mockable function
     sig: Signature,
+    /// The mock struct's field that holds this method's spy expectation, if
+    /// calls can be forwarded to a spy's real object
+    spy_obj: Option<Ident>,
     /// Name of the parent structure, if any
     struct_: Option<Ident>,
     /// Generics of the parent structure
     struct_generics: Generics,
+    /// Does each thread get its own expectations for this static method?
+    thread_local: bool,
     /// Name of this method's trait, if the method comes from a trait
     trait_: Option<Ident>,
     /// Type generics of the mock structure
@@ -453,19 +531,44 @@ impl MockFunction {
     // Supplying modname is an unfortunately hack.  Ideally MockFunction
     // wouldn't need to know that.
     pub fn call(&self, modname: Option<&Ident>) -> impl ToTokens {
-        let attrs = AttrFormatter::new(&self.attrs).format();
+        // Trait impl methods inherit these from the trait's declaration
+        let attrs = AttrFormatter::new(&self.attrs)
+            .fn_only(self.trait_.is_none())
+            .format();
         let call_exprs = &self.call_exprs;
-        let (_, tg, _) = if self.is_method_generic() || self.is_static() {
+        let generics = if self.is_method_generic() || self.is_static() {
             &self.egenerics
         } else {
             &self.call_generics
-        }.split_for_impl();
-        let tbf = tg.as_turbofish();
+        };
+        let tbf = if self.impl_params.is_empty() {
+            let (_, tg, _) = generics.split_for_impl();
+            tg.as_turbofish().to_token_stream()
+        } else {
+            let params = generics.params.iter()
+                .map(|gp| match gp {
+                    GenericParam::Type(tp)
+                        if self.impl_params.contains(&tp.ident) => quote!(_),
+                    GenericParam::Type(tp) => tp.ident.to_token_stream(),
+                    GenericParam::Lifetime(ld) =>
+                        ld.lifetime.to_token_stream(),
+                    GenericParam::Const(cp) => cp.ident.to_token_stream()
+                });
+            quote!(::<#(#params),*>)
+        };
         let name = self.name();
         let desc = self.desc();
         let no_match_msg = quote!(std::format!(
             "{}: No matching expectation found", #desc));
-        let sig = &self.sig;
+        let sig = self.call_sig();
+        let call_output = match &sig.output {
+            ReturnType::Default => Some(quote!(())),
+            ReturnType::Type(_, ty) => match ty.as_ref() {
+                // impl Trait types don't implement Default
+                Type::ImplTrait(_) => None,
+                ty => Some(ty.to_token_stream())
+            }
+        };
         let (vis, dead_code) = if self.trait_.is_some() {
             (&Visibility::Inherited, quote!())
         } else {
@@ -491,6 +594,32 @@ impl MockFunction {
         } else {
             Ident::new("call", Span::call_site())
         };
+        // Expectations can explain why nothing matched.  But nice and
+        // recording mocks return the default value instead, if they can,
+        // unless the call matched an expectation that was already saturated.
+        let expect = match call_output {
+            Some(output) if !self.is_static && !self.return_ref &&
+                !self.return_refmut => quote!(.unwrap_or_else(|__mockall_m| {
+                use ::mockall::{ReturnDefault, ReturnNoDefault};
+                if !__mockall_m.saturated && (self.__mockall_nice ||
+                    self.__mockall_unexpected.is_recording())
+                {
+                    if let Some(__mockall_d) =
+                        (&&::mockall::DefaultReturner::<#output>::new())
+                        .maybe_return_default()
+                    {
+                        self.__mockall_unexpected.record(|| std::format!(
+                            "{}{}", no_match_msg, __mockall_m));
+                        return __mockall_d;
+                    }
+                }
+                ::mockall::fail_call(
+                    std::format!("{}{}", no_match_msg, __mockall_m))
+            })),
+            _ => quote!(.unwrap_or_else(|__mockall_m|
+                ::mockall::fail_call(
+                    std::format!("{}{}", no_match_msg, __mockall_m))))
+        };
         let mut deref = quote!();
         if self.boxed {
             if self.return_ref {
@@ -499,26 +628,49 @@ impl MockFunction {
                 deref = quote!(&mut **);
             }
         }
-        if self.is_static {
+        let body = if self.is_static {
             let outer_mod_path = self.outer_mod_path(modname);
             quote!(
-                // Don't add a doc string.  The original is included in #attrs
+                let no_match_msg = #no_match_msg;
+                #deref {
+                    let __mockall_guard = #outer_mod_path::EXPECTATIONS
+                        .lock()
+                        .unwrap_or_else(::std::sync::PoisonError::into_inner);
+                    /*
+                     * TODO: catch panics, then gracefully release the mutex
+                     * so it won't be poisoned.  This requires bounding any
+                     * generic parameters with UnwindSafe
+                     */
+                    /* std::panic::catch_unwind(|| */
+                    __mockall_guard.#call#tbf(#(#call_exprs,)*)
+                    /*)*/
+                }#expect
+            )
+        } else if let Some(spy_obj) = &self.spy_obj {
+            quote!(
+                let no_match_msg = #no_match_msg;
+                self.#substruct_obj #name.call_spy#tbf(
+                    &self.#spy_obj.#name, #(#call_exprs,)*)
+                #expect
+            )
+        } else {
+            quote!(
+                let no_match_msg = #no_match_msg;
+                #deref self.#substruct_obj #name.#call#tbf(#(#call_exprs,)*)
+                #expect
+            )
+        };
+        if self.format {
+            // The body may return early, so run it in a closure
+            quote!(
                 #(#attrs)*
-                #dead_code
-                #vis #sig {
-                    let no_match_msg = #no_match_msg;
-                    #deref {
-                        let __mockall_guard = #outer_mod_path::EXPECTATIONS
-                            .lock().unwrap();
-                        /*
-                         * TODO: catch panics, then gracefully release the mutex
-                         * so it won't be poisoned.  This requires bounding any
-                         * generic parameters with UnwindSafe
-                         */
-                        /* std::panic::catch_unwind(|| */
-                        __mockall_guard.#call#tbf(#(#call_exprs,)*)
-                        /*)*/
-                    }.expect(&no_match_msg)
+                fn fmt(&self, __mockall_f: &mut ::std::fmt::Formatter<'_>)
+                    -> ::std::fmt::Result
+                {
+                    let __mockall_s: ::std::string::String = (|| {
+                        #body
+                    })();
+                    __mockall_f.write_str(&__mockall_s)
                 }
             )
         } else {
@@ -527,11 +679,8 @@ impl MockFunction {
                 #(#attrs)*
                 #dead_code
                 #vis #sig {
-                    let no_match_msg = #no_match_msg;
-                    #deref self.#substruct_obj #name.#call#tbf(#(#call_exprs,)*)
-                    .expect(&no_match_msg)
+                    #body
                 }
-
             )
         }
     }
@@ -547,7 +696,7 @@ impl MockFunction {
                 #(#attrs)*
                 {
                     let __mockall_timeses = #inner_mod_ident::EXPECTATIONS.lock()
-                        .unwrap()
+                        .unwrap_or_else(::std::sync::PoisonError::into_inner)
                         .checkpoint()
                         .collect::<Vec<_>>();
                 }
@@ -558,6 +707,24 @@ impl MockFunction {
         }
     }
 
+    /// Return this method's contribution to its parent's in_scenario method
+    pub fn in_scenario(&self) -> impl ToTokens {
+        let attrs = AttrFormatter::new(&self.attrs)
+            .doc(false)
+            .format();
+        let name = &self.name();
+        quote!(#(#attrs)* { self.#name.in_scenario(__mockall_scenario); })
+    }
+
+    /// Return this method's contribution to its parent's try_verify method
+    pub fn try_verify(&self) -> impl ToTokens {
+        let attrs = AttrFormatter::new(&self.attrs)
+            .doc(false)
+            .format();
+        let name = &self.name();
+        quote!(#(#attrs)* { self.#name.try_verify(__mockall_errors); })
+    }
+
     /// Return a function that creates a Context object for this function
     ///
     /// # Arguments
@@ -573,10 +740,10 @@ impl MockFunction {
             modname.map(|m| format!("{}/", m)).unwrap_or_default(),
             self.inner_mod_ident(),
             self.name());
-        let context_ident = format_ident!("{}_context", self.name());
+        let context_ident = format_ident!("{}_context", self.expect_name);
         let (_, tg, _) = self.type_generics.split_for_impl();
         let outer_mod_path = self.outer_mod_path(modname);
-        let v = &self.call_vis;
+        let v = &self.expect_vis;
         quote!(
             #(#attrs)*
             #[doc = #context_docstr]
@@ -587,6 +754,61 @@ impl MockFunction {
         )
     }
 
+    /// Return a function that checkpoints this static method's expectations,
+    /// for use without a Context object.
+    ///
+    /// # Arguments
+    ///
+    /// * `modname`:    Name of the parent struct's private module
+    pub fn checkpoint_fn(&self, modname: Option<&Ident>) -> impl ToTokens {
+        let attrs = AttrFormatter::new(&self.attrs)
+            .doc(false)
+            .format();
+        let docstr = format!("Verify that all current expectations for the `{}` method are satisfied and clear them.",
+            self.name());
+        let checkpoint_ident = format_ident!("{}_checkpoint", self.expect_name);
+        let (_, tg, _) = self.type_generics.split_for_impl();
+        let tbf = tg.as_turbofish();
+        let outer_mod_path = self.outer_mod_path(modname);
+        let v = &self.expect_vis;
+        quote!(
+            #(#attrs)*
+            #[doc = #docstr]
+            #v fn #checkpoint_ident() {
+                #outer_mod_path::Context #tbf ::do_checkpoint()
+            }
+        )
+    }
+
+    /// The signature of the mock function itself.
+    ///
+    /// Only `extern "C"` functions may be C-variadic, and defining one
+    /// requires the unstable `c_variadic` feature.  So without the "nightly"
+    /// feature, a mock variadic function takes only the fixed arguments.
+    /// Either way, its expectation ignores the variadic arguments.
+    fn call_sig(&self) -> Signature {
+        let mut sig = self.sig.clone();
+        if let Some(variadic) = sig.variadic.take() {
+            cfg_if! {
+                if #[cfg(feature = "nightly_derive")] {
+                    sig.abi = Some(parse2(quote!(extern "C")).unwrap());
+                    sig.inputs.push(FnArg::Typed(PatType {
+                        attrs: variadic.attrs,
+                        pat: Box::new(Pat::Wild(PatWild {
+                            attrs: Vec::new(),
+                            underscore_token: Token![_](Span::call_site())
+                        })),
+                        colon_token: Token![:](Span::call_site()),
+                        ty: Box::new(Type::Verbatim(quote!(...)))
+                    }));
+                } else {
+                    let _ = variadic;
+                }
+            }
+        }
+        sig
+    }
+
     /// Generate a code fragment that will print a description of the invocation
     fn desc(&self) -> impl ToTokens {
         let argnames = &self.argnames;
@@ -595,9 +817,17 @@ impl MockFunction {
         } else {
             format!("{}::{}", self.mod_ident, self.sig.ident)
         };
-        let fields = vec!["{:?}"; argnames.len()].join(", ");
+        let fields = vec!["{}"; argnames.len()].join(", ");
         let fstr = format!("{}({})", name, fields);
-        quote!(std::format!(#fstr, #(::mockall::MaybeDebugger(&#argnames)),*))
+        if argnames.is_empty() {
+            quote!(std::format!(#fstr))
+        } else {
+            quote!({
+                use ::mockall::{ArgDebug, ArgNoDebug};
+                std::format!(#fstr,
+                    #((&&::mockall::ArgPrinter(&#argnames)).debug_string()),*)
+            })
+        }
     }
 
     /// Generate code for the expect_ method
@@ -617,7 +847,7 @@ impl MockFunction {
             .doc(false)
             .format();
         let name = self.name();
-        let expect_ident = format_ident!("expect_{}", &name);
+        let expect_ident = format_ident!("expect_{}", &self.expect_name);
         let expectation_obj = self.expectation_obj(self_args);
         let funcname = &self.sig.ident;
         let (_, tg, _) = if self.is_method_generic() {
@@ -633,12 +863,17 @@ impl MockFunction {
             send_syncify(&mut wc, self.owned_output.clone());
         }
         let tbf = tg.as_turbofish();
-        let vis = &self.call_vis;
+        let vis = &self.expect_vis;
 
         #[cfg(not(feature = "nightly_derive"))]
-        let must_use = quote!(#[must_use =
-                "Must set return value when not using the \"nightly\" feature"
-            ]);
+        let must_use = if self.returns_unit() {
+            // Methods that return () don't need an explicit return value
+            quote!()
+        } else {
+            quote!(#[must_use =
+                "Must set return value unless its type is known to impl Default"
+            ])
+        };
         #[cfg(feature = "nightly_derive")]
         let must_use = quote!();
 
@@ -650,6 +885,26 @@ impl MockFunction {
         };
         let docstr = format!("Create an [`Expectation`]({}/{}/struct.Expectation.html) for mocking the `{}` method",
             modname, self.inner_mod_ident(), funcname);
+        let pending = if self.has_pending() {
+            let pending_ident = format_ident!("expect_{}_pending",
+                                              &self.expect_name);
+            let pending_obj = self.pending_obj(self_args);
+            let docstr = format!("Create a [`PendingExpectation`]({}/{}/struct.PendingExpectation.html) for mocking the `{}` method.  Unlike [`{}`](#method.{}), the new expectation can't take effect until it has a return value.",
+                modname, self.inner_mod_ident(), funcname, expect_ident,
+                expect_ident);
+            quote!(
+                #[doc = #docstr]
+                #(#attrs)*
+                #vis fn #pending_ident #ig(&mut self)
+                   -> #modname::#pending_obj
+                   #wc
+                {
+                    self.#substruct_obj #name.expect_pending#tbf()
+                }
+            )
+        } else {
+            quote!()
+        };
         quote!(
             #must_use
             #[doc = #docstr]
@@ -660,9 +915,52 @@ impl MockFunction {
             {
                 self.#substruct_obj #name.expect#tbf()
             }
+            #pending
         )
     }
 
+    /// Generate the mock builder's method for setting an expectation on this
+    /// method.  Static methods have none.
+    pub fn builder_step(&self, modname: &Ident,
+                        self_args: Option<&PathArguments>) -> impl ToTokens
+    {
+        if self.is_static() {
+            return TokenStream::new();
+        }
+        let attrs = AttrFormatter::new(&self.attrs)
+            .doc(false)
+            .format();
+        let expect_ident = format_ident!("expect_{}", &self.expect_name);
+        let expectation_obj = self.expectation_obj(self_args);
+        let mut generics = self.call_generics.clone();
+        generics.params.push(parse2(quote!(
+            __MockallF: for<'__mockall_e>
+                FnOnce(&'__mockall_e mut #modname::#expectation_obj)
+                -> &'__mockall_e mut #modname::#expectation_obj
+        )).unwrap());
+        let (ig, _, wc) = generics.split_for_impl();
+        let mut wc = wc.cloned();
+        if self.is_method_generic() && (self.return_ref || self.return_refmut) {
+            send_syncify(&mut wc, self.owned_output.clone());
+        }
+        let vis = &self.expect_vis;
+        let struct_ = self.struct_.as_ref()
+            .expect("builder_step is only for mock structs' methods");
+        let docstr = format!("Add an expectation for the `{}` method, configured by `f`.  See [`{}`](struct.{}.html#method.{}).",
+            self.sig.ident, expect_ident, struct_, expect_ident);
+        quote!(
+            #[doc = #docstr]
+            #(#attrs)*
+            #vis fn #expect_ident #ig(mut self, f: __MockallF) -> Self
+               #wc
+            {
+                // Type inference supplies any generic arguments
+                f(self.0.#expect_ident());
+                self
+            }
+        ).into_token_stream()
+    }
+
     /// Return the name of this function's expecation object
     fn expectation_obj(&self, self_args: Option<&PathArguments>)
         -> impl ToTokens
@@ -673,6 +971,11 @@ impl MockFunction {
             // object but not in the self args.  These come from the method's
             // return type.
             let mut abga2 = abga.clone();
+            // The struct's lifetime parameters aren't part of the Expectation
+            // object, except for those used by the return type.
+            abga2.args = abga2.args.into_iter()
+                .filter(|ga| !matches!(ga, GenericArgument::Lifetime(_)))
+                .collect();
             for _ in self.egenerics.lifetimes() {
                 let lt = Lifetime::new("'static", Span::call_site());
                 let la = GenericArgument::Lifetime(lt);
@@ -691,6 +994,48 @@ impl MockFunction {
         }
     }
 
+    /// Return the name of this function's pending expectation object.
+    ///
+    /// Like `expectation_obj`, but with an anonymous lifetime prepended.
+    fn pending_obj(&self, self_args: Option<&PathArguments>)
+        -> impl ToTokens
+    {
+        let inner_mod_ident = self.inner_mod_ident();
+        let anon = GenericArgument::Lifetime(
+            Lifetime::new("'_", Span::call_site())
+        );
+        let mut abga = match self_args {
+            Some(PathArguments::AngleBracketed(abga)) => {
+                let mut abga = abga.clone();
+                abga.args = abga.args.into_iter()
+                    .filter(|ga| !matches!(ga, GenericArgument::Lifetime(_)))
+                    .collect();
+                abga
+            },
+            _ => {
+                let mut abga = AngleBracketedGenericArguments {
+                    colon2_token: None,
+                    lt_token: <Token![<]>::default(),
+                    args: Punctuated::new(),
+                    gt_token: <Token![>]>::default(),
+                };
+                for param in self.egenerics.params.iter() {
+                    let ident = match param {
+                        GenericParam::Type(tp) => &tp.ident,
+                        GenericParam::Const(cp) => &cp.ident,
+                        GenericParam::Lifetime(_) => continue
+                    };
+                    abga.args.push(GenericArgument::Type(
+                        parse2(quote!(#ident)).unwrap()
+                    ));
+                }
+                abga
+            }
+        };
+        abga.args.insert(0, anon);
+        quote!(#inner_mod_ident::PendingExpectation #abga)
+    }
+
     /// Return the name of this function's expecations object
     pub fn expectations_obj(&self) -> impl ToTokens {
         let inner_mod_ident = self.inner_mod_ident();
@@ -719,12 +1064,45 @@ impl MockFunction {
         }
     }
 
+    /// The return type, with any lifetimes that it borrows from the arguments
+    /// replaced by `'static`.  Methods like `return_const` must use this,
+    /// because they can't name the arguments' lifetimes.
+    fn static_output(&self) -> TokenStream {
+        fn rename(ts: TokenStream, lts: &HashSet<&Ident>) -> TokenStream {
+            let mut output = Vec::new();
+            for tt in ts.into_iter() {
+                match tt {
+                    TokenTree::Ident(ident) if lts.contains(&ident) &&
+                        matches!(output.last(),
+                            Some(TokenTree::Punct(p)) if p.as_char() == '\'') =>
+                    {
+                        output.push(TokenTree::Ident(
+                            Ident::new("static", ident.span())));
+                    },
+                    TokenTree::Group(group) => {
+                        let mut new = Group::new(group.delimiter(),
+                            rename(group.stream(), lts));
+                        new.set_span(group.span());
+                        output.push(TokenTree::Group(new));
+                    },
+                    tt => output.push(tt)
+                }
+            }
+            output.into_iter().collect()
+        }
+
+        let lts = self.alifetimes.iter()
+            .map(|ltd| &ltd.lifetime.ident)
+            .collect::<HashSet<_>>();
+        rename(self.output.to_token_stream(), &lts)
+    }
+
     /// Human-readable name of the mock function
     fn funcname(&self) -> String {
         if let Some(si) = &self.struct_ {
             format!("{}::{}", si, self.name())
         } else {
-            format!("{}", self.name())
+            format!("{}::{}", self.mod_ident, self.name())
         }
     }
 
@@ -741,9 +1119,184 @@ impl MockFunction {
         }
     }
 
+    /// Does this function get a [`PendingExpectation`]?
+    ///
+    /// Only simple methods do: those that aren't static or generic, and that
+    /// return a `'static` value.
+    fn has_pending(&self) -> bool {
+        !self.is_static && !self.is_method_generic() && !self.return_ref &&
+            !self.return_refmut && self.egenerics.lifetimes().next().is_none()
+    }
+
+    /// The generic parameters and arguments of the `with_as_ref` method, if
+    /// this function should have one.
+    ///
+    /// Only concretized functions do, and only if at least one argument was
+    /// generic over `AsRef<T>`.  Those arguments are matched by predicates on
+    /// `T`.  Other arguments must not be trait objects.
+    fn with_as_ref_params(&self) -> Option<(TokenStream, TokenStream)> {
+        if !self.concretize {
+            return None;
+        }
+        let mut any_as_ref = false;
+        let pred_types = self.predty.iter()
+            .map(|ty| match ty {
+                Type::TraitObject(tto) if tto.bounds.len() == 1 => {
+                    let tb = match tto.bounds.first() {
+                        Some(TypeParamBound::Trait(tb)) => tb,
+                        _ => return None
+                    };
+                    let seg = tb.path.segments.last().unwrap();
+                    if seg.ident != "AsRef" {
+                        return None;
+                    }
+                    match &seg.arguments {
+                        PathArguments::AngleBracketed(abga) => {
+                            match abga.args.first() {
+                                Some(GenericArgument::Type(t)) => {
+                                    any_as_ref = true;
+                                    Some(t.clone())
+                                },
+                                _ => None
+                            }
+                        },
+                        _ => None
+                    }
+                },
+                Type::Path(_) => Some(ty.clone()),
+                _ => None
+            }).collect::<Option<Vec<_>>>()?;
+        if !any_as_ref {
+            return None;
+        }
+        let generics = pred_types.iter().enumerate()
+            .map(|(i, ty)| {
+                let id = format_ident!("MockallMatcher{}", i);
+                quote!(#id: ::mockall::Predicate<#ty> + Send + 'static, )
+            }).collect::<TokenStream>();
+        let args = self.argnames.iter().enumerate()
+            .map(|(i, argname)| {
+                let id = format_ident!("MockallMatcher{}", i);
+                quote!(#argname: #id, )
+            }).collect::<TokenStream>();
+        Some((generics, args))
+    }
+
+    /// The bounds of the trait object, if this function returns a
+    /// `Box<dyn Trait>`.
+    fn boxed_dyn_bounds(&self) -> Option<TokenStream> {
+        let tp = match &self.output {
+            Type::Path(tp) if tp.qself.is_none() => tp,
+            _ => return None
+        };
+        let seg = tp.path.segments.last().unwrap();
+        if seg.ident != "Box" {
+            return None;
+        }
+        match &seg.arguments {
+            PathArguments::AngleBracketed(abga) if abga.args.len() == 1 => {
+                match abga.args.first() {
+                    Some(GenericArgument::Type(Type::TraitObject(tto))) => {
+                        let bounds = &tto.bounds;
+                        Some(quote!(#bounds))
+                    },
+                    _ => None
+                }
+            },
+            _ => None
+        }
+    }
+
+    /// If the function returns an `Option<T>`, like `Iterator::next`, return
+    /// `T`.
+    fn option_inner(&self) -> Option<&Type> {
+        let tp = match &self.output {
+            Type::Path(tp) if tp.qself.is_none() => tp,
+            _ => return None
+        };
+        let seg = tp.path.segments.last().unwrap();
+        if seg.ident != "Option" {
+            return None;
+        }
+        match &seg.arguments {
+            PathArguments::AngleBracketed(abga) if abga.args.len() == 1 => {
+                match abga.args.first() {
+                    Some(GenericArgument::Type(ty)) => Some(ty),
+                    _ => None
+                }
+            },
+            _ => None
+        }
+    }
+
+    /// If the function returns a `Pin<Box<dyn Future<Output = T>>>`, return
+    /// `T` and the trait object's other, non-lifetime, bounds.
+    fn pinned_future(&self) -> Option<(Type, TokenStream)> {
+        fn single_type_arg(tp: &TypePath, ident: &str) -> Option<Type> {
+            if tp.qself.is_some() {
+                return None;
+            }
+            let seg = tp.path.segments.last().unwrap();
+            if seg.ident != ident {
+                return None;
+            }
+            match &seg.arguments {
+                PathArguments::AngleBracketed(abga) if abga.args.len() == 1 =>
+                {
+                    match abga.args.first() {
+                        Some(GenericArgument::Type(ty)) => Some(ty.clone()),
+                        _ => None
+                    }
+                },
+                _ => None
+            }
+        }
+
+        let boxed = match &self.output {
+            Type::Path(tp) => single_type_arg(tp, "Pin")?,
+            _ => return None
+        };
+        let tto = match single_type_arg(
+            match &boxed {
+                Type::Path(tp) => tp,
+                _ => return None
+            }, "Box")?
+        {
+            Type::TraitObject(tto) => tto,
+            _ => return None
+        };
+        let mut fut_output = None;
+        let mut bounds = Vec::new();
+        for bound in tto.bounds.iter() {
+            if let TypeParamBound::Trait(tb) = bound {
+                let seg = tb.path.segments.last().unwrap();
+                if seg.ident == "Future" {
+                    if let PathArguments::AngleBracketed(abga) = &seg.arguments
+                    {
+                        for ga in abga.args.iter() {
+                            if let GenericArgument::Binding(b) = ga {
+                                if b.ident == "Output" {
+                                    fut_output = Some(b.ty.clone());
+                                }
+                            }
+                        }
+                    }
+                } else {
+                    bounds.push(tb);
+                }
+            }
+        }
+        fut_output.map(|t| (t, quote!(#(+ #bounds)*)))
+    }
+
+    /// Does this function return `()`?
+    fn returns_unit(&self) -> bool {
+        matches!(&self.output, Type::Tuple(tt) if tt.elems.is_empty())
+    }
+
     fn is_expectation_generic(&self) -> bool {
         self.egenerics.params.iter().any(|p| {
-            matches!(p, GenericParam::Type(_))
+            matches!(p, GenericParam::Type(_) | GenericParam::Const(_))
         }) || self.egenerics.where_clause.is_some()
     }
 
@@ -751,7 +1304,7 @@ impl MockFunction {
     /// generic mock struct)?
     pub fn is_method_generic(&self) -> bool {
         self.call_generics.params.iter().any(|p| {
-            matches!(p, GenericParam::Type(_))
+            matches!(p, GenericParam::Type(_) | GenericParam::Const(_))
         }) || self.call_generics.where_clause.is_some()
     }
 
@@ -773,10 +1326,163 @@ impl MockFunction {
         self.is_static
     }
 
+    /// Does the function return a non-'static reference?  If so, its
+    /// expectations must store the referent, and will be `Send` and `Sync`
+    /// only if the referent is.
+    pub fn returns_ref(&self) -> bool {
+        self.return_ref || self.return_refmut
+    }
+
+    /// Can calls to this trait method be forwarded to a real object by a spy?
+    ///
+    /// Only methods that take `&self` or `&mut self`, aren't generic, and
+    /// return owned values that don't mention `Self` can be.
+    pub fn is_spyable(&self) -> bool {
+        // By now, Self has usually been replaced by the mock struct's name
+        let is_self = |i: &Ident| i == "Self" || self.struct_.as_ref() == Some(i);
+        fn mentions<F>(ts: TokenStream, refs: bool, is_self: &F) -> bool
+            where F: Fn(&Ident) -> bool
+        {
+            ts.into_iter().any(|tt| match tt {
+                TokenTree::Group(g) => mentions(g.stream(), refs, is_self),
+                TokenTree::Ident(i) => is_self(&i) ||
+                    (refs && (i == "impl" || i == "dyn")),
+                TokenTree::Punct(p) => refs &&
+                    (p.as_char() == '&' || p.as_char() == '\''),
+                TokenTree::Literal(_) => false
+            })
+        }
+
+        let by_ref = matches!(self.sig.inputs.first(),
+            Some(FnArg::Receiver(r)) if r.reference.is_some());
+        let args_mention_self = self.sig.inputs.iter()
+            .skip(1)
+            .any(|fa| match fa {
+                FnArg::Typed(pt) =>
+                    mentions(pt.ty.to_token_stream(), false, &is_self),
+                FnArg::Receiver(_) => true
+            });
+        // impl Trait outputs have already been converted to Box<dyn Trait>,
+        // which the real method's output can't be coerced to.
+        let output_ok = match &self.sig.output {
+            ReturnType::Default => true,
+            ReturnType::Type(_, ty) =>
+                !mentions(ty.to_token_stream(), true, &is_self)
+        };
+        self.trait_.is_some() && by_ref && output_ok && !args_mention_self &&
+            self.sig.generics.params.is_empty() &&
+            !self.is_expectation_generic() &&
+            !self.concretize &&
+            self.sig.asyncness.is_none() &&
+            self.sig.unsafety.is_none() &&
+            self.sig.variadic.is_none()
+    }
+
+    /// Let a spy forward calls that no expectation matches to its real object,
+    /// using the spy expectation in the mock struct's `spy_obj` field.  Does
+    /// nothing if this method can't be forwarded.
+    pub fn spy(&mut self, spy_obj: &Ident) {
+        if self.is_spyable() {
+            self.spy_obj = Some(spy_obj.clone());
+        }
+    }
+
+    /// Code that makes a spy forward this method to the real object in
+    /// `__mockall_real`, an `Arc<Mutex<__MockallReal>>`, if possible.
+    pub fn spy_init(&self, trait_path: &Path) -> Option<TokenStream> {
+        let spy_obj = self.spy_obj.as_ref()?;
+        let attrs = AttrFormatter::new(&self.attrs)
+            .async_trait(false)
+            .doc(false)
+            .format();
+        let name = self.name();
+        let anames = (0..self.argnames.len())
+            .map(|i| format_ident!("__mockall_a{}", i))
+            .collect::<Vec<_>>();
+        let mutability = match self.sig.inputs.first() {
+            Some(FnArg::Receiver(r)) => r.mutability,
+            _ => None
+        };
+        let call_real = if self.format {
+            // The real fmt method needs a Formatter, so let format! supply one
+            let spec = if trait_path.segments.last().unwrap().ident == "Debug" {
+                "{:?}"
+            } else {
+                "{}"
+            };
+            quote!(::std::format!(#spec, &*__mockall_guard))
+        } else {
+            quote!(<__MockallReal as #trait_path>::#name(
+                & #mutability *__mockall_guard, #(#anames),*))
+        };
+        Some(quote!(
+            #(#attrs)*
+            #[allow(deprecated)]
+            {
+                let __mockall_real = __mockall_real.clone();
+                __mockall_mock.#spy_obj.#name.expect()
+                    .returning(move |#(#anames),*| {
+                        let #mutability __mockall_guard =
+                            __mockall_real.lock().unwrap();
+                        #call_real
+                    });
+            }
+        ))
+    }
+
+    /// The `EXPECTATIONS` static that holds a static method's expectations.
+    ///
+    /// Normally it's a global Mutex.  In thread-local mode, it's a unit struct
+    /// that derefs to a Mutex owned by the current thread, just like the one
+    /// that `lazy_static!` would generate.
+    fn expectations_static(&self, ty: TokenStream) -> TokenStream {
+        let v = &self.privmod_vis;
+        if self.thread_local {
+            quote!(
+                #[allow(non_camel_case_types)]
+                #[doc(hidden)]
+                #v struct EXPECTATIONS {
+                    __private_field: ()
+                }
+                #[doc(hidden)]
+                #v static EXPECTATIONS: EXPECTATIONS =
+                    EXPECTATIONS {__private_field: ()};
+                impl ::std::ops::Deref for EXPECTATIONS {
+                    type Target = ::std::sync::Mutex<#ty>;
+                    fn deref(&self) -> &Self::Target {
+                        ::std::thread_local! {
+                            // Leaked so the guard can outlive the closure.
+                            // Each test thread leaks just one.
+                            static EXPECTATIONS:
+                                &'static ::std::sync::Mutex<#ty> =
+                                Box::leak(Box::new(
+                                    ::std::sync::Mutex::new(<#ty>::new())));
+                        }
+                        EXPECTATIONS.with(|e| *e)
+                    }
+                }
+            )
+        } else {
+            quote!(
+                ::mockall::lazy_static! {
+                    #[doc(hidden)]
+                    #v static ref EXPECTATIONS: ::std::sync::Mutex<#ty> =
+                        ::std::sync::Mutex::new(<#ty>::new());
+                }
+            )
+        }
+    }
+
     pub fn name(&self) -> &Ident {
         &self.sig.ident
     }
 
+    /// Qualify the names of the `expect_*` and `*_context` methods with the
+    /// name of the method's trait, like `expect_Foo_bar`.
+    pub fn qualify_expect_name(&mut self, trait_ident: &Ident) {
+        self.expect_name = format_ident!("{}_{}", trait_ident, self.name());
+    }
+
     /// Generate code for this function's private module
     pub fn priv_module(&self) -> impl ToTokens {
         let attrs = AttrFormatter::new(&self.attrs)
@@ -805,6 +1511,7 @@ impl MockFunction {
             Box::new(ConcreteExpectationGuard{f: self})
         };
         let matcher = &Matcher{f: self};
+        let pending = PendingExpectation{f: self};
         let std_mutexguard = if self.is_static {
             quote!(use ::std::sync::MutexGuard;)
         } else {
@@ -836,6 +1543,7 @@ impl MockFunction {
                 #matcher
                 #common
                 #expectation
+                #pending
                 #expectations
                 #generic_expectations
                 #guard
@@ -867,6 +1575,11 @@ impl<'a> ToTokens for Common<'a> {
             .map(|(id, mt)|
                 quote!(#id: #hrtb ::mockall::Predicate<#mt> + Send + 'static, )
             ).collect::<TokenStream>();
+        let with_st_generics = with_generics_idents.iter()
+            .zip(self.f.predty.iter())
+            .map(|(id, mt)|
+                quote!(#id: #hrtb ::mockall::Predicate<#mt> + 'static, )
+            ).collect::<TokenStream>();
         let with_args = self.f.argnames.iter()
             .zip(with_generics_idents.iter())
             .map(|(argname, id)| quote!(#argname: #id, ))
@@ -885,6 +1598,19 @@ impl<'a> ToTokens for Common<'a> {
                         let mut __mockall_guard = self.matcher.lock().unwrap();
                         *__mockall_guard.deref_mut() =
                             Matcher::Pred(Box::new((#boxed_withargs)));
+                        ::std::mem::drop(__mockall_guard);
+                        self.update_scenario();
+                    }
+
+                fn with_st<#with_st_generics>(&mut self, #with_args)
+                    {
+                        let mut __mockall_guard = self.matcher.lock().unwrap();
+                        *__mockall_guard.deref_mut() =
+                            Matcher::PredSt(::mockall::Fragile::new(
+                                Box::new((#boxed_withargs))
+                            ));
+                        ::std::mem::drop(__mockall_guard);
+                        self.update_scenario();
                     }
             )
         };
@@ -893,7 +1619,13 @@ impl<'a> ToTokens for Common<'a> {
             /// Holds the stuff that is independent of the output type
             struct Common #ig #wc {
                 matcher: Mutex<Matcher #tg>,
-                seq_handle: Option<::mockall::SeqHandle>,
+                /// Run on every call that this expectation handles
+                inspectors: Mutex<Vec<Box<dyn #hrtb FnMut(#(#refpredty, )*)
+                    + Send>>>,
+                /// Expectations with higher priority are checked first
+                priority: i32,
+                seq_handles: ::std::vec::Vec<::mockall::SeqHandle>,
+                scenario_handle: Option<::mockall::ScenarioHandle>,
                 times: ::mockall::Times
             }
 
@@ -902,33 +1634,58 @@ impl<'a> ToTokens for Common<'a> {
                 fn default() -> Self {
                     Common {
                         matcher: Mutex::new(Matcher::default()),
-                        seq_handle: None,
+                        inspectors: Mutex::default(),
+                        priority: 0,
+                        seq_handles: ::std::vec::Vec::new(),
+                        scenario_handle: None,
                         times: ::mockall::Times::default()
                     }
                 }
             }
 
             impl #ig Common #tg #wc {
-                fn call(&self, desc: &str) {
+                #[allow(clippy::ptr_arg)]
+                fn call #lg (&self, __mockall_desc: &str,
+                    #( #argnames: &#predty, )*)
+                {
                     self.times.call()
                         .unwrap_or_else(|m| {
                             let desc = std::format!(
                                 "{}", self.matcher.lock().unwrap());
-                            panic!("{}: Expectation({}) {}", #funcname, desc,
-                                m);
+                            ::mockall::fail_call(std::format!(
+                                "{}: Expectation({}) {}", #funcname, desc, m));
                         });
-                    self.verify_sequence(desc);
-                    if self.times.is_satisfied() {
-                        self.satisfy_sequence()
+                    self.update_scenario();
+                    self.verify_sequence(__mockall_desc);
+                    self.satisfy_sequence();
+                    for __mockall_f in self.inspectors.lock().unwrap().iter_mut()
+                    {
+                        __mockall_f(#(#argnames, )*);
                     }
                 }
 
+                fn inspect<MockallF>(&mut self, __mockall_f: MockallF)
+                    where MockallF: #hrtb FnMut(#( #refpredty, )*)
+                                    + Send + 'static
+                {
+                    self.inspectors.get_mut().unwrap()
+                        .push(Box::new(__mockall_f));
+                }
+
+                fn in_scenario(&mut self,
+                    __mockall_scenario: &mut ::mockall::Scenario)
+                {
+                    self.scenario_handle =
+                        Some(__mockall_scenario.next_handle());
+                    self.update_scenario();
+                }
+
                 fn in_sequence(&mut self, __mockall_seq: &mut ::mockall::Sequence)
                     -> &mut Self
                 {
                     assert!(self.times.is_exact(),
                         "Only Expectations with an exact call count have sequences");
-                    self.seq_handle = Some(__mockall_seq.next_handle());
+                    self.seq_handles.push(__mockall_seq.next_handle());
                     self
                 }
 
@@ -941,23 +1698,73 @@ impl<'a> ToTokens for Common<'a> {
                     self.matcher.lock().unwrap().matches(#(#argnames, )*)
                 }
 
-                /// Forbid this expectation from ever being called.
-                fn never(&mut self) {
-                    self.times.never();
+                #[allow(clippy::ptr_arg)]
+                fn mismatch #lg (&self, #( #argnames: &#predty, )*)
+                    -> Option<::std::string::String>
+                {
+                    let __mockall_matcher = self.matcher.lock().unwrap();
+                    if let Some(__mockall_args) =
+                        __mockall_matcher.mismatch(#(#argnames, )*)
+                    {
+                        Some(std::format!("Expectation({}) rejected {}",
+                            __mockall_matcher, __mockall_args))
+                    } else if !__mockall_matcher.matches(#(#argnames, )*) {
+                        Some(std::format!("Expectation({}) rejected the call",
+                            __mockall_matcher))
+                    } else if self.times.is_done() {
+                        Some(std::format!(
+                            "Expectation({}) was already called {} times",
+                            __mockall_matcher, self.times.count()))
+                    } else {
+                        None
+                    }
                 }
 
-                fn satisfy_sequence(&self) {
-                    if let Some(__mockall_handle) = &self.seq_handle {
-                        __mockall_handle.satisfy()
-                    }
+                fn at_least(&mut self, __mockall_n: usize) {
+                    self.times.at_least(__mockall_n);
+                    self.update_scenario();
                 }
 
-                /// Expect this expectation to be called any number of times
-                /// contained with the given range.
+                fn at_most(&mut self, __mockall_n: usize) {
+                    self.times.at_most(__mockall_n);
+                    self.update_scenario();
+                }
+
+                /// Handle any call that nothing else does, as often as needed.
+                fn by_default(&mut self) {
+                    self.priority = i32::MIN;
+                    self.times(..);
+                }
+
+                /// Forbid this expectation from ever being called.
+                fn never(&mut self) {
+                    self.times.never();
+                    self.update_scenario();
+                }
+
+                fn satisfy_sequence(&self) {
+                    for __mockall_handle in self.seq_handles.iter() {
+                        __mockall_handle.called(self.times.is_satisfied())
+                    }
+                }
+
+                /// Expect this expectation to be called any number of times
+                /// contained with the given range.
                 fn times<MockallR>(&mut self, __mockall_r: MockallR)
                     where MockallR: Into<::mockall::TimesRange>
                 {
-                    self.times.times(__mockall_r)
+                    self.times.times(__mockall_r);
+                    self.update_scenario();
+                }
+
+                /// Tell the Scenario, if any, about this expectation's current
+                /// matcher and call count.
+                fn update_scenario(&self) {
+                    if let Some(__mockall_handle) = &self.scenario_handle {
+                        let desc = std::format!("{}: Expectation({})",
+                            #funcname, self.matcher.lock().unwrap());
+                        __mockall_handle.update(desc, &self.times);
+                    }
                 }
 
                 #with_method
@@ -969,6 +1776,8 @@ impl<'a> ToTokens for Common<'a> {
                     let mut __mockall_guard = self.matcher.lock().unwrap();
                     *__mockall_guard.deref_mut() =
                          Matcher::Func(Box::new(__mockall_f));
+                    ::std::mem::drop(__mockall_guard);
+                    self.update_scenario();
                 }
 
                 fn withf_st<MockallF>(&mut self, __mockall_f: MockallF)
@@ -980,10 +1789,32 @@ impl<'a> ToTokens for Common<'a> {
                          Matcher::FuncSt(
                              ::mockall::Fragile::new(Box::new(__mockall_f))
                         );
+                    ::std::mem::drop(__mockall_guard);
+                    self.update_scenario();
+                }
+
+                /// Describe this expectation if it hasn't yet been called
+                /// enough times.  Expectations in a Scenario are verified by
+                /// the Scenario instead.
+                fn unsatisfied(&self) -> Option<::mockall::ExpectationError> {
+                    if self.scenario_handle.is_none() &&
+                        !self.times.is_satisfied()
+                    {
+                        // This may run while unwinding from a panic in a
+                        // matcher, so it mustn't panic on a poisoned Mutex.
+                        let desc = std::format!("{}: Expectation({})",
+                            #funcname,
+                            self.matcher.lock().unwrap_or_else(
+                                ::std::sync::PoisonError::into_inner));
+                        Some(::mockall::ExpectationError::new(desc,
+                                                              &self.times))
+                    } else {
+                        None
+                    }
                 }
 
                 fn verify_sequence(&self, desc: &str) {
-                    if let Some(__mockall_handle) = &self.seq_handle {
+                    for __mockall_handle in self.seq_handles.iter() {
                         __mockall_handle.verify(desc)
                     }
                 }
@@ -991,15 +1822,9 @@ impl<'a> ToTokens for Common<'a> {
 
             impl #ig Drop for Common #tg #wc {
                 fn drop(&mut self) {
-                    if !::std::thread::panicking() && !self.times.is_satisfied()
-                    {
-                        let desc = std::format!(
-                            "{}", self.matcher.lock().unwrap());
-                        panic!("{}: Expectation({}) called {} time(s) which is fewer than expected {}",
-                               #funcname,
-                               desc,
-                               self.times.count(),
-                               self.times.minimum());
+                    if let Some(__mockall_e) = self.unsatisfied() {
+                        ::mockall::fail_verification(
+                            std::format!("{}", __mockall_e));
                     }
                 }
             }
@@ -1026,11 +1851,88 @@ impl<'a> ToTokens for CommonExpectationMethods<'a> {
             .map(|(id, mt)|
                 quote!(#id: #hrtb ::mockall::Predicate<#mt> + Send + 'static, )
             ).collect::<TokenStream>();
+        let with_st_generics = with_generics_idents.iter()
+            .zip(self.f.predty.iter())
+            .map(|(id, mt)|
+                quote!(#id: #hrtb ::mockall::Predicate<#mt> + 'static, )
+            ).collect::<TokenStream>();
         let with_args = self.f.argnames.iter()
             .zip(with_generics_idents.iter())
             .map(|(argname, id)| quote!(#argname: #id, ))
             .collect::<TokenStream>();
         let v = &self.f.privmod_vis;
+        let with_as_ref_method = if let Some((generics, args)) =
+            self.f.with_as_ref_params()
+        {
+            let closure_args = (0..argnames.len())
+                .map(|i| format_ident!("__mockall_a{}", i))
+                .collect::<Vec<_>>();
+            let evals = argnames.iter().zip(closure_args.iter())
+                .zip(self.f.predty.iter())
+                .map(|((argname, a), ty)| if let Type::TraitObject(_) = ty {
+                    quote!(#argname.eval(::std::convert::AsRef::as_ref(#a)))
+                } else {
+                    quote!(#argname.eval(#a))
+                });
+            quote!(
+                /// Set matching criteria for this Expectation, converting
+                /// each `AsRef` argument with `as_ref` before evaluating its
+                /// predicate.
+                ///
+                /// This lets a single expectation match calls made with
+                /// different argument types, like `&str` and `PathBuf`.
+                #v fn with_as_ref<#generics>(&mut self, #args) -> &mut Self
+                {
+                    self.common.withf(move |#(#closure_args, )*|
+                        #(#evals)&&*
+                    );
+                    self
+                }
+            )
+        } else {
+            quote!()
+        };
+        let capture_method = if self.f.concretize {
+            quote!()
+        } else {
+            let cgen = (0..argnames.len())
+                .map(|i| format_ident!("MockallC{}", i))
+                .collect::<Vec<_>>();
+            let cexprs = argnames.iter().zip(cgen.iter())
+                .map(|(argname, cg)|
+                    quote!(::mockall::CaptureArg::<#cg>::capture_arg(#argname))
+                ).collect::<Vec<_>>();
+            let (cty, cexpr) = if argnames.len() == 1 {
+                let cg = &cgen[0];
+                let ce = &cexprs[0];
+                (quote!(#cg), quote!(#ce))
+            } else {
+                (quote!((#(#cgen, )*)), quote!((#(#cexprs, )*)))
+            };
+            quote!(
+                /// Record the arguments of every call that this Expectation
+                /// handles.
+                ///
+                /// Returns a
+                /// [`Captured`](../../../mockall/struct.Captured.html) handle
+                /// that the test can use to examine them afterwards.  Each
+                /// argument is converted to its owned type, so `&str` is
+                /// captured as `String`.  Methods with several arguments
+                /// capture tuples.
+                #v fn capture<#(#cgen, )*>(&mut self)
+                    -> ::mockall::Captured<#cty>
+                    where #(#hrtb #predty: ::mockall::CaptureArg<#cgen>,
+                            #cgen: Send + 'static, )*
+                {
+                    let __mockall_captured = ::mockall::Captured::default();
+                    let __mockall_c = __mockall_captured.clone();
+                    self.common.inspect(move |#(#argnames, )*|
+                        __mockall_c.push(#cexpr)
+                    );
+                    __mockall_captured
+                }
+            )
+        };
         let with_method = if self.f.concretize {
             quote!(
                 // No `with` method when concretizing generics
@@ -1047,11 +1949,23 @@ impl<'a> ToTokens for CommonExpectationMethods<'a> {
                     self.common.with(#(#argnames, )*);
                     self
                 }
+
+                /// Single-threaded version of [`with`](#method.with).
+                /// Can be used when the argument type or the predicates
+                /// aren't `Send`.
+                #v fn with_st<#with_st_generics>(&mut self, #with_args)
+                    -> &mut Self
+                {
+                    self.common.with_st(#(#argnames, )*);
+                    self
+                }
             )
         };
         quote!(
             /// Add this expectation to a
-            /// [`Sequence`](../../../mockall/struct.Sequence.html).
+            /// [`Sequence`](../../../mockall/struct.Sequence.html).  It may
+            /// be added to several sequences; each call must be in order in
+            /// every one of them.
             #v fn in_sequence(&mut self, __mockall_seq: &mut ::mockall::Sequence)
                 -> &mut Self
             {
@@ -1059,6 +1973,16 @@ impl<'a> ToTokens for CommonExpectationMethods<'a> {
                 self
             }
 
+            /// Add this expectation to a
+            /// [`Scenario`](../../../mockall/struct.Scenario.html).  It will
+            /// be verified by the scenario rather than by its mock object.
+            #v fn in_scenario(&mut self,
+                __mockall_scenario: &mut ::mockall::Scenario) -> &mut Self
+            {
+                self.common.in_scenario(__mockall_scenario);
+                self
+            }
+
             fn is_done(&self) -> bool {
                 self.common.is_done()
             }
@@ -1069,6 +1993,30 @@ impl<'a> ToTokens for CommonExpectationMethods<'a> {
                 self.common.matches(#(#argnames, )*)
             }
 
+            /// Describe which arguments this expectation's matcher rejects.
+            #[allow(clippy::ptr_arg)]
+            fn mismatch #lg (&self, #(#argnames: &#predty, )*)
+                -> Option<::std::string::String>
+            {
+                self.common.mismatch(#(#argnames, )*)
+            }
+
+            /// Expect this expectation to be called at least `n` times.  The
+            /// maximum is unchanged, so it may be combined with
+            /// [`at_most`](#method.at_most).
+            #v fn at_least(&mut self, n: usize) -> &mut Self {
+                self.common.at_least(n);
+                self
+            }
+
+            /// Allow this expectation to be called at most `n` times.  The
+            /// minimum is unchanged, so it may be combined with
+            /// [`at_least`](#method.at_least).
+            #v fn at_most(&mut self, n: usize) -> &mut Self {
+                self.common.at_most(n);
+                self
+            }
+
             /// Forbid this expectation from ever being called.
             #v fn never(&mut self) -> &mut Self {
                 self.common.never();
@@ -1104,6 +2052,47 @@ impl<'a> ToTokens for CommonExpectationMethods<'a> {
                 self
             }
 
+            #capture_method
+
+            /// Run a closure on every call that this Expectation handles.
+            ///
+            /// The closure receives the method's arguments by reference, like
+            /// [`withf`](#method.withf), but it doesn't affect matching or the
+            /// return value.  It's useful for side effects like signalling
+            /// another thread.  Several closures may be added; they run in
+            /// order, before the return value is computed.
+            #v fn inspect<MockallF>(&mut self, __mockall_f: MockallF)
+                -> &mut Self
+                where MockallF: #hrtb FnMut(#(&#predty, )*) + Send + 'static
+            {
+                self.common.inspect(__mockall_f);
+                self
+            }
+
+            /// Set the priority of this Expectation.
+            ///
+            /// When several of a method's Expectations could match a call, the
+            /// one with the highest priority is used.  Expectations with equal
+            /// priority are checked in the order they were created.  The
+            /// default priority is 0.
+            #v fn priority(&mut self, __mockall_p: i32) -> &mut Self {
+                self.common.priority = __mockall_p;
+                self
+            }
+
+            /// Make this Expectation a fallback for calls that no other
+            /// Expectation handles.
+            ///
+            /// It's checked after all of the method's other Expectations,
+            /// whenever they were created, and it may be called any number of
+            /// times, including none.  That lets broad stubbing coexist with a
+            /// few strict Expectations.  It's shorthand for
+            /// `.priority(i32::MIN).times(..)`.
+            #v fn by_default(&mut self) -> &mut Self {
+                self.common.by_default();
+                self
+            }
+
             #with_method
 
             /// Set a matching function for this Expectation.
@@ -1127,6 +2116,8 @@ impl<'a> ToTokens for CommonExpectationMethods<'a> {
                 self.common.withf_st(__mockall_f);
                 self
             }
+
+            #with_as_ref_method
         ).to_tokens(tokens);
     }
 }
@@ -1139,8 +2130,29 @@ struct CommonExpectationsMethods<'a> {
 
 impl<'a> ToTokens for CommonExpectationsMethods<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        let argnames = &self.f.argnames;
         let (ig, tg, wc) = self.f.egenerics.split_for_impl();
+        let lg = lifetimes_to_generics(&self.f.alifetimes);
+        let predty = &self.f.predty;
         let v = &self.f.privmod_vis;
+        let expect_pending = if self.f.has_pending() {
+            let pgenerics = pending_generics(&self.f.egenerics);
+            let (_, ptg, _) = pgenerics.split_for_impl();
+            quote!(
+                /// Create a new expectation for this method, which won't take
+                /// effect until it has a return value.
+                #v fn expect_pending<'__mockall_e>(&'__mockall_e mut self)
+                    -> PendingExpectation #ptg
+                {
+                    PendingExpectation {
+                        expectation: Expectation::default(),
+                        expectations: self
+                    }
+                }
+            )
+        } else {
+            quote!()
+        };
         quote!(
             /// A collection of [`Expectation`](struct.Expectations.html)
             /// objects.  Users will rarely if ever use this struct directly.
@@ -1163,6 +2175,77 @@ impl<'a> ToTokens for CommonExpectationsMethods<'a> {
                     &mut self.0[__mockall_l - 1]
                 }
 
+                #expect_pending
+
+                /// Hand verification of every current expectation that isn't
+                /// already in a scenario over to `__mockall_scenario`.
+                #v fn in_scenario(&mut self,
+                    __mockall_scenario: &mut ::mockall::Scenario)
+                {
+                    for __mockall_e in self.0.iter_mut() {
+                        if __mockall_e.common.scenario_handle.is_none() {
+                            __mockall_e.common.in_scenario(__mockall_scenario);
+                        }
+                    }
+                }
+
+                /// Record every current expectation that hasn't yet been
+                /// called enough times.
+                #v fn try_verify(&self,
+                    __mockall_errors:
+                        &mut ::std::vec::Vec<::mockall::ExpectationError>)
+                {
+                    __mockall_errors.extend(self.0.iter()
+                        .filter_map(|__mockall_e|
+                            __mockall_e.common.unsatisfied()
+                        ));
+                }
+
+                /// Indices of the expectations in the order that they should
+                /// be checked: highest priority first, then FIFO.
+                fn dispatch_order(&self) -> ::std::iter::Chain<
+                    ::std::ops::Range<usize>, ::std::vec::IntoIter<usize>>
+                {
+                    let __mockall_n = self.0.len();
+                    if self.0.windows(2).all(|__mockall_w|
+                        __mockall_w[0].common.priority ==
+                            __mockall_w[1].common.priority)
+                    {
+                        // The usual case.  Don't allocate.
+                        return (0..__mockall_n).chain(::std::vec::Vec::new());
+                    }
+                    let mut __mockall_order = (0..__mockall_n)
+                        .collect::<Vec<_>>();
+                    // Stable, so equal priorities stay in FIFO order
+                    __mockall_order.sort_by_key(|__mockall_i|
+                        ::std::cmp::Reverse(self.0[*__mockall_i].common.priority)
+                    );
+                    (0..0).chain(__mockall_order)
+                }
+
+                /// Explain why no expectation matched these arguments, for the
+                /// panic message.
+                #[allow(clippy::ptr_arg)]
+                fn mismatches #lg (&self, #( #argnames: &#predty, )*)
+                    -> ::mockall::NoMatch
+                {
+                    let __mockall_ms = self.0.iter()
+                        .filter_map(|__mockall_e|
+                            __mockall_e.mismatch(#(#argnames, )*)
+                        ).collect::<Vec<_>>();
+                    let __mockall_saturated = self.0.iter()
+                        .any(|__mockall_e| __mockall_e.is_done() &&
+                             __mockall_e.matches(#(#argnames, )*));
+                    ::mockall::NoMatch {
+                        msg: if __mockall_ms.is_empty() {
+                            ::std::string::String::new()
+                        } else {
+                            std::format!(".  {}", __mockall_ms.join("; "))
+                        },
+                        saturated: __mockall_saturated
+                    }
+                }
+
                 #v fn new() -> Self {
                     Self::default()
                 }
@@ -1204,7 +2287,81 @@ impl<'a> ToTokens for ExpectationGuardCommonMethods<'a> {
         };
         let hrtb = self.f.hrtb();
         let output = &self.f.output;
+        let static_output = self.f.static_output();
         let predty = &self.f.predty;
+        let v = &self.f.privmod_vis;
+        let boxed_methods = if let Some(bounds) = self.f.boxed_dyn_bounds() {
+            quote!(
+                /// Just like
+                /// [`Expectation::return_boxed`](struct.Expectation.html#method.return_boxed)
+                #v fn return_boxed<MockallT>(&mut self, __mockall_v: MockallT)
+                    -> &mut Expectation #tg
+                    where MockallT: #bounds + Send + 'static
+                {
+                    #expectations.0[self.i].return_boxed(__mockall_v)
+                }
+
+                /// Just like
+                /// [`Expectation::returning_boxed`](struct.Expectation.html#method.returning_boxed)
+                #v fn returning_boxed<MockallF, MockallT>(&mut self,
+                    __mockall_f: MockallF)
+                    -> &mut Expectation #tg
+                    where MockallF: #hrtb FnMut(#(#argty, )*)
+                                    -> MockallT + Send + 'static,
+                          MockallT: #bounds + 'static
+                {
+                    #expectations.0[self.i].returning_boxed(__mockall_f)
+                }
+            )
+        } else {
+            quote!()
+        };
+        let future_methods = if let Some((fut_output, bounds)) =
+            self.f.pinned_future()
+        {
+            quote!(
+                /// Just like
+                /// [`Expectation::returning_async`](struct.Expectation.html#method.returning_async)
+                #v fn returning_async<MockallF, MockallFut>(&mut self,
+                    __mockall_f: MockallF)
+                    -> &mut Expectation #tg
+                    where MockallF: #hrtb FnMut(#(#argty, )*)
+                                    -> MockallFut + Send + 'static,
+                          MockallFut: ::std::future::Future<Output=#fut_output>
+                                      #bounds + 'static
+                {
+                    #expectations.0[self.i].returning_async(__mockall_f)
+                }
+
+                /// Just like
+                /// [`Expectation::returning_future`](struct.Expectation.html#method.returning_future)
+                #v fn returning_future<MockallOutput>(&mut self,
+                    __mockall_c: MockallOutput)
+                    -> &mut Expectation #tg
+                    where MockallOutput: Clone + Into<#fut_output> #bounds
+                                         + Send + 'static
+                {
+                    #expectations.0[self.i].returning_future(__mockall_c)
+                }
+            )
+        } else {
+            quote!()
+        };
+        let items_methods = if let Some(item) = self.f.option_inner() {
+            quote!(
+                /// Just like
+                /// [`Expectation::return_items`](struct.Expectation.html#method.return_items)
+                #v fn return_items<MockallI>(&mut self, __mockall_i: MockallI)
+                    -> &mut Expectation #tg
+                    where MockallI: IntoIterator<Item=#item>,
+                          MockallI::IntoIter: Send + 'static
+                {
+                    #expectations.0[self.i].return_items(__mockall_i)
+                }
+            )
+        } else {
+            quote!()
+        };
         let with_generics_idents = (0..self.f.predty.len())
             .map(|i| format_ident!("MockallMatcher{}", i))
             .collect::<Vec<_>>();
@@ -1213,12 +2370,28 @@ impl<'a> ToTokens for ExpectationGuardCommonMethods<'a> {
             .map(|(id, mt)|
                 quote!(#id: #hrtb ::mockall::Predicate<#mt> + Send + 'static, )
             ).collect::<TokenStream>();
+        let with_st_generics = with_generics_idents.iter()
+            .zip(self.f.predty.iter())
+            .map(|(id, mt)|
+                quote!(#id: #hrtb ::mockall::Predicate<#mt> + 'static, )
+            ).collect::<TokenStream>();
         let with_args = self.f.argnames.iter()
             .zip(with_generics_idents.iter())
             .map(|(argname, id)| quote!(#argname: #id, ))
             .collect::<TokenStream>();
-        let v = &self.f.privmod_vis;
-        let with_method = if self.f.concretize {
+        let with_method = if let Some((generics, args)) =
+            self.f.with_as_ref_params()
+        {
+            quote!(
+                /// Just like
+                /// [`Expectation::with_as_ref`](struct.Expectation.html#method.with_as_ref)
+                #v fn with_as_ref<#generics> (&mut self, #args)
+                    -> &mut Expectation #tg
+                {
+                    #expectations.0[self.i].with_as_ref(#(#argnames, )*)
+                }
+            )
+        } else if self.f.concretize {
             quote!()
         } else {
                 quote!(
@@ -1229,6 +2402,14 @@ impl<'a> ToTokens for ExpectationGuardCommonMethods<'a> {
                 {
                     #expectations.0[self.i].with(#(#argnames, )*)
                 }
+
+                /// Just like
+                /// [`Expectation::with_st`](struct.Expectation.html#method.with_st)
+                #v fn with_st<#with_st_generics> (&mut self, #with_args)
+                    -> &mut Expectation #tg
+                {
+                    #expectations.0[self.i].with_st(#(#argnames, )*)
+                }
             )
         };
         quote!(
@@ -1241,6 +2422,33 @@ impl<'a> ToTokens for ExpectationGuardCommonMethods<'a> {
                 #expectations.0[self.i].in_sequence(__mockall_seq)
             }
 
+            /// Just like
+            /// [`Expectation::in_scenario`](struct.Expectation.html#method.in_scenario)
+            #v fn in_scenario(&mut self,
+                __mockall_scenario: &mut ::mockall::Scenario)
+                -> &mut Expectation #tg
+            {
+                #expectations.0[self.i].in_scenario(__mockall_scenario)
+            }
+
+            /// Just like
+            /// [`Expectation::at_least`](struct.Expectation.html#method.at_least)
+            #v fn at_least(&mut self, n: usize) -> &mut Expectation #tg {
+                #expectations.0[self.i].at_least(n)
+            }
+
+            /// Just like
+            /// [`Expectation::at_most`](struct.Expectation.html#method.at_most)
+            #v fn at_most(&mut self, n: usize) -> &mut Expectation #tg {
+                #expectations.0[self.i].at_most(n)
+            }
+
+            /// Just like
+            /// [`Expectation::by_default`](struct.Expectation.html#method.by_default)
+            #v fn by_default(&mut self) -> &mut Expectation #tg {
+                #expectations.0[self.i].by_default()
+            }
+
             /// Just like
             /// [`Expectation::never`](struct.Expectation.html#method.never)
             #v fn never(&mut self) -> &mut Expectation #tg {
@@ -1258,7 +2466,7 @@ impl<'a> ToTokens for ExpectationGuardCommonMethods<'a> {
             #v fn return_const<MockallOutput>
             (&mut self, __mockall_c: MockallOutput)
                 -> &mut Expectation #tg
-                where MockallOutput: Clone + Into<#output> + Send + 'static
+                where MockallOutput: Clone + Into<#static_output> + Send + 'static
             {
                 #expectations.0[self.i].return_const(__mockall_c)
             }
@@ -1268,11 +2476,19 @@ impl<'a> ToTokens for ExpectationGuardCommonMethods<'a> {
             #v fn return_const_st<MockallOutput>
             (&mut self, __mockall_c: MockallOutput)
                 -> &mut Expectation #tg
-                where MockallOutput: Clone + Into<#output> + 'static
+                where MockallOutput: Clone + Into<#static_output> + 'static
             {
                 #expectations.0[self.i].return_const_st(__mockall_c)
             }
 
+            /// Just like
+            /// [`Expectation::return_default`](struct.Expectation.html#method.return_default)
+            #v fn return_default(&mut self) -> &mut Expectation #tg
+                where for<'__mockall_d> #static_output: Default
+            {
+                #expectations.0[self.i].return_default()
+            }
+
             /// Just like
             /// [`Expectation::returning`](struct.Expectation.html#method.returning)
             #v fn returning<MockallF>(&mut self, __mockall_f: MockallF)
@@ -1283,6 +2499,10 @@ impl<'a> ToTokens for ExpectationGuardCommonMethods<'a> {
                 #expectations.0[self.i].returning(__mockall_f)
             }
 
+            #boxed_methods
+            #future_methods
+            #items_methods
+
             /// Just like
             /// [`Expectation::return_once`](struct.Expectation.html#method.return_once)
             #v fn return_once<MockallF>(&mut self, __mockall_f: MockallF)
@@ -1371,13 +2591,9 @@ impl<'a> ToTokens for ConcreteExpectationGuard<'a> {
         let (e_ig, e_tg, e_wc) = e_generics.split_for_impl();
         let (ei_ig, _, _) = e_generics.split_for_impl();
         let v = &self.f.privmod_vis;
+        let expectations = self.f.expectations_static(quote!(Expectations #tg));
         quote!(
-            ::mockall::lazy_static! {
-                #[doc(hidden)]
-                #v static ref EXPECTATIONS:
-                    ::std::sync::Mutex<Expectations #tg> =
-                    ::std::sync::Mutex::new(Expectations::new());
-            }
+            #expectations
             /// Like an [`&Expectation`](struct.Expectation.html) but
             /// protected by a Mutex guard.  Useful for mocking static
             /// methods.  Forwards accesses to an `Expectation` object.
@@ -1440,12 +2656,10 @@ impl<'a> ToTokens for GenericExpectationGuard<'a> {
         let fn_params = &self.f.fn_params;
         let tbf = tg.as_turbofish();
         let v = &self.f.privmod_vis;
+        let expectations = self.f.expectations_static(
+            quote!(GenericExpectations));
         quote!(
-            ::mockall::lazy_static! {
-                #v static ref EXPECTATIONS:
-                    ::std::sync::Mutex<GenericExpectations> =
-                    ::std::sync::Mutex::new(GenericExpectations::new());
-            }
+            #expectations
             /// Like an [`&Expectation`](struct.Expectation.html) but
             /// protected by a Mutex guard.  Useful for mocking static
             /// methods.  Forwards accesses to an `Expectation` object.
@@ -1516,9 +2730,14 @@ impl<'a> ToTokens for Context<'a> {
         let v = &self.f.privmod_vis;
 
         #[cfg(not(feature = "nightly_derive"))]
-        let must_use = quote!(#[must_use =
-                "Must set return value when not using the \"nightly\" feature"
-            ]);
+        let must_use = if self.f.returns_unit() {
+            // Methods that return () don't need an explicit return value
+            quote!()
+        } else {
+            quote!(#[must_use =
+                "Must set return value unless its type is known to impl Default"
+            ])
+        };
         #[cfg(feature = "nightly_derive")]
         let must_use = quote!();
 
@@ -1548,7 +2767,7 @@ impl<'a> ToTokens for Context<'a> {
                 #v fn do_checkpoint() {
                     let __mockall_timeses = EXPECTATIONS
                         .lock()
-                        .unwrap()
+                        .unwrap_or_else(::std::sync::PoisonError::into_inner)
                         .checkpoint()
                         .collect::<Vec<_>>();
                 }
@@ -1558,7 +2777,8 @@ impl<'a> ToTokens for Context<'a> {
                 #v fn expect #meth_ig ( &self,) -> ExpectationGuard #e_tg
                     #meth_wc
                 {
-                    ExpectationGuard::new(EXPECTATIONS.lock().unwrap())
+                    ExpectationGuard::new(EXPECTATIONS.lock()
+                        .unwrap_or_else(::std::sync::PoisonError::into_inner))
                 }
             }
             impl #ty_ig Default for Context #ty_tg #ty_wc {
@@ -1568,9 +2788,7 @@ impl<'a> ToTokens for Context<'a> {
             }
             impl #ty_ig Drop for Context #ty_tg #ty_wc {
                 fn drop(&mut self) {
-                    if !std::thread::panicking() {
-                        Self::do_checkpoint()
-                    }
+                    Self::do_checkpoint()
                 }
             }
         ).to_tokens(tokens);
@@ -1613,12 +2831,44 @@ impl<'a> ToTokens for Matcher<'a> {
             .map(|t| quote!(Box<dyn #hrtb ::mockall::Predicate<#t> + Send>,))
             .collect::<TokenStream>()
         };
+        let preds_st = if self.f.concretize {
+            quote!(())
+        } else {
+            self.f.predty.iter()
+            .map(|t| quote!(Box<dyn #hrtb ::mockall::Predicate<#t>>,))
+            .collect::<TokenStream>()
+        };
         let predty = &self.f.predty;
         let refpredty = &self.f.refpredty;
+        let nargs = argnames.len();
+        let argname_strs = argnames.iter()
+            .map(|argname| quote!(#argname).to_string())
+            .collect::<Vec<_>>();
+        let predmismatch_body = if self.f.concretize {
+            quote!()
+        } else {
+            quote!(
+                Matcher::Pred(__mockall_pred) => {
+                    let __mockall_ok: [bool; #nargs] = [#pred_matches];
+                    Self::rejected(&__mockall_ok)
+                },
+                Matcher::PredSt(__mockall_pred) => {
+                    let __mockall_pred = __mockall_pred.get();
+                    let __mockall_ok: [bool; #nargs] = [#pred_matches];
+                    Self::rejected(&__mockall_ok)
+                },
+            )
+        };
         let predmatches_body = if self.f.concretize {
             quote!()
         } else {
-            quote!(Matcher::Pred(__mockall_pred) => [#pred_matches].iter().all(|__mockall_x| *__mockall_x),)
+            quote!(
+                Matcher::Pred(__mockall_pred) => [#pred_matches].iter().all(|__mockall_x| *__mockall_x),
+                Matcher::PredSt(__mockall_pred) => {
+                    let __mockall_pred = __mockall_pred.get();
+                    [#pred_matches].iter().all(|__mockall_x| *__mockall_x)
+                },
+            )
         };
         let preddbg_body = if self.f.concretize {
             quote!()
@@ -1628,6 +2878,11 @@ impl<'a> ToTokens for Matcher<'a> {
                     write!(__mockall_fmt, #braces,
                         #(__mockall_p.#indices,)*)
                 }
+                Matcher::PredSt(__mockall_p) => {
+                    let __mockall_p = __mockall_p.get();
+                    write!(__mockall_fmt, #braces,
+                        #(__mockall_p.#indices,)*)
+                }
             )
         };
         quote!(
@@ -1637,6 +2892,8 @@ impl<'a> ToTokens for Matcher<'a> {
                 // Version of Matcher::Func for closures that aren't Send
                 FuncSt(::mockall::Fragile<Box<dyn #hrtb Fn(#( #refpredty, )*) -> bool>>),
                 Pred(Box<(#preds)>),
+                // Version of Matcher::Pred for predicates that aren't Send
+                PredSt(::mockall::Fragile<Box<(#preds_st)>>),
                 // Prevent "unused type parameter" errors
                 // Surprisingly, PhantomData<Fn(generics)> is Send even if
                 // generics are not, unlike PhantomData<generics>
@@ -1655,6 +2912,38 @@ impl<'a> ToTokens for Matcher<'a> {
                         _ => unreachable!()
                     }
                 }
+
+                /// Name the arguments rejected by their individual predicates.
+                /// Other kinds of matchers can't tell which argument was wrong.
+                #[allow(clippy::ptr_arg)]
+                #[allow(unused_variables)]
+                fn mismatch #lg (&self, #( #argnames: &#predty, )*)
+                    -> Option<::std::string::String>
+                {
+                    match self {
+                        #predmismatch_body
+                        _ => None
+                    }
+                }
+
+                #[allow(dead_code)]
+                fn rejected(__mockall_ok: &[bool; #nargs])
+                    -> Option<::std::string::String>
+                {
+                    const NAMES: [&str; #nargs] = [#(#argname_strs, )*];
+                    let __mockall_bad = NAMES.iter()
+                        .zip(__mockall_ok.iter())
+                        .filter(|(_, __mockall_ok)| !**__mockall_ok)
+                        .map(|(__mockall_name, _)|
+                             std::format!("`{}`", __mockall_name))
+                        .collect::<Vec<_>>();
+                    match __mockall_bad.len() {
+                        0 => None,
+                        1 => Some(std::format!("argument {}", __mockall_bad[0])),
+                        _ => Some(std::format!("arguments {}",
+                                               __mockall_bad.join(", ")))
+                    }
+                }
             }
 
             impl #ig Default for Matcher #tg #wc {
@@ -1694,7 +2983,7 @@ impl<'a> ToTokens for RefRfunc<'a> {
 
         #[cfg(not(feature = "nightly_derive"))]
         let default_err_msg =
-            "Returning default values requires the \"nightly\" feature";
+            "Can only return default values for types known to impl std::Default, unless using the \"nightly\" feature";
         #[cfg(feature = "nightly_derive")]
         let default_err_msg =
             "Can only return default values for types that impl std::Default";
@@ -1731,9 +3020,10 @@ impl<'a> ToTokens for RefRfunc<'a> {
             impl #ig std::default::Default for Rfunc #tg #wc
             {
                 fn default() -> Self {
-                    use ::mockall::ReturnDefault;
-                    Rfunc::Default(::mockall::DefaultReturner::<#owned_output>
-                                ::maybe_return_default())
+                    use ::mockall::{ReturnDefault, ReturnNoDefault};
+                    Rfunc::Default(
+                        (&&::mockall::DefaultReturner::<#owned_output>::new())
+                        .maybe_return_default())
                 }
             }
         ).to_tokens(tokens);
@@ -1756,7 +3046,7 @@ impl<'a> ToTokens for RefMutRfunc<'a> {
 
         #[cfg(not(feature = "nightly_derive"))]
         let default_err_msg =
-            "Returning default values requires the \"nightly\" feature";
+            "Can only return default values for types known to impl std::Default, unless using the \"nightly\" feature";
         #[cfg(feature = "nightly_derive")]
         let default_err_msg =
             "Can only return default values for types that impl std::Default";
@@ -1816,14 +3106,26 @@ impl<'a> ToTokens for RefMutRfunc<'a> {
                         Rfunc::_Phantom(_) => unreachable!()
                     }
                 }
+
+                /// The value most recently returned, if any
+                fn var(&self) -> Option<&#owned_output> {
+                    match self {
+                        Rfunc::Default(Some(ref __mockall_o)) |
+                        Rfunc::Mut(_, Some(ref __mockall_o)) |
+                        Rfunc::MutSt(_, Some(ref __mockall_o)) |
+                        Rfunc::Var(ref __mockall_o) => Some(__mockall_o),
+                        _ => None
+                    }
+                }
             }
 
             impl #ig std::default::Default for Rfunc #tg #wc
             {
                 fn default() -> Self {
-                    use ::mockall::ReturnDefault;
-                    Rfunc::Default(::mockall::DefaultReturner::<#owned_output>
-                                ::maybe_return_default())
+                    use ::mockall::{ReturnDefault, ReturnNoDefault};
+                    Rfunc::Default(
+                        (&&::mockall::DefaultReturner::<#owned_output>::new())
+                        .maybe_return_default())
                 }
             }
         ).to_tokens(tokens);
@@ -1843,6 +3145,32 @@ impl<'a> ToTokens for StaticRfunc<'a> {
         let hrtb = self.f.hrtb();
         let lg = lifetimes_to_generics(&self.f.alifetimes);
         let output = &self.f.output;
+        let default_body = if self.f.returns_unit() {
+            // There's nothing to return, so the expectation doesn't need a
+            // return value.
+            quote!(::std::result::Result::Ok(()))
+        } else {
+            quote!(
+                use ::mockall::{ReturnDefault, ReturnNoDefault};
+                (&&::mockall::DefaultReturner::<#output>::new())
+                    .return_default()
+            )
+        };
+        let (forward_variant, forward_arm) = if self.f.spy_obj.is_some() {
+            (
+                quote!(
+                    // Get the return value from a spy's real object instead
+                    Forward,
+                ),
+                quote!(
+                    Rfunc::Forward => {
+                        Err("can only forward calls for mock objects created with `spy`")
+                    },
+                )
+            )
+        } else {
+            (quote!(), quote!())
+        };
         quote!(
             #[allow(clippy::unused_unit)]
             enum Rfunc #ig #wc {
@@ -1850,6 +3178,7 @@ impl<'a> ToTokens for StaticRfunc<'a> {
                 // Indicates that a `return_once` expectation has already
                 // returned
                 Expired,
+                #forward_variant
                 Mut(Box<dyn #hrtb FnMut(#(#argty, )*) -> #output + Send>),
                 // Version of Rfunc::Mut for closures that aren't Send
                 MutSt(::mockall::Fragile<
@@ -1872,13 +3201,12 @@ impl<'a> ToTokens for StaticRfunc<'a> {
                 {
                     match self {
                         Rfunc::Default => {
-                            use ::mockall::ReturnDefault;
-                            ::mockall::DefaultReturner::<#output>
-                                ::return_default()
+                            #default_body
                         },
                         Rfunc::Expired => {
                             Err("called twice, but it returns by move")
                         },
+                        #forward_arm
                         Rfunc::Mut(__mockall_f) => {
                             ::std::result::Result::Ok(__mockall_f( #(#argnames, )* ))
                         },
@@ -1927,6 +3255,7 @@ impl<'a> ToTokens for RefExpectation<'a> {
         let argty = &self.f.argty;
         let common_methods = CommonExpectationMethods{f: self.f};
         let desc = self.f.desc();
+        let predexprs = &self.f.predexprs;
         let funcname = self.f.funcname();
         let (ig, tg, wc) = self.f.egenerics.split_for_impl();
 
@@ -1949,12 +3278,12 @@ impl<'a> ToTokens for RefExpectation<'a> {
                 /// Call this [`Expectation`] as if it were the real method.
                 #v fn call #lg (&self, #(#argnames: #argty, )*) -> #output
                 {
-                    self.common.call(&#desc);
+                    self.common.call(&#desc, #(#predexprs, )*);
                     self.rfunc.call().unwrap_or_else(|m| {
                         let desc = std::format!(
                             "{}", self.common.matcher.lock().unwrap());
-                        panic!("{}: Expectation({}) {}", #funcname, desc,
-                            m);
+                        ::mockall::fail_call(std::format!(
+                            "{}: Expectation({}) {}", #funcname, desc, m));
                     })
                 }
 
@@ -1992,6 +3321,7 @@ impl<'a> ToTokens for RefMutExpectation<'a> {
         let argnames = &self.f.argnames;
         let argty = &self.f.argty;
         let desc = self.f.desc();
+        let predexprs = &self.f.predexprs;
         let funcname = self.f.funcname();
         let (ig, tg, wc) = self.f.egenerics.split_for_impl();
         let (_, common_tg, _) = self.f.cgenerics.split_for_impl();
@@ -2004,6 +3334,7 @@ impl<'a> ToTokens for RefMutExpectation<'a> {
             /// `expect_*` methods.
             #v struct Expectation #ig #wc {
                 common: Common #common_tg,
+                inspector: Option<Box<dyn FnOnce(&#owned_output) + Send + Sync>>,
                 rfunc: Rfunc #tg
             }
 
@@ -2013,12 +3344,12 @@ impl<'a> ToTokens for RefMutExpectation<'a> {
                 #v fn call_mut #lg (&mut self, #(#argnames: #argty, )*)
                     -> &mut #owned_output
                 {
-                    self.common.call(&#desc);
+                    self.common.call(&#desc, #(#predexprs, )*);
                     let desc = std::format!(
                         "{}", self.common.matcher.lock().unwrap());
                     self.rfunc.call_mut(#(#argnames, )*).unwrap_or_else(|m| {
-                            panic!("{}: Expectation({}) {}", #funcname, desc,
-                                   m);
+                        ::mockall::fail_call(std::format!(
+                            "{}: Expectation({}) {}", #funcname, desc, m));
                     })
                 }
 
@@ -2053,6 +3384,19 @@ impl<'a> ToTokens for RefMutExpectation<'a> {
                     self
                 }
 
+                /// Supply a closure that will inspect the return value after
+                /// the caller is done mutating it.  The closure runs when the
+                /// `Expectation` is dropped, either by `checkpoint` or by
+                /// dropping the mock object, but only if the method was
+                /// called.
+                #v fn inspect_var<MockallF>(&mut self, __mockall_f: MockallF)
+                    -> &mut Self
+                    where MockallF: FnOnce(&#owned_output) + Send + Sync + 'static
+                {
+                    self.inspector = Some(Box::new(__mockall_f));
+                    self
+                }
+
                 #common_methods
             }
             impl #ig Default for Expectation #tg #wc
@@ -2060,10 +3404,24 @@ impl<'a> ToTokens for RefMutExpectation<'a> {
                 fn default() -> Self {
                     Expectation {
                         common: Common::default(),
+                        inspector: None,
                         rfunc: Rfunc::default()
                     }
                 }
             }
+            impl #ig Drop for Expectation #tg #wc {
+                fn drop(&mut self) {
+                    if !::std::thread::panicking() &&
+                        self.common.times.count() > 0
+                    {
+                        if let Some(__mockall_f) = self.inspector.take() {
+                            if let Some(__mockall_o) = self.rfunc.var() {
+                                __mockall_f(__mockall_o);
+                            }
+                        }
+                    }
+                }
+            }
         ).to_tokens(tokens);
     }
 }
@@ -2073,160 +3431,324 @@ struct StaticExpectation<'a> {
     f: &'a MockFunction
 }
 
-impl<'a> ToTokens for StaticExpectation<'a> {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
+impl<'a> StaticExpectation<'a> {
+    /// All of the `Expectation`'s methods.  `PendingExpectation` forwards
+    /// some of them.
+    fn methods(&self) -> TokenStream {
         let common_methods = CommonExpectationMethods{f: self.f};
         let argnames = &self.f.argnames;
         let argty = &self.f.argty;
         let desc = self.f.desc();
+        let predexprs = &self.f.predexprs;
         let hrtb = self.f.hrtb();
         let funcname = self.f.funcname();
-        let (ig, tg, wc) = self.f.egenerics.split_for_impl();
-        let (_, common_tg, _) = self.f.cgenerics.split_for_impl();
+        let (_, tg, _) = self.f.egenerics.split_for_impl();
         let lg = lifetimes_to_generics(&self.f.alifetimes);
         let output = &self.f.output;
+        let static_output = self.f.static_output();
         let v = &self.f.privmod_vis;
+        let boxed_methods = if let Some(bounds) = self.f.boxed_dyn_bounds() {
+            quote!(
+                /// Return a value that will be boxed into the method's
+                /// `Box<dyn Trait>` return type.  This is useful for values
+                /// that aren't `Clone`.  As with
+                /// [`return_once`](#method.return_once), it will be an error
+                /// to call the mock method more than once.
+                #[allow(unused_variables)]
+                #v fn return_boxed<MockallT>(&mut self, __mockall_v: MockallT)
+                    -> &mut Self
+                    where MockallT: #bounds + Send + 'static
+                {
+                    self.return_once(move |#(#argnames, )*|
+                        Box::new(__mockall_v))
+                }
 
-        quote!(
-            /// Expectation type for methods that return a `'static` type.
-            /// This is the type returned by the `expect_*` methods.
-            #v struct Expectation #ig #wc {
-                common: Common #common_tg,
-                rfunc: Mutex<Rfunc #tg>,
-            }
-
-            #[allow(clippy::unused_unit)]
-            impl #ig Expectation #tg #wc {
-                /// Call this [`Expectation`] as if it were the real method.
-                #[doc(hidden)]
-                #v fn call #lg (&self, #(#argnames: #argty, )* ) -> #output
+                /// Supply a closure that will provide the return value for
+                /// this Expectation, boxing its result into the method's
+                /// `Box<dyn Trait>` return type.
+                #v fn returning_boxed<MockallF, MockallT>(&mut self,
+                    mut __mockall_f: MockallF)
+                    -> &mut Self
+                    where MockallF: #hrtb FnMut(#(#argty, )*)
+                                    -> MockallT + Send + 'static,
+                          MockallT: #bounds + 'static
                 {
-                    self.common.call(&#desc);
-                    self.rfunc.lock().unwrap().call_mut(#(#argnames, )*)
-                        .unwrap_or_else(|message| {
-                            let desc = std::format!(
-                                "{}", self.common.matcher.lock().unwrap());
-                            panic!("{}: Expectation({}) {}", #funcname, desc,
-                                   message);
-                        })
+                    self.returning(move |#(#argnames, )*|
+                        Box::new(__mockall_f(#(#argnames, )*)))
                 }
+            )
+        } else {
+            quote!()
+        };
 
-                /// Return a constant value from the `Expectation`
-                ///
-                /// The output type must be `Clone`.  The compiler can't always
-                /// infer the proper type to use with this method; you will
-                /// usually need to specify it explicitly.  i.e.
-                /// `return_const(42i32)` instead of `return_const(42)`.
-                // We must use Into<#output> instead of #output because where
-                // clauses don't accept equality constraints.
-                // https://github.com/rust-lang/rust/issues/20041
-                #[allow(unused_variables)]
-                #v fn return_const<MockallOutput>(&mut self,
-                    __mockall_c: MockallOutput)
+        let future_methods = if let Some((fut_output, bounds)) =
+            self.f.pinned_future()
+        {
+            quote!(
+                /// Supply a closure that returns a `Future`, which will be
+                /// pinned and boxed into the method's
+                /// `Pin<Box<dyn Future>>` return type.  Typically the closure
+                /// will return an `async move` block.
+                #v fn returning_async<MockallF, MockallFut>(&mut self,
+                    mut __mockall_f: MockallF)
                     -> &mut Self
-                    where MockallOutput: Clone + Into<#output> + Send + 'static
+                    where MockallF: #hrtb FnMut(#(#argty, )*)
+                                    -> MockallFut + Send + 'static,
+                          MockallFut: ::std::future::Future<Output=#fut_output>
+                                      #bounds + 'static
                 {
-                    self.returning(move |#(#argnames, )*| __mockall_c.clone().into())
+                    self.returning(move |#(#argnames, )*|
+                        Box::pin(__mockall_f(#(#argnames, )*)))
                 }
 
-                /// Single-threaded version of
-                /// [`return_const`](#method.return_const).  This is useful for
-                /// return types that are not `Send`.
-                ///
-                /// The output type must be `Clone`.  The compiler can't always
-                /// infer the proper type to use with this method; you will
-                /// usually need to specify it explicitly.  i.e.
-                /// `return_const(42i32)` instead of `return_const(42)`.
-                ///
-                /// It is a runtime error to call the mock method from a
-                /// different thread than the one that originally called this
-                /// method.
-                // We must use Into<#output> instead of #output because where
-                // clauses don't accept equality constraints.
-                // https://github.com/rust-lang/rust/issues/20041
+                /// Return a `Future` that will immediately resolve to a clone
+                /// of the given value, every time the method is called.
                 #[allow(unused_variables)]
-                #v fn return_const_st<MockallOutput>(&mut self,
+                #v fn returning_future<MockallOutput>(&mut self,
                     __mockall_c: MockallOutput)
                     -> &mut Self
-                    where MockallOutput: Clone + Into<#output> + 'static
+                    where MockallOutput: Clone + Into<#fut_output> #bounds
+                                         + Send + 'static
                 {
-                    self.returning_st(move |#(#argnames, )*| __mockall_c.clone().into())
+                    self.returning_async(move |#(#argnames, )*| {
+                        let __mockall_v = __mockall_c.clone();
+                        async move { __mockall_v.into() }
+                    })
                 }
+            )
+        } else {
+            quote!()
+        };
 
-                /// Supply an `FnOnce` closure that will provide the return
-                /// value for this Expectation.  This is useful for return types
-                /// that aren't `Clone`.  It will be an error to call this
-                /// method multiple times.
-                #v fn return_once<MockallF>(&mut self, __mockall_f: MockallF)
-                    -> &mut Self
-                    where MockallF: #hrtb FnOnce(#(#argty, )*)
-                                    -> #output + Send + 'static
+        let spy_methods = if self.f.spy_obj.is_some() {
+            quote!(
+                /// Like `call`, but get the return value from the spy's
+                /// expectations if this expectation forwards its calls.
+                #[doc(hidden)]
+                #v fn call_spy #lg (&self, __mockall_spy: &Expectations #tg,
+                    #(#argnames: #argty, )* ) -> #output
                 {
-                    {
-                        let mut __mockall_guard = self.rfunc.lock().unwrap();
-                        *__mockall_guard.deref_mut() =
-                            Rfunc::Once(Box::new(__mockall_f));
+                    let __mockall_forward = matches!(
+                        *self.rfunc.lock().unwrap(), Rfunc::Forward);
+                    if !__mockall_forward || __mockall_spy.0.is_empty() {
+                        return self.call(#(#argnames, )*);
                     }
-                    self
+                    self.common.call(&#desc, #(#predexprs, )*);
+                    __mockall_spy.call(#(#argnames, )*)
+                        .unwrap_or_else(|__mockall_m| {
+                            ::mockall::fail_call(std::format!(
+                                "{}: No matching expectation found{}",
+                                #funcname, __mockall_m))
+                        })
                 }
 
-                /// Single-threaded version of
-                /// [`return_once`](#method.return_once).  This is useful for
-                /// return types that are neither `Send` nor `Clone`.
-                ///
-                /// It is a runtime error to call the mock method from a
-                /// different thread than the one that originally called this
-                /// method.  It is also a runtime error to call the method more
-                /// than once.
-                #v fn return_once_st<MockallF>(&mut self, __mockall_f:
-                                                  MockallF) -> &mut Self
-                    where MockallF: #hrtb FnOnce(#(#argty, )*)
-                                    -> #output + 'static
-                {
+                /// Forward calls that match this expectation to the real
+                /// object of a mock created with `spy`, and return its result.
+                /// Unlike calls that no expectation matches, these can be
+                /// counted and verified with [`times`](#method.times) and
+                /// related methods.
+                #v fn forward(&mut self) -> &mut Self {
                     {
                         let mut __mockall_guard = self.rfunc.lock().unwrap();
-                        *__mockall_guard.deref_mut() = Rfunc::OnceSt(
-                            ::mockall::Fragile::new(Box::new(__mockall_f)));
+                        *__mockall_guard.deref_mut() = Rfunc::Forward;
                     }
                     self
                 }
+            )
+        } else {
+            quote!()
+        };
 
-                /// Supply a closure that will provide the return value for this
-                /// `Expectation`.  The method's arguments are passed to the
-                /// closure by value.
-                #v fn returning<MockallF>(&mut self, __mockall_f: MockallF)
+        let items_methods = if let Some(item) = self.f.option_inner() {
+            quote!(
+                /// Return each of the given items in turn, wrapped in `Some`,
+                /// and then `None` on every call thereafter.  This makes it
+                /// easy to mock methods like `Iterator::next`.
+                #[allow(unused_variables)]
+                #v fn return_items<MockallI>(&mut self, __mockall_i: MockallI)
                     -> &mut Self
-                    where MockallF: #hrtb FnMut(#(#argty, )*)
-                                    -> #output + Send + 'static
+                    where MockallI: IntoIterator<Item=#item>,
+                          MockallI::IntoIter: Send + 'static
                 {
-                    {
-                        let mut __mockall_guard = self.rfunc.lock().unwrap();
-                        *__mockall_guard.deref_mut() =
-                            Rfunc::Mut(Box::new(__mockall_f));
-                    }
-                    self
+                    let mut __mockall_iter = __mockall_i.into_iter();
+                    self.returning(move |#(#argnames, )*|
+                        __mockall_iter.next())
                 }
+            )
+        } else {
+            quote!()
+        };
 
-                /// Single-threaded version of [`returning`](#method.returning).
-                /// Can be used when the argument or return type isn't `Send`.
-                ///
-                /// It is a runtime error to call the mock method from a
-                /// different thread than the one that originally called this
-                /// method.
-                #v fn returning_st<MockallF>(&mut self, __mockall_f: MockallF)
-                    -> &mut Self
-                    where MockallF: #hrtb FnMut(#(#argty, )*)
-                                    -> #output + 'static
+        quote!(
+            /// Call this [`Expectation`] as if it were the real method.
+            #[doc(hidden)]
+            #v fn call #lg (&self, #(#argnames: #argty, )* ) -> #output
+            {
+                self.common.call(&#desc, #(#predexprs, )*);
+                self.rfunc.lock().unwrap().call_mut(#(#argnames, )*)
+                    .unwrap_or_else(|message| {
+                        let desc = std::format!(
+                            "{}", self.common.matcher.lock().unwrap());
+                        ::mockall::fail_call(std::format!(
+                            "{}: Expectation({}) {}", #funcname, desc,
+                            message));
+                    })
+            }
+
+            /// Return a constant value from the `Expectation`
+            ///
+            /// The output type must be `Clone`.  The compiler can't always
+            /// infer the proper type to use with this method; you will
+            /// usually need to specify it explicitly.  i.e.
+            /// `return_const(42i32)` instead of `return_const(42)`.
+            // We must use Into<#output> instead of #output because where
+            // clauses don't accept equality constraints.
+            // https://github.com/rust-lang/rust/issues/20041
+            #[allow(unused_variables)]
+            #v fn return_const<MockallOutput>(&mut self,
+                __mockall_c: MockallOutput)
+                -> &mut Self
+                where MockallOutput: Clone + Into<#static_output> + Send + 'static
+            {
+                self.returning(move |#(#argnames, )*| __mockall_c.clone().into())
+            }
+
+            /// Single-threaded version of
+            /// [`return_const`](#method.return_const).  This is useful for
+            /// return types that are not `Send`.
+            ///
+            /// The output type must be `Clone`.  The compiler can't always
+            /// infer the proper type to use with this method; you will
+            /// usually need to specify it explicitly.  i.e.
+            /// `return_const(42i32)` instead of `return_const(42)`.
+            ///
+            /// It is a runtime error to call the mock method from a
+            /// different thread than the one that originally called this
+            /// method.
+            // We must use Into<#output> instead of #output because where
+            // clauses don't accept equality constraints.
+            // https://github.com/rust-lang/rust/issues/20041
+            #[allow(unused_variables)]
+            #v fn return_const_st<MockallOutput>(&mut self,
+                __mockall_c: MockallOutput)
+                -> &mut Self
+                where MockallOutput: Clone + Into<#static_output> + 'static
+            {
+                self.returning_st(move |#(#argnames, )*| __mockall_c.clone().into())
+            }
+
+            /// Return the default value of the method's return type.
+            ///
+            /// The return type must implement `Default`.
+            // The bound is higher-ranked only so that it won't be checked
+            // for methods whose return types don't implement Default.
+            #[allow(unused_variables)]
+            #v fn return_default(&mut self) -> &mut Self
+                where for<'__mockall_d> #static_output: Default
+            {
+                self.returning(|#(#argnames, )*| Default::default())
+            }
+
+            #boxed_methods
+            #future_methods
+            #items_methods
+            #spy_methods
+
+            /// Supply an `FnOnce` closure that will provide the return
+            /// value for this Expectation.  This is useful for return types
+            /// that aren't `Clone`.  It will be an error to call this
+            /// method multiple times.
+            #v fn return_once<MockallF>(&mut self, __mockall_f: MockallF)
+                -> &mut Self
+                where MockallF: #hrtb FnOnce(#(#argty, )*)
+                                -> #output + Send + 'static
+            {
                 {
-                    {
-                        let mut __mockall_guard = self.rfunc.lock().unwrap();
-                        *__mockall_guard.deref_mut() = Rfunc::MutSt(
-                            ::mockall::Fragile::new(Box::new(__mockall_f)));
-                    }
-                    self
+                    let mut __mockall_guard = self.rfunc.lock().unwrap();
+                    *__mockall_guard.deref_mut() =
+                        Rfunc::Once(Box::new(__mockall_f));
                 }
+                self
+            }
 
-                #common_methods
+            /// Single-threaded version of
+            /// [`return_once`](#method.return_once).  This is useful for
+            /// return types that are neither `Send` nor `Clone`.
+            ///
+            /// It is a runtime error to call the mock method from a
+            /// different thread than the one that originally called this
+            /// method.  It is also a runtime error to call the method more
+            /// than once.
+            #v fn return_once_st<MockallF>(&mut self, __mockall_f:
+                                              MockallF) -> &mut Self
+                where MockallF: #hrtb FnOnce(#(#argty, )*)
+                                -> #output + 'static
+            {
+                {
+                    let mut __mockall_guard = self.rfunc.lock().unwrap();
+                    *__mockall_guard.deref_mut() = Rfunc::OnceSt(
+                        ::mockall::Fragile::new(Box::new(__mockall_f)));
+                }
+                self
+            }
+
+            /// Supply a closure that will provide the return value for this
+            /// `Expectation`.  The method's arguments are passed to the
+            /// closure by value.
+            #v fn returning<MockallF>(&mut self, __mockall_f: MockallF)
+                -> &mut Self
+                where MockallF: #hrtb FnMut(#(#argty, )*)
+                                -> #output + Send + 'static
+            {
+                {
+                    let mut __mockall_guard = self.rfunc.lock().unwrap();
+                    *__mockall_guard.deref_mut() =
+                        Rfunc::Mut(Box::new(__mockall_f));
+                }
+                self
+            }
+
+            /// Single-threaded version of [`returning`](#method.returning).
+            /// Can be used when the argument or return type isn't `Send`.
+            ///
+            /// It is a runtime error to call the mock method from a
+            /// different thread than the one that originally called this
+            /// method.
+            #v fn returning_st<MockallF>(&mut self, __mockall_f: MockallF)
+                -> &mut Self
+                where MockallF: #hrtb FnMut(#(#argty, )*)
+                                -> #output + 'static
+            {
+                {
+                    let mut __mockall_guard = self.rfunc.lock().unwrap();
+                    *__mockall_guard.deref_mut() = Rfunc::MutSt(
+                        ::mockall::Fragile::new(Box::new(__mockall_f)));
+                }
+                self
+            }
+
+            #common_methods
+        )
+    }
+}
+
+impl<'a> ToTokens for StaticExpectation<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let (ig, tg, wc) = self.f.egenerics.split_for_impl();
+        let (_, common_tg, _) = self.f.cgenerics.split_for_impl();
+        let v = &self.f.privmod_vis;
+        let methods = self.methods();
+        quote!(
+            /// Expectation type for methods that return a `'static` type.
+            /// This is the type returned by the `expect_*` methods.
+            #v struct Expectation #ig #wc {
+                common: Common #common_tg,
+                rfunc: Mutex<Rfunc #tg>,
+            }
+
+            #[allow(clippy::unused_unit)]
+            impl #ig Expectation #tg #wc {
+                #methods
             }
             impl #ig Default for Expectation #tg #wc
             {
@@ -2241,6 +3763,117 @@ impl<'a> ToTokens for StaticExpectation<'a> {
     }
 }
 
+/// Prepend the lifetime of a `PendingExpectation`'s borrow to the
+/// `Expectation`'s generics
+fn pending_generics(egenerics: &Generics) -> Generics {
+    let mut pgenerics = egenerics.clone();
+    let lt = Lifetime::new("'__mockall_e", Span::call_site());
+    pgenerics.params.insert(0, GenericParam::Lifetime(LifetimeDef::new(lt)));
+    pgenerics
+}
+
+/// An Expectation that won't take effect until it has a return value.  Only
+/// generated for methods that return `'static` values.
+struct PendingExpectation<'a> {
+    f: &'a MockFunction
+}
+
+impl<'a> PendingExpectation<'a> {
+    /// Forward one of the `Expectation`'s builder methods: those that take
+    /// `&mut self` and return `&mut Self`.  Methods that set the return value
+    /// register the finished `Expectation`; the rest return the
+    /// `PendingExpectation` to be configured further.
+    fn forward(&self, meth: &ImplItemMethod) -> Option<TokenStream> {
+        let mut sig = meth.sig.clone();
+        match sig.inputs.first() {
+            Some(FnArg::Receiver(r))
+                if r.reference.is_some() && r.mutability.is_some() => (),
+            _ => return None
+        }
+        match &sig.output {
+            ReturnType::Type(_, ty)
+                if **ty == parse2::<Type>(quote!(&mut Self)).unwrap() => (),
+            _ => return None
+        }
+        let args = sig.inputs.iter()
+            .skip(1)
+            .map(|fa| match fa {
+                FnArg::Typed(pt) => match &*pt.pat {
+                    Pat::Ident(pi) => pi.ident.clone(),
+                    _ => unreachable!("Expectation methods have named arguments")
+                },
+                _ => unreachable!("Expectation methods have one receiver")
+            }).collect::<Vec<_>>();
+        let (_, tg, _) = self.f.egenerics.split_for_impl();
+        let ident = &sig.ident;
+        let returns = ident.to_string().starts_with("return") ||
+            ident == "forward";
+        *sig.inputs.first_mut().unwrap() = parse2(quote!(mut self)).unwrap();
+        let body = if returns {
+            sig.output = parse2(quote!(-> &'__mockall_e mut Expectation #tg))
+                .unwrap();
+            quote!(self.finish())
+        } else {
+            sig.output = parse2(quote!(-> Self)).unwrap();
+            quote!(self)
+        };
+        let vis = &meth.vis;
+        let docstr = format!("Just like [`Expectation::{}`](struct.Expectation.html#method.{})",
+            ident, ident);
+        Some(quote!(
+            #[doc = #docstr]
+            #vis #sig {
+                self.expectation.#ident(#(#args, )*);
+                #body
+            }
+        ))
+    }
+}
+
+impl<'a> ToTokens for PendingExpectation<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if !self.f.has_pending() {
+            return;
+        }
+        let (_, tg, _) = self.f.egenerics.split_for_impl();
+        let pgenerics = pending_generics(&self.f.egenerics);
+        let (ig, ptg, wc) = pgenerics.split_for_impl();
+        let v = &self.f.privmod_vis;
+        let methods = StaticExpectation{f: self.f}.methods();
+        let eimpl: ItemImpl = parse2(quote!(impl Expectation { #methods }))
+            .unwrap();
+        let forwards = eimpl.items.iter()
+            .filter_map(|item| match item {
+                ImplItem::Method(meth) => self.forward(meth),
+                _ => None
+            }).collect::<Vec<_>>();
+        quote!(
+            /// An [`Expectation`](struct.Expectation.html) that doesn't yet
+            /// have a return value.  This is the type returned by the
+            /// `expect_*_pending` methods.
+            ///
+            /// It may be configured just like an `Expectation`, but the mock
+            /// object won't know about it until a return value has been
+            /// supplied.  Supplying one yields the finished `Expectation`.
+            #[must_use = "The expectation won't take effect until it has a return value"]
+            #v struct PendingExpectation #ig #wc {
+                expectation: Expectation #tg,
+                expectations: &'__mockall_e mut Expectations #tg
+            }
+
+            impl #ig PendingExpectation #ptg #wc {
+                /// Register the finished expectation.
+                fn finish(self) -> &'__mockall_e mut Expectation #tg {
+                    self.expectations.0.push(self.expectation);
+                    self.expectations.0.last_mut().unwrap()
+                }
+
+                #(#forwards)*
+            }
+        ).to_tokens(tokens);
+    }
+}
+
 /// An collection of RefExpectation's
 struct RefExpectations<'a> {
     f: &'a MockFunction
@@ -2260,18 +3893,22 @@ impl<'a> ToTokens for RefExpectations<'a> {
             #common_methods
             impl #ig Expectations #tg #wc {
                 /// Simulate calling the real method.  Every current expectation
-                /// will be checked in FIFO order and the first one with
-                /// matching arguments will be used.
+                /// will be checked in order of priority, then FIFO order, and
+                /// the first one with matching arguments will be used.
                 #v fn call #lg (&self, #(#argnames: #argty, )* )
-                    -> Option<#output>
+                    -> std::result::Result<#output, ::mockall::NoMatch>
                 {
-                    self.0.iter()
+                    match self.dispatch_order()
+                        .map(|__mockall_i| &self.0[__mockall_i])
                         .find(|__mockall_e|
                               __mockall_e.matches(#(#predexprs, )*) &&
                               (!__mockall_e.is_done() || self.0.len() == 1))
-                        .map(move |__mockall_e|
-                             __mockall_e.call(#(#argnames),*)
-                        )
+                    {
+                        Some(__mockall_e) => ::std::result::Result::Ok(
+                            __mockall_e.call(#(#argnames),*)),
+                        None => ::std::result::Result::Err(
+                            self.mismatches(#(#predexprs, )*))
+                    }
                 }
 
             }
@@ -2298,19 +3935,25 @@ impl<'a> ToTokens for RefMutExpectations<'a> {
             #common_methods
             impl #ig Expectations #tg #wc {
                 /// Simulate calling the real method.  Every current expectation
-                /// will be checked in FIFO order and the first one with
-                /// matching arguments will be used.
+                /// will be checked in order of priority, then FIFO order, and
+                /// the first one with matching arguments will be used.
                 #v fn call_mut #lg (&mut self, #(#argnames: #argty, )* )
-                    -> Option<#output>
+                    -> std::result::Result<#output, ::mockall::NoMatch>
                 {
                     let __mockall_n = self.0.len();
-                    self.0.iter_mut()
-                        .find(|__mockall_e|
-                              __mockall_e.matches(#(#predexprs, )*) &&
-                              (!__mockall_e.is_done() || __mockall_n == 1))
-                        .map(move |__mockall_e|
-                             __mockall_e.call_mut(#(#argnames, )*)
-                        )
+                    let __mockall_i = self.dispatch_order()
+                        .find(|__mockall_i| {
+                            let __mockall_e = &self.0[*__mockall_i];
+                            __mockall_e.matches(#(#predexprs, )*) &&
+                              (!__mockall_e.is_done() || __mockall_n == 1)
+                        });
+                    match __mockall_i {
+                        Some(__mockall_i) =>
+                            ::std::result::Result::Ok(
+                                self.0[__mockall_i].call_mut(#(#argnames, )*)),
+                        None => ::std::result::Result::Err(
+                            self.mismatches(#(#predexprs, )*))
+                    }
                 }
 
             }
@@ -2333,24 +3976,56 @@ impl<'a> ToTokens for StaticExpectations<'a> {
         let output = &self.f.output;
         let predexprs = &self.f.predexprs;
         let v = &self.f.privmod_vis;
+        let call_spy = if self.f.spy_obj.is_some() {
+            quote!(
+                /// Like `call`, but if no expectation matches, use the
+                /// spy's expectation, if it has one.
+                #v fn call_spy #lg (&self, __mockall_spy: &Self,
+                    #(#argnames: #argty, )* )
+                    -> std::result::Result<#output, ::mockall::NoMatch>
+                {
+                    match self.dispatch_order()
+                        .map(|__mockall_i| &self.0[__mockall_i])
+                        .find(|__mockall_e|
+                              __mockall_e.matches(#(#predexprs, )*) &&
+                              (!__mockall_e.is_done() || self.0.len() == 1))
+                    {
+                        Some(__mockall_e) => ::std::result::Result::Ok(
+                            __mockall_e.call_spy(__mockall_spy,
+                                                 #(#argnames, )*)),
+                        None if __mockall_spy.0.is_empty() =>
+                            ::std::result::Result::Err(
+                                self.mismatches(#(#predexprs, )*)),
+                        None => __mockall_spy.call(#(#argnames, )*)
+                    }
+                }
+            )
+        } else {
+            quote!()
+        };
         quote!(
             #common_methods
             impl #ig Expectations #tg #wc {
                 /// Simulate calling the real method.  Every current expectation
-                /// will be checked in FIFO order and the first one with
-                /// matching arguments will be used.
+                /// will be checked in order of priority, then FIFO order, and
+                /// the first one with matching arguments will be used.
                 #v fn call #lg (&self, #(#argnames: #argty, )* )
-                    -> Option<#output>
+                    -> std::result::Result<#output, ::mockall::NoMatch>
                 {
-                    self.0.iter()
+                    match self.dispatch_order()
+                        .map(|__mockall_i| &self.0[__mockall_i])
                         .find(|__mockall_e|
                               __mockall_e.matches(#(#predexprs, )*) &&
                               (!__mockall_e.is_done() || self.0.len() == 1))
-                        .map(move |__mockall_e|
-                             __mockall_e.call(#(#argnames, )*)
-                        )
+                    {
+                        Some(__mockall_e) => ::std::result::Result::Ok(
+                            __mockall_e.call(#(#argnames, )*)),
+                        None => ::std::result::Result::Err(
+                            self.mismatches(#(#predexprs, )*))
+                    }
                 }
 
+                #call_spy
             }
         ).to_tokens(tokens);
     }
@@ -2391,6 +4066,50 @@ impl<'a> ToTokens for GenericExpectations<'a> {
                     self.store.drain()
                 }
 
+                /// Hand verification of every current expectation that isn't
+                /// already in a scenario over to `__mockall_scenario`.  This
+                /// applies to all sets of generic parameters!
+                #v fn in_scenario(&mut self,
+                    __mockall_scenario: &mut ::mockall::Scenario)
+                {
+                    for __mockall_e in self.store.values_mut() {
+                        __mockall_e.in_scenario(__mockall_scenario);
+                    }
+                }
+
+                /// Record every current expectation that hasn't yet been
+                /// called enough times.  This applies to all sets of generic
+                /// parameters!
+                #v fn try_verify(&self,
+                    __mockall_errors:
+                        &mut ::std::vec::Vec<::mockall::ExpectationError>)
+                {
+                    for __mockall_e in self.store.values() {
+                        __mockall_e.try_verify(__mockall_errors);
+                    }
+                }
+
+                /// If there are no expectations at all for the given generic
+                /// parameters, describe the ones that do have expectations.
+                fn missing_msg(&self, key: &::mockall::Key) -> String {
+                    if self.store.contains_key(key) {
+                        return String::new();
+                    }
+                    let mut names = self.store.keys()
+                        .map(|k| std::format!("`{}`", k.name()))
+                        .collect::<Vec<_>>();
+                    names.sort();
+                    if names.is_empty() {
+                        std::format!(
+                            ".  No expectations were set for generic parameters `{}`, or any others",
+                            key.name())
+                    } else {
+                        std::format!(
+                            ".  No expectations were set for generic parameters `{}`.  Expectations exist for {}",
+                            key.name(), names.join(", "))
+                    }
+                }
+
                 #v fn new() -> Self {
                     Self::default()
                 }
@@ -2421,6 +4140,9 @@ impl<'a> ToTokens for StaticGenericExpectations<'a> {
         let tbf = tg.as_turbofish();
         let output = &self.f.output;
         let v = &self.f.privmod_vis;
+        let lg = lifetimes_to_generics(&self.f.alifetimes);
+        let call_generics = merge_generics(&lg, &self.f.egenerics);
+        let (call_ig, _, _) = call_generics.split_for_impl();
         let (call, get, self_, downcast) = if self.f.return_refmut {
             (format_ident!("call_mut"),
              format_ident!("get_mut"),
@@ -2433,18 +4155,40 @@ impl<'a> ToTokens for StaticGenericExpectations<'a> {
              format_ident!("downcast_ref"))
         };
         quote!(
-            impl #ig ::mockall::AnyExpectations for Expectations #tg #any_wc {}
+            impl #ig ::mockall::AnyExpectations for Expectations #tg #any_wc {
+                fn in_scenario(&mut self,
+                    __mockall_scenario: &mut ::mockall::Scenario)
+                {
+                    Expectations::in_scenario(self, __mockall_scenario)
+                }
+
+                fn try_verify(&self,
+                    __mockall_errors:
+                        &mut ::std::vec::Vec<::mockall::ExpectationError>)
+                {
+                    Expectations::try_verify(self, __mockall_errors)
+                }
+            }
             impl GenericExpectations {
                 /// Simulating calling the real method.
-                #v fn #call #ig (#self_, #(#argnames: #argty, )* )
-                    -> Option<#output> #wc
+                ///
+                /// On failure, returns extra information about why no
+                /// expectation matched.
+                #v fn #call #call_ig (#self_, #(#argnames: #argty, )* )
+                    -> Result<#output, ::mockall::NoMatch> #wc
                 {
-                    self.store.#get(&::mockall::Key::new::#keyid())
+                    let __mockall_key = ::mockall::Key::new::#keyid();
+                    let __mockall_msg = self.missing_msg(&__mockall_key);
+                    self.store.#get(&__mockall_key)
                         .map(|__mockall_e| {
                             __mockall_e.#downcast::<Expectations #tg>()
                             .unwrap()
                             .#call(#(#argnames, )*)
-                        }).flatten()
+                        }).unwrap_or(
+                            ::std::result::Result::Err(::mockall::NoMatch {
+                                msg: __mockall_msg,
+                                saturated: false
+                            }))
                 }
 
                 /// Create a new Expectation.
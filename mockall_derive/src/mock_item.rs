@@ -3,11 +3,13 @@ use super::*;
 
 use crate::{
     mock_function::MockFunction,
-    mockable_item::{MockableItem, MockableModule}
+    mockable_item::{MockableFunctions, MockableItem, MockableModule}
 };
 
 /// A Mock item
 pub(crate) enum MockItem {
+    Alias(MockItemAlias),
+    Functions(MockItemFunctions),
     Module(MockItemModule),
     Struct(MockItemStruct)
 }
@@ -15,6 +17,12 @@ pub(crate) enum MockItem {
 impl From<MockableItem> for MockItem {
     fn from(mockable: MockableItem) -> MockItem {
         match mockable {
+            MockableItem::Alias(item_type) => MockItem::Alias(
+                MockItemAlias(item_type)
+            ),
+            MockableItem::Functions(fns) => MockItem::Functions(
+                MockItemFunctions::from(fns)
+            ),
             MockableItem::Struct(s) => MockItem::Struct(
                 MockItemStruct::from(s)
             ),
@@ -28,12 +36,185 @@ impl From<MockableItem> for MockItem {
 impl ToTokens for MockItem {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
+            MockItem::Alias(alias) => alias.to_tokens(tokens),
+            MockItem::Functions(fns) => fns.to_tokens(tokens),
             MockItem::Module(mod_) => mod_.to_tokens(tokens),
             MockItem::Struct(s) => s.to_tokens(tokens)
         }
     }
 }
 
+/// A mock for a type alias of a trait object.
+///
+/// The mock struct must be generated separately, by mocking the trait itself.
+/// This just names it after the alias, and checks that it satisfies the
+/// alias's other bounds, like `Send`.
+pub(crate) struct MockItemAlias(ItemType);
+
+impl ToTokens for MockItemAlias {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let item_type = &self.0;
+        let tto = match item_type.ty.as_ref() {
+            Type::TraitObject(tto) => tto,
+            ty => {
+                compile_error(ty.span(),
+                    "automock can only mock aliases of trait objects, like `type Foo = dyn Bar + Send;`");
+                return;
+            }
+        };
+        let principal = tto.bounds.iter()
+            .filter_map(|tpb| match tpb {
+                TypeParamBound::Trait(tb) => Some(&tb.path),
+                _ => None
+            }).find(|path| {
+                let ident = &path.segments.last().unwrap().ident;
+                !AUTO_TRAITS.iter().any(|at| ident == at)
+            });
+        let mut mock_path = if let Some(path) = principal {
+            path.clone()
+        } else {
+            compile_error(tto.span(),
+                "automock requires a trait object with a non-auto trait");
+            return;
+        };
+        let last_seg = mock_path.segments.last_mut().unwrap();
+        last_seg.ident = gen_mock_ident(&last_seg.ident);
+        // Associated types are part of the mock struct's definition, not its
+        // generic parameters.
+        if let PathArguments::AngleBracketed(abga) = &mut last_seg.arguments {
+            abga.args = abga.args.iter()
+                .filter(|ga| !matches!(ga, GenericArgument::Binding(_)))
+                .cloned()
+                .collect();
+            if abga.args.is_empty() {
+                last_seg.arguments = PathArguments::None;
+            }
+        }
+
+        let attrs = item_type.attrs.iter()
+            .filter(|attr| attr.path.is_ident("cfg"))
+            .collect::<Vec<_>>();
+        let docstr = format!("Mock version of [`{}`]", item_type.ident);
+        let vis = &item_type.vis;
+        let mock_ident = gen_mock_ident(&item_type.ident);
+        let generics = &item_type.generics;
+        quote!(
+            #(#attrs)*
+            #[doc = #docstr]
+            #vis type #mock_ident #generics = #mock_path;
+        ).to_tokens(tokens);
+        if generics.params.is_empty() {
+            let bounds = &tto.bounds;
+            quote!(
+                #(#attrs)*
+                const _: () = {
+                    fn __mockall_assert_bounds<T: ?Sized + #bounds>() {}
+                    let _ = __mockall_assert_bounds::<#mock_ident>;
+                };
+            ).to_tokens(tokens);
+        }
+    }
+}
+
+/// Standalone mock functions, created by [`mock!`] outside of any struct.
+///
+/// Each function `foo` gets a hidden module to hold its expectations, from
+/// which the mock function is exported as `mock_foo` and its context function
+/// as `mock_foo_context`.
+pub(crate) struct MockItemFunctions(Vec<(Visibility, MockFunction)>);
+
+impl From<MockableFunctions> for MockItemFunctions {
+    fn from(fns: MockableFunctions) -> MockItemFunctions {
+        MockItemFunctions(fns.0.into_iter()
+            .map(|f| {
+                let mock_ident = format_ident!("mock_{}", f.sig.ident);
+                let mf = mock_function::Builder::new(&f.sig, &f.vis)
+                    .attrs(&f.attrs)
+                    .parent(&mock_ident)
+                    .levels(2)
+                    .call_levels(1)
+                    .build();
+                (f.vis, mf)
+            }).collect())
+    }
+}
+
+impl ToTokens for MockItemFunctions {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        for (vis, f) in self.0.iter() {
+            let name = f.name();
+            let cfgs = f.attrs.iter()
+                .filter(|attr| attr.path.is_ident("cfg"))
+                .collect::<Vec<_>>();
+            let modname = format_ident!("__mock_{}", name);
+            let mock_ident = format_ident!("mock_{}", name);
+            let ctx_ident = format_ident!("{}_context", name);
+            let mock_ctx_ident = format_ident!("mock_{}_context", name);
+            let call = f.call(None);
+            let ctx_fn = f.context_fn(None);
+            let priv_mod = f.priv_module();
+            quote!(
+                #(#cfgs)*
+                #[allow(missing_docs)]
+                #[allow(unused_imports)]
+                #[doc(hidden)]
+                mod #modname {
+                    use super::*;
+                    #priv_mod
+                    #call
+                    #ctx_fn
+                }
+                #(#cfgs)*
+                #[allow(unused_imports)]
+                #vis use #modname::{
+                    #name as #mock_ident,
+                    #ctx_ident as #mock_ctx_ident
+                };
+            ).to_tokens(tokens);
+        }
+    }
+}
+
+/// Generate a function that exports a mock foreign function under the
+/// original function's symbol name, so that calls through the original
+/// `extern` declaration will reach the mock.
+fn no_mangle_fn(abi: &Abi, f: &ForeignItemFn) -> TokenStream {
+    if let Some(variadic) = &f.sig.variadic {
+        compile_error(variadic.span(),
+            "no_mangle does not support variadic functions");
+        return TokenStream::new();
+    }
+    let ident = &f.sig.ident;
+    let export_name = f.attrs.iter()
+        .filter(|attr| attr.path.is_ident("link_name"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue{lit: Lit::Str(s), ..})) =>
+                Some(s.value()),
+            _ => None
+        }).next()
+        .unwrap_or_else(|| ident.to_string());
+    let cfgs = f.attrs.iter()
+        .filter(|attr| attr.path.is_ident("cfg"));
+    let fn_ident = format_ident!("__mockall_no_mangle_{}", ident);
+    let argnames = (0..f.sig.inputs.len())
+        .map(|i| format_ident!("__mockall_arg{}", i))
+        .collect::<Vec<_>>();
+    let argtys = f.sig.inputs.iter()
+        .map(|fa| match fa {
+            FnArg::Typed(pt) => pt.ty.as_ref().clone(),
+            FnArg::Receiver(_) => unreachable!()
+        });
+    let output = &f.sig.output;
+    quote!(
+        #(#cfgs)*
+        #[doc(hidden)]
+        #[export_name = #export_name]
+        #abi fn #fn_ident(#(#argnames: #argtys),*) #output {
+            unsafe { #ident(#(#argnames),*) }
+        }
+    )
+}
+
 enum MockItemContent {
     Fn(Box<MockFunction>),
     Tokens(TokenStream)
@@ -59,6 +240,23 @@ impl From<MockableModule> for MockItemModule {
                 {
                     // Ignore
                 },
+                Item::Enum(ItemEnum{vis, ident, ..})
+                    | Item::Struct(ItemStruct{vis, ident, ..})
+                    | Item::Trait(ItemTrait{vis, ident, ..})
+                    | Item::Union(ItemUnion{vis, ident, ..}) =>
+                {
+                    // Types and traits stay in the original module.  Import
+                    // them, if possible, so the mock functions can use them.
+                    // Traits are mocked within the original module, too.
+                    if let (Some(orig), false) =
+                        (&orig_ident, matches!(vis, Visibility::Inherited))
+                    {
+                        content.push(MockItemContent::Tokens(quote!(
+                            #[allow(unused_imports)]
+                            use super::#orig::#ident;
+                        )));
+                    }
+                },
                 Item::Static(is) => {
                     content.push(
                         MockItemContent::Tokens(is.into_token_stream())
@@ -75,6 +273,7 @@ impl From<MockableModule> for MockItemModule {
                         .parent(&mock_ident)
                         .levels(1)
                         .call_levels(0)
+                        .thread_local(mod_.thread_local)
                         .build();
                     content.push(MockItemContent::Fn(Box::new(mf)));
                 },
@@ -90,18 +289,21 @@ impl From<MockableModule> for MockItemModule {
                                 .parent(&mock_ident)
                                 .levels(1)
                                 .call_levels(0)
+                                .thread_local(mod_.thread_local)
                                 .build();
                             content.push(MockItemContent::Fn(Box::new(mf)));
+                            if mod_.no_mangle {
+                                content.push(MockItemContent::Tokens(
+                                    no_mangle_fn(&ifm.abi, &f)
+                                ));
+                            }
                         } else {
                             compile_error(item.span(),
                                 "Mockall does not yet support  this type in this position.  Please open an issue with your use case at https://github.com/asomers/mockall");
                         }
                     }
                 },
-                Item::Mod(_)
-                    | Item::Struct(_) | Item::Enum(_)
-                    | Item::Union(_) | Item::Trait(_) =>
-                {
+                Item::Mod(_) => {
                     compile_error(span,
                         "Mockall does not yet support deriving nested mocks");
                 },
@@ -13,6 +13,7 @@ fn phantom_default_inits(generics: &Generics) -> Vec<TokenStream> {
     generics.params
     .iter()
     .enumerate()
+    .filter(|(_, param)| !matches!(param, GenericParam::Const(_)))
     .map(|(count, _param)| {
         let phident = format_ident!("_t{}", count);
         quote!(#phident: ::std::marker::PhantomData)
@@ -28,10 +29,6 @@ fn phantom_fields(generics: &Generics) -> Vec<TokenStream> {
         let phident = format_ident!("_t{}", count);
         match param {
             syn::GenericParam::Lifetime(l) => {
-                if !l.bounds.is_empty() {
-                    compile_error(l.bounds.span(),
-                        "#automock does not yet support lifetime bounds on structs");
-                }
                 let lifetime = &l.lifetime;
                 Some(
                 quote!(#phident: ::std::marker::PhantomData<&#lifetime ()>)
@@ -43,11 +40,8 @@ fn phantom_fields(generics: &Generics) -> Vec<TokenStream> {
                 quote!(#phident: ::std::marker::PhantomData<#ty>)
                 )
             },
-            syn::GenericParam::Const(_) => {
-                compile_error(param.span(),
-                    "#automock does not yet support generic constants");
-                None
-            }
+            // Const parameters are always used, so they need no PhantomData
+            syn::GenericParam::Const(_) => None
         }
     }).collect()
 }
@@ -74,6 +68,68 @@ fn unique_trait_iter<'a, I: Iterator<Item = &'a MockTrait>>(i: I)
     })
 }
 
+/// Name of the mock struct's field that holds a spy's expectations for a trait
+fn spy_field(mt: &MockTrait) -> Ident {
+    format_ident!("{}_spy", mt.ss_name())
+}
+
+/// If two traits, or a trait and the struct itself, have methods of the same
+/// name, then qualify the trait methods' `expect_*` and `*_context` methods
+/// with the trait's name so they won't conflict.
+///
+/// If the conflicting traits are instantiations of the same generic trait,
+/// like `From<u32>` and `From<String>`, then qualify them with the trait's
+/// generic arguments too, like `expect_From_u32_from`.
+fn qualify_duplicate_names(methods: &Methods, traits: &mut [MockTrait]) {
+    let trait_ident = |mt: &MockTrait| -> Ident {
+        mt.trait_path.segments.last().unwrap().ident.clone()
+    };
+    // Decide how to qualify each trait method before changing any of them
+    let mut qualifiers = Vec::new();
+    for mt in traits.iter() {
+        let ident = trait_ident(mt);
+        for meth in mt.methods.iter() {
+            let in_struct = methods.0.iter()
+                .any(|m| m.name() == meth.name());
+            // Other traits whose expectation methods would be defined on the
+            // same type.  Traits implemented for different instantiations of
+            // a generic struct don't conflict.
+            let rivals = traits.iter()
+                .filter(|mt2| mt2.trait_path != mt.trait_path &&
+                    mt2.self_path.arguments == mt.self_path.arguments &&
+                    mt2.methods.iter().any(|m| m.name() == meth.name()))
+                .collect::<Vec<_>>();
+            qualifiers.push(if rivals.iter().any(|mt2| trait_ident(mt2) == ident)
+            {
+                Some(mangle_path(&mt.trait_path))
+            } else if in_struct || !rivals.is_empty() {
+                Some(ident.clone())
+            } else {
+                None
+            });
+        }
+    }
+    let mut qualifiers = qualifiers.into_iter();
+    for mt in traits.iter_mut() {
+        for meth in mt.methods.iter_mut() {
+            if let Some(q) = qualifiers.next().unwrap() {
+                meth.qualify_expect_name(&q);
+            }
+        }
+    }
+}
+
+/// Turn a trait path's last segment, including its generic arguments, into an
+/// identifier, like `From<Vec<u8>>` => `From_Vec_u8`
+fn mangle_path(path: &Path) -> Ident {
+    let seg = path.segments.last().unwrap();
+    let s = seg.to_token_stream().to_string();
+    let words = s.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>();
+    format_ident!("{}", words.join("_"))
+}
+
 /// A collection of methods defined in one spot
 struct Methods(Vec<MockFunction>);
 
@@ -91,6 +147,20 @@ impl Methods {
             .collect::<Vec<_>>()
     }
 
+    fn in_scenarios(&self) -> Vec<impl ToTokens> {
+        self.0.iter()
+            .filter(|meth| !meth.is_static())
+            .map(|meth| meth.in_scenario())
+            .collect::<Vec<_>>()
+    }
+
+    fn try_verifies(&self) -> Vec<impl ToTokens> {
+        self.0.iter()
+            .filter(|meth| !meth.is_static())
+            .map(|meth| meth.try_verify())
+            .collect::<Vec<_>>()
+    }
+
     /// Return a fragment of code to initialize struct fields during default()
     fn default_inits(&self) -> Vec<TokenStream> {
         self.0.iter()
@@ -121,6 +191,8 @@ impl Methods {
 pub(crate) struct MockItemStruct {
     attrs: Vec<Attribute>,
     consts: Vec<ImplItemConst>,
+    /// Inherent methods with fixed implementations
+    fakes: Vec<ImplItemMethod>,
     generics: Generics,
     /// Should Mockall generate a Debug implementation?
     auto_debug: bool,
@@ -131,6 +203,8 @@ pub(crate) struct MockItemStruct {
     /// Name of the overall module that holds all of the mock stuff
     modname: Ident,
     name: Ident,
+    /// Will Mockall generate a `spy` constructor?
+    spy: bool,
     /// Is this a whole MockStruct or just a substructure for a trait impl?
     traits: Vec<MockTrait>,
     vis: Visibility,
@@ -156,8 +230,20 @@ impl MockItemStruct {
         }
     }
 
+    /// Does the mock struct have a method, mocked or fake, inherent or from a
+    /// trait, with this name?  If so, Mockall mustn't generate one of its own.
+    /// An inherent method would conflict with another inherent method, or
+    /// shadow a trait method.
+    fn has_method(&self, name: &str) -> bool {
+        self.methods.0.iter().any(|meth| meth.name() == name) ||
+            self.fakes.iter().any(|meth| meth.sig.ident == name) ||
+            self.traits.iter().any(|mt|
+                mt.methods.iter().any(|meth| meth.name() == name) ||
+                mt.fakes.iter().any(|meth| meth.sig.ident == name))
+    }
+
     fn new_method(&self) -> impl ToTokens {
-        if self.has_new {
+        let new = if self.has_new {
             TokenStream::new()
         } else {
             quote!(
@@ -172,7 +258,69 @@ impl MockItemStruct {
                     Self::default()
                 }
             )
+        };
+        let new_nice = if self.has_method("new_nice") {
+            TokenStream::new()
+        } else {
+            quote!(
+                /// Create a new "nice" mock object with no expectations.
+                ///
+                /// When a nice mock's method is called with arguments that no
+                /// expectation matches, it returns the default value of the
+                /// method's return type instead of panicking.  It still panics
+                /// if the return type doesn't implement `Default`, or if the
+                /// method is static or returns a reference.  Expectations that
+                /// are set are verified as usual.
+                pub fn new_nice() -> Self {
+                    let mut __mockall_mock = Self::default();
+                    __mockall_mock.__mockall_nice = true;
+                    __mockall_mock
+                }
+            )
+        };
+        let new_recording = if self.has_method("new_recording") {
+            TokenStream::new()
+        } else {
+            quote!(
+                /// Create a new mock object that records unexpected calls.
+                ///
+                /// Like a nice mock, when a method is called with arguments
+                /// that no expectation matches, it returns the default value
+                /// of the method's return type.  But it also records the call.
+                /// Recorded calls are reported all together by `checkpoint` or
+                /// when the mock object drops, unless they were first removed
+                /// with `take_unexpected`.
+                ///
+                /// Only calls that a nice mock could handle are recorded.
+                /// Calls to static methods, to methods that return references
+                /// or types that don't implement `Default`, and calls that
+                /// exceed an expectation's call count still panic immediately.
+                pub fn new_recording() -> Self {
+                    let mut __mockall_mock = Self::default();
+                    __mockall_mock.__mockall_unexpected =
+                        ::mockall::UnexpectedCalls::recording();
+                    __mockall_mock
+                }
+            )
+        };
+        quote!(#new #new_nice #new_recording)
+    }
+
+    /// Generate the `take_unexpected` method, unless the mock already has one.
+    fn take_unexpected_method(&self) -> TokenStream {
+        if self.has_method("take_unexpected") {
+            return TokenStream::new();
         }
+        quote!(
+            /// Remove and return descriptions of the calls that no
+            /// expectation matched, for a mock object created with
+            /// `new_recording`.
+            pub fn take_unexpected(&mut self)
+                -> ::std::vec::Vec<::std::string::String>
+            {
+                self.__mockall_unexpected.take()
+            }
+        )
     }
 
     fn phantom_default_inits(&self) -> Vec<TokenStream> {
@@ -182,6 +330,160 @@ impl MockItemStruct {
     fn phantom_fields(&self) -> Vec<TokenStream> {
         phantom_fields(&self.generics)
     }
+
+    /// Generate a compile-time assertion that the mock struct is `Send` and
+    /// `Sync`, if it always should be.
+    ///
+    /// That's true unless it's generic, or some method returns a non-'static
+    /// reference to a type that might not be `Send` or `Sync`.
+    fn send_sync_assertion(&self) -> TokenStream {
+        if !self.generics.params.is_empty() ||
+            self.generics.where_clause.is_some() ||
+            self.methods.0.iter().any(MockFunction::returns_ref) ||
+            self.traits.iter().any(|mt| !mt.generics.params.is_empty() ||
+                mt.methods.iter().any(MockFunction::returns_ref))
+        {
+            return TokenStream::new();
+        }
+        let cfgs = self.attrs.iter()
+            .filter(|attr| attr.path.is_ident("cfg"));
+        let struct_name = &self.name;
+        quote!(
+            #(#cfgs)*
+            const _: fn() = ::mockall::assert_send_sync::<#struct_name>;
+        )
+    }
+
+    /// The name of the mock's builder struct, unless the mock already has a
+    /// method named `builder`.  The name is mangled so it can't collide with
+    /// another mock struct, like the one for a trait named `FooBuilder`.
+    fn builder_name(&self) -> Option<Ident> {
+        if self.has_method("builder") {
+            None
+        } else {
+            Some(format_ident!("__mockall_{}Builder", self.name))
+        }
+    }
+
+    /// Generate the builder struct, which sets many expectations in a single
+    /// statement.
+    fn builder(&self) -> TokenStream {
+        let builder_name = if let Some(name) = self.builder_name() {
+            name
+        } else {
+            return TokenStream::new();
+        };
+        let cfgs = self.attrs.iter()
+            .filter(|attr| attr.path.is_ident("cfg"))
+            .collect::<Vec<_>>();
+        let (ig, tg, wc) = self.generics.split_for_impl();
+        let modname = &self.modname;
+        let steps = self.methods.0.iter()
+            .map(|meth| meth.builder_step(modname, None))
+            .collect::<Vec<_>>();
+        let struct_name = &self.name;
+        let vis = &self.vis;
+        let docstr = format!("Builds a [`{}`](struct.{}.html) with all of its expectations set in a single statement.",
+            struct_name, struct_name);
+        quote!(
+            #(#cfgs)*
+            #[doc = #docstr]
+            #[allow(non_camel_case_types)]
+            #[allow(non_snake_case)]
+            #vis struct #builder_name #ig (#struct_name #tg) #wc;
+            #(#cfgs)*
+            #[allow(non_snake_case)]
+            impl #ig #builder_name #tg #wc {
+                #(#steps)*
+                /// Finish building the mock object.
+                pub fn build(self) -> #struct_name #tg {
+                    self.0
+                }
+            }
+            #(#cfgs)*
+            impl #ig #struct_name #tg #wc {
+                /// Start building a mock object.  The builder has an
+                /// `expect_*` method for each of the mock's methods, which
+                /// takes a closure to configure the new expectation.
+                pub fn builder() -> #builder_name #tg {
+                    #builder_name(Self::default())
+                }
+            }
+        )
+    }
+
+    /// Can the mock struct have a `spy` constructor?
+    ///
+    /// Only non-generic mock structs of non-generic trait impls can have one,
+    /// and only if at least one trait method can be forwarded.
+    fn can_spy(&self) -> bool {
+        let has_cfg = self.traits.iter()
+            .any(|mt| !AttrFormatter::new(&mt.attrs)
+                .async_trait(false)
+                .doc(false)
+                .format()
+                .is_empty());
+        !self.has_method("spy") && !has_cfg &&
+            self.generics.params.is_empty() &&
+            self.generics.where_clause.is_none() &&
+            !self.traits.iter().any(|mt| !mt.generics.params.is_empty() ||
+                mt.types.iter().any(|t| !t.generics.params.is_empty())) &&
+            self.traits.iter().any(|mt|
+                mt.methods.iter().any(MockFunction::is_spyable))
+    }
+
+    /// Generate the `spy` constructor, if the mock struct has one.
+    fn spy_method(&self) -> TokenStream {
+        if !self.spy {
+            return TokenStream::new();
+        }
+        let traits = unique_trait_iter(self.traits.iter())
+            .collect::<Vec<_>>();
+        let inits = traits.iter()
+            .flat_map(|mt| mt.methods.iter()
+                .filter_map(move |meth| meth.spy_init(&mt.trait_path)))
+            .collect::<Vec<_>>();
+        let bounds = traits.iter()
+            .map(|mt| {
+                // Bind the real object's associated types to the mock's
+                let mut path = mt.trait_path.clone();
+                let last = path.segments.last_mut().unwrap();
+                let bindings = mt.types.iter()
+                    .map(|t| {
+                        let ident = &t.ident;
+                        let ty = &t.ty;
+                        quote!(#ident = #ty)
+                    });
+                if let PathArguments::AngleBracketed(abga) = &mut last.arguments
+                {
+                    for b in bindings {
+                        abga.args.push(parse2(b).unwrap());
+                    }
+                } else if !mt.types.is_empty() {
+                    last.arguments = PathArguments::AngleBracketed(
+                        parse2(quote!(<#(#bindings),*>)).unwrap());
+                }
+                path
+            }).collect::<Vec<_>>();
+        quote!(
+            /// Create a new mock object that spies on a real one.
+            ///
+            /// Calls that no expectation matches are forwarded to `real`,
+            /// whose results are returned.  Expectations can still be set to
+            /// intercept or verify particular calls.  Only trait methods that
+            /// take `&self` or `&mut self`, aren't generic, and return owned
+            /// values can be forwarded.
+            pub fn spy<__MockallReal>(real: __MockallReal) -> Self
+                where __MockallReal: #(#bounds +)* Send + 'static
+            {
+                let __mockall_real = ::std::sync::Arc::new(
+                    ::std::sync::Mutex::new(real));
+                let mut __mockall_mock = Self::default();
+                #(#inits)*
+                __mockall_mock
+            }
+        )
+    }
 }
 
 impl From<MockableStruct> for MockItemStruct {
@@ -191,7 +493,10 @@ impl From<MockableStruct> for MockItemStruct {
         let generics = mockable.generics.clone();
         let struct_name = &mockable.name;
         let vis = mockable.vis;
+        let expect_vis = mockable.expect_vis;
+        let thread_local = mockable.thread_local;
         let has_new = mockable.methods.iter()
+            .chain(mockable.fakes.iter())
             .any(|meth| meth.sig.ident == "new") ||
             mockable.impls.iter()
             .any(|impl_|
@@ -207,6 +512,8 @@ impl From<MockableStruct> for MockItemStruct {
             .map(|meth|
                 mock_function::Builder::new(&meth.sig, &meth.vis)
                     .attrs(&meth.attrs)
+                    .expect_vis(expect_vis.as_ref())
+                    .thread_local(thread_local)
                     .struct_(struct_name)
                     .struct_generics(&generics)
                     .levels(2)
@@ -214,22 +521,36 @@ impl From<MockableStruct> for MockItemStruct {
                     .build()
             ).collect::<Vec<_>>());
         let structname = &mockable.name;
-        let traits = mockable.impls.into_iter()
-            .map(|i| MockTrait::new(structname, &generics, i, &vis))
-            .collect();
+        let mut traits = mockable.impls.into_iter()
+            .map(|i| MockTrait::new(structname, &generics, i, &vis,
+                                    expect_vis.as_ref(), thread_local))
+            .collect::<Vec<_>>();
+        qualify_duplicate_names(&methods, &mut traits);
 
-        MockItemStruct {
+        let mut mis = MockItemStruct {
             attrs: mockable.attrs,
             auto_debug,
             consts: mockable.consts,
+            fakes: mockable.fakes,
             generics,
             has_new,
             methods,
             modname,
             name: mockable.name,
+            spy: false,
             traits,
             vis
+        };
+        if mis.can_spy() {
+            mis.spy = true;
+            for mt in mis.traits.iter_mut() {
+                let spyfieldname = spy_field(mt);
+                for meth in mt.methods.iter_mut() {
+                    meth.spy(&spyfieldname);
+                }
+            }
         }
+        mis
     }
 }
 
@@ -239,6 +560,7 @@ impl ToTokens for MockItemStruct {
             .async_trait(false)
             .format();
         let consts = &self.consts;
+        let fakes = &self.fakes;
         let debug_impl = self.debug_impl();
         let struct_name = &self.name;
         let (ig, tg, wc) = self.generics.split_for_impl();
@@ -248,14 +570,39 @@ impl ToTokens for MockItemStruct {
             .collect::<Vec<_>>();
         let contexts = self.methods.0.iter()
             .filter(|meth| meth.is_static())
-            .map(|meth| meth.context_fn(Some(modname)))
-            .collect::<Vec<_>>();
+            .map(|meth| {
+                let context_fn = meth.context_fn(Some(modname));
+                let checkpoint_fn = meth.checkpoint_fn(Some(modname));
+                quote!(#context_fn #checkpoint_fn)
+            }).collect::<Vec<_>>();
         let expects = self.methods.0.iter()
             .filter(|meth| !meth.is_static())
             .map(|meth| meth.expect(modname, None))
             .collect::<Vec<_>>();
         let method_checkpoints = self.methods.checkpoints();
+        let method_in_scenarios = self.methods.in_scenarios();
+        let method_try_verifies = self.methods.try_verifies();
         let new_method = self.new_method();
+        let send_sync_assertion = self.send_sync_assertion();
+        let spy_method = self.spy_method();
+        let take_unexpected_method = self.take_unexpected_method();
+        let try_verify_method = if self.has_method("try_verify") {
+            TokenStream::new()
+        } else {
+            quote!(
+                /// Check whether every current expectation for every method
+                /// has been called at least as many times as required,
+                /// without panicking.  The expectations are left in place, so
+                /// they will still be verified when the mock object is
+                /// dropped or checkpointed.  Static methods' expectations, and
+                /// those in a `Scenario`, aren't checked.
+                pub fn try_verify(&self) -> ::std::result::Result<(),
+                    ::std::vec::Vec<::mockall::ExpectationError>>
+                {
+                    self.__mockall_try_verify()
+                }
+            )
+        };
         let priv_mods = self.methods.priv_mods();
         let substructs = unique_trait_iter(self.traits.iter())
             .map(|trait_| {
@@ -264,6 +611,11 @@ impl ToTokens for MockItemStruct {
                     generics: self.generics.clone(),
                     fieldname: format_ident!("{}_expectations",
                                              trait_.ss_name()),
+                    spyfieldname: if self.spy {
+                        Some(spy_field(trait_))
+                    } else {
+                        None
+                    },
                     methods: Methods(trait_.methods.clone()),
                     modname: format_ident!("{}_{}", &self.modname,
                                            trait_.ss_name()),
@@ -280,6 +632,45 @@ impl ToTokens for MockItemStruct {
                 let fieldname = &ss.fieldname;
                 quote!(#(#attrs)* self.#fieldname.checkpoint();)
             }).collect::<Vec<_>>();
+        let substruct_in_scenarios = substructs.iter()
+            .filter(|ss| !ss.all_static())
+            .map(|ss| {
+                let attrs = AttrFormatter::new(&ss.attrs)
+                    .async_trait(false)
+                    .doc(false)
+                    .format();
+                let fieldname = &ss.fieldname;
+                quote!(#(#attrs)* self.#fieldname.in_scenario(__mockall_scenario);)
+            }).collect::<Vec<_>>();
+        let in_scenario_method = if self.has_method("in_scenario") {
+            TokenStream::new()
+        } else {
+            quote!(
+                /// Hand verification of every current expectation for every
+                /// method over to `scenario`, as if each had been added to it
+                /// with `in_scenario`.  The expectations won't be verified
+                /// when the mock object is dropped, so this works even for
+                /// mocks consumed by methods that take `self` by value.
+                /// Static methods' expectations, and expectations set later,
+                /// aren't affected.
+                pub fn in_scenario(&mut self,
+                    __mockall_scenario: &mut ::mockall::Scenario)
+                {
+                    #(#substruct_in_scenarios)*
+                    #(#method_in_scenarios)*
+                }
+            )
+        };
+        let substruct_try_verifies = substructs.iter()
+            .filter(|ss| !ss.all_static())
+            .map(|ss| {
+                let attrs = AttrFormatter::new(&ss.attrs)
+                    .async_trait(false)
+                    .doc(false)
+                    .format();
+                let fieldname = &ss.fieldname;
+                quote!(#(#attrs)* self.#fieldname.try_verify(__mockall_errors);)
+            }).collect::<Vec<_>>();
         let mut field_definitions = substructs.iter()
             .filter(|ss| !ss.all_static())
             .map(|ss| {
@@ -289,10 +680,23 @@ impl ToTokens for MockItemStruct {
                     .format();
                 let fieldname = &ss.fieldname;
                 let tyname = &ss.name;
-                quote!(#(#attrs)* #fieldname: #tyname #tg)
+                let spyfield = ss.spyfieldname.as_ref().map(|spyfieldname|
+                    quote!(
+                        // Expectations that forward calls to a spy's real
+                        // object.  Unused if none of the trait's methods can
+                        // be forwarded.
+                        , #(#attrs)* #[allow(dead_code)]
+                        #spyfieldname: #tyname #tg
+                    ));
+                quote!(#(#attrs)* #fieldname: #tyname #tg #spyfield)
             }).collect::<Vec<_>>();
         field_definitions.extend(self.methods.field_definitions(modname));
         field_definitions.extend(self.phantom_fields());
+        // Was this mock created by new_nice?
+        field_definitions.push(quote!(#[allow(dead_code)] __mockall_nice: bool));
+        field_definitions.push(quote!(
+            __mockall_unexpected: ::mockall::UnexpectedCalls
+        ));
         let mut default_inits = substructs.iter()
             .filter(|ss| !ss.all_static())
             .map(|ss| {
@@ -301,15 +705,23 @@ impl ToTokens for MockItemStruct {
                     .doc(false)
                     .format();
                 let fieldname = &ss.fieldname;
-                quote!(#(#attrs)* #fieldname: Default::default())
+                let spyinit = ss.spyfieldname.as_ref().map(|spyfieldname|
+                    quote!(, #(#attrs)* #spyfieldname: Default::default()));
+                quote!(#(#attrs)* #fieldname: Default::default() #spyinit)
             }).collect::<Vec<_>>();
         default_inits.extend(self.methods.default_inits());
         default_inits.extend(self.phantom_default_inits());
+        default_inits.push(quote!(__mockall_nice: false));
+        default_inits.push(quote!(
+            __mockall_unexpected: ::mockall::UnexpectedCalls::default()
+        ));
+        let builder_name = self.builder_name();
+        let builder = self.builder();
         let trait_impls = self.traits.iter()
             .map(|trait_| {
                 let modname = format_ident!("{}_{}", &self.modname,
                                             trait_.ss_name());
-                trait_.trait_impl(&modname)
+                trait_.trait_impl(&modname, builder_name.as_ref())
             }).collect::<Vec<_>>();
         let vis = &self.vis;
         quote!(
@@ -337,20 +749,68 @@ impl ToTokens for MockItemStruct {
                 }
             }
             #(#substructs)*
+            impl #ig ::std::ops::Drop for #struct_name #tg #wc {
+                fn drop(&mut self) {
+                    // Verify everything at once, rather than letting each
+                    // expectation panic on its own
+                    self.__mockall_verify();
+                }
+            }
             impl #ig #struct_name #tg #wc {
                 #(#consts)*
+                #(#fakes)*
                 #(#calls)*
                 #(#contexts)*
                 #(#expects)*
                 /// Validate that all current expectations for all methods have
-                /// been satisfied, and discard them.
+                /// been satisfied, and discard them.  Also panic if any
+                /// unexpected calls were recorded.  A single panic message
+                /// reports every failure.
                 pub fn checkpoint(&mut self) {
-                    #(#substruct_expectations)*
-                    #(#method_checkpoints)*
+                    self.__mockall_verify();
+                }
+                /// Report every unsatisfied expectation and unexpected call,
+                /// if there are any, and clear the expectations.
+                fn __mockall_verify(&mut self) {
+                    let __mockall_errors = self.__mockall_try_verify()
+                        .err()
+                        .unwrap_or_default();
+                    ::mockall::report_failures(__mockall_errors,
+                        self.__mockall_unexpected.take());
+                    // If a failure handler took the report, clear the
+                    // expectations without reporting them again.
+                    ::mockall::suppress_verification(|| {
+                        #(#substruct_expectations)*
+                        #(#method_checkpoints)*
+                    });
+                }
+                #in_scenario_method
+                #try_verify_method
+                /// Check every current expectation for every method, without
+                /// panicking.
+                fn __mockall_try_verify(&self) -> ::std::result::Result<(),
+                    ::std::vec::Vec<::mockall::ExpectationError>>
+                {
+                    let mut __mockall_errors = ::std::vec::Vec::new();
+                    {
+                        #[allow(unused_variables)]
+                        let __mockall_errors = &mut __mockall_errors;
+                        #(#substruct_try_verifies)*
+                        #(#method_try_verifies)*
+                    }
+                    if __mockall_errors.is_empty() {
+                        ::std::result::Result::Ok(())
+                    } else {
+                        ::std::result::Result::Err(__mockall_errors)
+                    }
                 }
+                #take_unexpected_method
                 #new_method
+                #spy_method
             }
+            #builder
             #(#trait_impls)*
+            #send_sync_assertion
         ).to_tokens(tokens);
     }
 }
@@ -365,6 +825,9 @@ pub(crate) struct MockItemTraitImpl {
     name: Ident,
     /// Name of the field of this type in the parent's structure
     fieldname: Ident,
+    /// Name of the field of this type that holds a spy's expectations, if the
+    /// mock struct has a `spy` constructor
+    spyfieldname: Option<Ident>,
 }
 
 impl MockItemTraitImpl {
@@ -392,6 +855,8 @@ impl ToTokens for MockItemTraitImpl {
         let (ig, tg, wc) = self.generics.split_for_impl();
         let modname = &self.modname;
         let method_checkpoints = self.methods.checkpoints();
+        let method_in_scenarios = self.methods.in_scenarios();
+        let method_try_verifies = self.methods.try_verifies();
         let mut default_inits = self.methods.default_inits();
         default_inits.extend(self.phantom_default_inits());
         let mut field_definitions = self.methods.field_definitions(modname);
@@ -428,6 +893,21 @@ impl ToTokens for MockItemTraitImpl {
                 pub fn checkpoint(&mut self) {
                     #(#method_checkpoints)*
                 }
+                /// Hand verification of every current expectation for every
+                /// method over to `scenario`.
+                pub fn in_scenario(&mut self,
+                    __mockall_scenario: &mut ::mockall::Scenario)
+                {
+                    #(#method_in_scenarios)*
+                }
+                /// Record every current expectation that hasn't yet been
+                /// called enough times.
+                pub fn try_verify(&self,
+                    __mockall_errors:
+                        &mut ::std::vec::Vec<::mockall::ExpectationError>)
+                {
+                    #(#method_try_verifies)*
+                }
             }
         ).to_tokens(tokens);
     }
@@ -12,13 +12,17 @@ use syn::{
 
 use crate::{
     AttrFormatter,
+    FORMAT_TRAITS,
     mock_function::{self, MockFunction},
-    compile_error
+    compile_error,
+    is_fake
 };
 
 pub(crate) struct MockTrait {
     pub attrs: Vec<Attribute>,
     pub consts: Vec<ImplItemConst>,
+    /// Methods with fixed implementations: fakes and stubs
+    pub fakes: Vec<ImplItemMethod>,
     pub generics: Generics,
     pub methods: Vec<MockFunction>,
     /// Internally-used name of the trait used.
@@ -27,7 +31,7 @@ pub(crate) struct MockTrait {
     pub trait_path: Path,
     /// Path on which the trait is implemented.  Usually will be the same as
     /// structname, but might include concrete generic parameters.
-    self_path: PathSegment,
+    pub self_path: PathSegment,
     pub types: Vec<ImplItemType>,
     pub unsafety: Option<Token![unsafe]>
 }
@@ -59,12 +63,19 @@ impl MockTrait {
     /// * `struct_generics` - Generics of the parent structure
     /// * `impl_`  -    Mockable ItemImpl for a trait
     /// * `vis`     -   Visibility of the struct
+    /// * `expect_vis` - Visibility of the `expect_*` methods, if different
+    ///   from the struct's
+    /// * `thread_local` - Give each thread its own expectations for static
+    ///   methods
     pub fn new(structname: &Ident,
                struct_generics: &Generics,
                impl_: ItemImpl,
-               vis: &Visibility) -> Self
+               vis: &Visibility,
+               expect_vis: Option<&Visibility>,
+               thread_local: bool) -> Self
     {
         let mut consts = Vec::new();
+        let mut fakes = Vec::new();
         let mut methods = Vec::new();
         let mut types = Vec::new();
         let trait_path = if let Some((_, path, _)) = impl_.trait_ {
@@ -89,9 +100,30 @@ impl MockTrait {
                 ImplItem::Const(iic) => {
                     consts.push(iic);
                 },
+                ImplItem::Method(iim) if iim.attrs.iter().any(|attr|
+                    attr.path.segments.last()
+                    .map(|ps| ps.ident == "keep_default")
+                    .unwrap_or(false)) =>
+                {
+                    // Don't mock this method.  The mock struct will inherit
+                    // the trait's default implementation instead.
+                },
+                ImplItem::Method(iim) if is_fake(&iim) => {
+                    // Don't mock this method either.  Use its body as-is.
+                    fakes.push(iim);
+                },
                 ImplItem::Method(iim) => {
+                    // A formatting method declared without its Formatter, as
+                    // by the `impl Display for Foo;` shorthand
+                    let format = FORMAT_TRAITS.iter().any(|t|
+                            trait_path.segments.last().unwrap().ident == t) &&
+                        iim.sig.ident == "fmt" &&
+                        iim.sig.inputs.len() == 1;
                     let mf = mock_function::Builder::new(&iim.sig, vis)
                         .attrs(&iim.attrs)
+                        .expect_vis(expect_vis)
+                        .format(format)
+                        .thread_local(thread_local)
                         .levels(2)
                         .call_levels(0)
                         .struct_(structname)
@@ -112,6 +144,7 @@ impl MockTrait {
         MockTrait {
             attrs: impl_.attrs,
             consts,
+            fakes,
             generics: impl_.generics,
             methods,
             ss_name,
@@ -127,9 +160,12 @@ impl MockTrait {
     /// # Arguments
     ///
     /// * `modname`:    Name of the parent struct's private module
+    /// * `builder`:    Name of the mock struct's builder, if it has one
     // Supplying modname is an unfortunately hack.  Ideally MockTrait
     // wouldn't need to know that.
-    pub fn trait_impl(&self, modname: &Ident) -> impl ToTokens {
+    pub fn trait_impl(&self, modname: &Ident, builder: Option<&Ident>)
+        -> impl ToTokens
+    {
         let trait_impl_attrs = &self.attrs;
         let impl_attrs = AttrFormatter::new(&self.attrs)
             .async_trait(false)
@@ -137,14 +173,18 @@ impl MockTrait {
             .format();
         let (ig, _tg, wc) = self.generics.split_for_impl();
         let consts = &self.consts;
+        let fakes = &self.fakes;
         let path_args = &self.self_path.arguments;
         let calls = self.methods.iter()
                 .map(|meth| meth.call(Some(modname)))
                 .collect::<Vec<_>>();
         let contexts = self.methods.iter()
             .filter(|meth| meth.is_static())
-            .map(|meth| meth.context_fn(Some(modname)))
-            .collect::<Vec<_>>();
+            .map(|meth| {
+                let context_fn = meth.context_fn(Some(modname));
+                let checkpoint_fn = meth.checkpoint_fn(Some(modname));
+                quote!(#context_fn #checkpoint_fn)
+            }).collect::<Vec<_>>();
         let expects = self.methods.iter()
             .filter(|meth| !meth.is_static())
             .map(|meth| {
@@ -155,6 +195,21 @@ impl MockTrait {
                     meth.expect(modname, Some(path_args))
                 }
             }).collect::<Vec<_>>();
+        let builder_impl = builder.map(|builder| {
+            let steps = self.methods.iter()
+                .map(|meth| if meth.is_method_generic() {
+                    meth.builder_step(modname, None)
+                } else {
+                    meth.builder_step(modname, Some(path_args))
+                }).collect::<Vec<_>>();
+            quote!(
+                #(#impl_attrs)*
+                #[allow(non_snake_case)]
+                impl #ig #builder #path_args #wc {
+                    #(#steps)*
+                }
+            )
+        });
         let trait_path = &self.trait_path;
         let self_path = &self.self_path;
         let types = &self.types;
@@ -164,13 +219,16 @@ impl MockTrait {
             #unsafety impl #ig #trait_path for #self_path #wc {
                 #(#consts)*
                 #(#types)*
+                #(#fakes)*
                 #(#calls)*
             }
             #(#impl_attrs)*
+            #[allow(non_snake_case)]
             impl #ig #self_path #wc {
                 #(#expects)*
                 #(#contexts)*
             }
+            #builder_impl
         )
     }
 }
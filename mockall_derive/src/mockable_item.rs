@@ -1,9 +1,13 @@
 // vim: tw=80
 use super::*;
+use syn::parse::{Parse, ParseStream};
+
+use crate::mockable_struct::add_lifetime_parameters;
 
 /// Performs transformations on a function to make it mockable
 fn mockable_fn(mut item_fn: ItemFn) -> ItemFn {
     demutify(&mut item_fn.sig.inputs);
+    add_lifetime_parameters(&mut item_fn.sig);
     deimplify(&mut item_fn.sig.output);
     item_fn
 }
@@ -22,23 +26,53 @@ fn mockable_item(item: Item) -> Item {
 /// but with minor alterations that make it suitable for mocking, such as
 /// altered lifetimes.
 pub(crate) enum MockableItem {
+    /// A type alias for a trait object, like `type Db = dyn Database + Send;`
+    Alias(ItemType),
+    /// Standalone functions, mocked with [`mock!`] outside of any struct
+    Functions(MockableFunctions),
     Module(MockableModule),
     Struct(MockableStruct)
 }
 
+impl Parse for MockableItem {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let fork = input.fork();
+        fork.call(Attribute::parse_outer)?;
+        fork.parse::<Visibility>()?;
+        if fork.peek(Token![fn]) || fork.peek(Token![async]) ||
+            fork.peek(Token![unsafe]) || fork.peek(Token![extern])
+        {
+            input.parse().map(MockableItem::Functions)
+        } else {
+            input.parse().map(MockableItem::Struct)
+        }
+    }
+}
+
 impl From<(Attrs, Item)> for MockableItem {
     fn from((attrs, item): (Attrs, Item)) -> MockableItem {
+        if !matches!(item, Item::Impl(_) | Item::Trait(_)) {
+            if let Some(name) = &attrs.name {
+                compile_error(name.span(),
+                    "name can only be used when mocking traits and structs");
+            }
+            if let Some(vis) = &attrs.expect_vis {
+                compile_error(vis.span(),
+                    "expect_vis can only be used when mocking traits and structs");
+            }
+        }
         match item {
             Item::Impl(item_impl) =>
-                MockableItem::Struct(MockableStruct::from(item_impl)),
+                MockableItem::Struct(MockableStruct::from((attrs, item_impl))),
             Item::ForeignMod(item_foreign_mod) =>
                 MockableItem::Module(
                     MockableModule::from((attrs, item_foreign_mod))
                 ),
             Item::Mod(item_mod) =>
-                MockableItem::Module(MockableModule::from(item_mod)),
+                MockableItem::Module(MockableModule::from((attrs, item_mod))),
             Item::Trait(trait_) =>
                 MockableItem::Struct(MockableStruct::from((attrs, trait_))),
+            Item::Type(item_type) => MockableItem::Alias(item_type),
             _ => panic!("automock does not support this item type")
         }
     }
@@ -50,22 +84,52 @@ impl From<MockableStruct> for MockableItem {
     }
 }
 
+/// Standalone functions, like `fn foo(x: u32) -> u32;`
+pub(crate) struct MockableFunctions(pub Vec<ForeignItemFn>);
+
+impl Parse for MockableFunctions {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let mut fns = Vec::new();
+        while !input.is_empty() {
+            let attrs = input.call(Attribute::parse_outer)?;
+            let vis: Visibility = input.parse()?;
+            let mut sig: Signature = input.parse()?;
+            let semi_token: Token![;] = input.parse()?;
+            demutify(&mut sig.inputs);
+            add_lifetime_parameters(&mut sig);
+            deimplify(&mut sig.output);
+            fns.push(ForeignItemFn{attrs, vis, sig, semi_token});
+        }
+        Ok(MockableFunctions(fns))
+    }
+}
+
 pub(crate) struct MockableModule {
     pub attrs: TokenStream,
     pub vis: Visibility,
     pub mock_ident: Ident,
     /// Ident of the original module, if any
     pub orig_ident: Option<Ident>,
-    pub content: Vec<Item>
+    pub content: Vec<Item>,
+    /// Export mock foreign functions under their original symbol names
+    pub no_mangle: bool,
+    /// Give each thread its own expectations for the mock functions
+    pub thread_local: bool
 }
 
 impl From<(Attrs, ItemForeignMod)> for MockableModule {
     fn from((attrs, foreign): (Attrs, ItemForeignMod)) -> MockableModule {
         let orig_ident = None;
-        let mock_ident = attrs.modname.expect(concat!(
-            "module name is required when mocking foreign functions,",
-            " like `#[automock(mod mock_ffi)]`"
-        ));
+        if attrs.no_mangle {
+            compile_error(foreign.span(),
+                "no_mangle requires wrapping the extern block in a module, like #[automock(no_mangle)] mod ffi { extern \"C\" { fn foo ... } }");
+        }
+        let mock_ident = attrs.modname.unwrap_or_else(|| {
+            compile_error(foreign.span(),
+                "automock on an extern block requires a module name, like #[automock(mod mock_ffi;)].  Better yet, wrap the extern block in a module, and automock that, like #[automock] mod ffi { extern \"C\" { fn foo ... } }");
+            format_ident!("mock_ffi")
+        });
+        let thread_local = attrs.thread_local;
         let vis = Visibility::Public(VisPublic{
             pub_token: <Token![pub]>::default()
         });
@@ -142,13 +206,15 @@ impl From<(Attrs, ItemForeignMod)> for MockableModule {
             vis,
             mock_ident,
             orig_ident,
-            content
+            content,
+            no_mangle: false,
+            thread_local
         }
     }
 }
 
-impl From<ItemMod> for MockableModule {
-    fn from(mod_: ItemMod) -> MockableModule {
+impl From<(Attrs, ItemMod)> for MockableModule {
+    fn from((attrs, mod_): (Attrs, ItemMod)) -> MockableModule {
         let span = mod_.span();
         let vis = mod_.vis;
         let mock_ident = format_ident!("mock_{}", mod_.ident);
@@ -167,7 +233,9 @@ impl From<ItemMod> for MockableModule {
             vis,
             mock_ident,
             orig_ident,
-            content
+            content,
+            no_mangle: attrs.no_mangle,
+            thread_local: attrs.thread_local
         }
     }
 }
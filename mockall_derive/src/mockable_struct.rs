@@ -1,9 +1,9 @@
 // vim: tw=80
 use super::*;
-use syn::parse::{Parse, ParseStream};
+use syn::parse::{Parse, ParseStream, discouraged::Speculative};
 
 /// Make any implicit lifetime parameters explicit
-fn add_lifetime_parameters(sig: &mut Signature) {
+pub(crate) fn add_lifetime_parameters(sig: &mut Signature) {
     fn add_to_trait_object(generics: &mut Generics, var: &Pat, to: &mut TypeTraitObject) {
         let mut has_lifetime = false;
         for bound in to.bounds.iter() {
@@ -35,7 +35,9 @@ fn add_lifetime_parameters(sig: &mut Signature) {
                     format_ident!("dont_care")
                 }
             };
-            let s = format!("'__mockall_{}", arg_ident);
+            // Use a distinct prefix so these can't collide with Mockall's
+            // internal lifetimes, like '__mockall_e
+            let s = format!("'__mockall_arg_{}", arg_ident);
             let span = Span::call_site();
             let lt = Lifetime::new(&s, span);
             to.bounds.push(TypeParamBound::Lifetime(lt.clone()));
@@ -92,6 +94,117 @@ fn add_lifetime_parameters(sig: &mut Signature) {
     }
 }
 
+/// Parse the shorthand `impl Clone for X;`, `impl DerefMut for X;`, `impl
+/// PartialEq<Rhs> for X;`, `impl Debug for X;`, or `impl Display for X;`, if
+/// present.
+///
+/// The first is equivalent to an impl block containing `fn clone(&self) ->
+/// Self;`, so `clone` gets an expectation like any other method.  The second
+/// is equivalent to an empty impl block, whose method will be supplied by
+/// [`fill_deref_methods`].  `PartialEq` gets `fn eq(&self, other: &Rhs) ->
+/// bool;`, where `Rhs` is `Self` if omitted or if it names the mocked struct.
+/// The formatting traits get a `fn fmt(&self) -> String;` method, which
+/// `MockTrait` will wrap in the real signature.  No other trait may omit its
+/// body.
+fn impl_shorthand(input: ParseStream) -> syn::parse::Result<Option<ItemImpl>>
+{
+    let fork = input.fork();
+    let header = (|| {
+        let attrs = fork.call(Attribute::parse_outer).ok()?;
+        fork.parse::<Token![impl]>().ok()?;
+        let mut generics: Generics = fork.parse().ok()?;
+        let path: Path = fork.parse().ok()?;
+        fork.parse::<Token![for]>().ok()?;
+        let self_ty: Type = fork.parse().ok()?;
+        generics.where_clause = fork.parse().ok()?;
+        let semi = fork.parse::<Token![;]>().ok()?;
+        Some((attrs, generics, path, self_ty, semi))
+    })();
+    // Anything else will be parsed as a normal item
+    let (attrs, generics, mut path, self_ty, semi) = match header {
+        Some(h) => h,
+        None => return Ok(None)
+    };
+    let seg = path.segments.last_mut().unwrap();
+    let body = if seg.ident == "Clone" {
+        quote!(fn clone(&self) -> Self;)
+    } else if seg.ident == "DerefMut" {
+        TokenStream::new()
+    } else if seg.ident == "PartialEq" {
+        let mut rhs = quote!(Self);
+        if let PathArguments::AngleBracketed(abga) = &mut seg.arguments {
+            if let Some(GenericArgument::Type(ty)) = abga.args.first_mut() {
+                // The struct's own name isn't a type inside of mock!
+                if *ty == self_ty {
+                    *ty = parse2(quote!(Self)).unwrap();
+                }
+                rhs = ty.to_token_stream();
+            }
+        }
+        quote!(fn eq(&self, other: &#rhs) -> bool;)
+    } else if FORMAT_TRAITS.iter().any(|t| seg.ident == t) {
+        quote!(fn fmt(&self) -> ::std::string::String;)
+    } else {
+        return Err(syn::Error::new(semi.span(),
+            "Only Clone, DerefMut, PartialEq, Debug, and Display may be mocked without an impl body"));
+    };
+    input.advance_to(&fork);
+    let wc = &generics.where_clause;
+    parse2(quote!(
+        #(#attrs)*
+        impl #generics #path for #self_ty #wc {
+            #body
+        }
+    )).map(Some)
+}
+
+/// Supply the methods of any `Deref` or `DerefMut` impls that omit them.
+///
+/// Their signatures are fully determined by the `Deref` impl's `Target` type,
+/// so `impl Deref for X { type Target = Inner; }` and `impl DerefMut for X {}`
+/// are enough to get `expect_deref` and `expect_deref_mut` methods.
+fn fill_deref_methods(impls: &mut [ItemImpl], name: &Ident,
+                      generics: &Generics) -> syn::parse::Result<()>
+{
+    fn is_trait(impl_: &ItemImpl, ident: &str) -> bool {
+        matches!(&impl_.trait_, Some((_, path, _))
+            if path.segments.last().unwrap().ident == ident)
+    }
+    fn has_method(impl_: &ItemImpl, ident: &str) -> bool {
+        impl_.items.iter().any(|item|
+            matches!(item, ImplItem::Method(iim) if iim.sig.ident == ident))
+    }
+
+    let target = impls.iter()
+        .filter(|impl_| is_trait(impl_, "Deref"))
+        .flat_map(|impl_| impl_.items.iter())
+        .find_map(|item| match item {
+            ImplItem::Type(iit) if iit.ident == "Target" => Some(iit.ty.clone()),
+            _ => None
+        });
+    for impl_ in impls.iter_mut() {
+        let meth = if is_trait(impl_, "Deref") && !has_method(impl_, "deref") {
+            quote!(fn deref(&self) -> &#target;)
+        } else if is_trait(impl_, "DerefMut") &&
+            !has_method(impl_, "deref_mut")
+        {
+            quote!(fn deref_mut(&mut self) -> &mut #target;)
+        } else {
+            continue;
+        };
+        if target.is_none() {
+            return Err(syn::Error::new(impl_.impl_token.span(),
+                "Mocking Deref or DerefMut without their methods requires a Deref impl with a Target type"));
+        }
+        let mut item: ImplItem = parse2(meth)?;
+        if let ImplItem::Method(ref mut iim) = item {
+            mockable_method(iim, name, generics);
+        }
+        impl_.items.push(item);
+    }
+    Ok(())
+}
+
 /// Generate a #[derive(Debug)] Attribute
 fn derive_debug() -> Attribute {
     Attribute {
@@ -103,8 +216,8 @@ fn derive_debug() -> Attribute {
     }
 }
 
-/// Add "Mock" to the front of the named type
-fn mock_ident_in_type(ty: &mut Type) {
+/// Replace the named type with the mock struct's name
+fn mock_ident_in_type(ty: &mut Type, name: &Ident) {
     match ty {
         Type::Path(type_path) => {
             if type_path.path.segments.len() != 1 {
@@ -113,7 +226,7 @@ fn mock_ident_in_type(ty: &mut Type) {
                 return;
             }
             let ident = &mut type_path.path.segments.last_mut().unwrap().ident;
-            *ident = gen_mock_ident(ident)
+            *ident = name.clone()
         },
         x => {
             compile_error(x.span(),
@@ -123,18 +236,82 @@ fn mock_ident_in_type(ty: &mut Type) {
 }
 
 /// Performs transformations on the ItemImpl to make it mockable
-fn mockable_item_impl(mut impl_: ItemImpl, name: &Ident, generics: &Generics)
+fn mockable_item_impl(impl_: ItemImpl, name: &Ident, generics: &Generics)
     -> ItemImpl
 {
-    mock_ident_in_type(&mut impl_.self_ty);
+    let mut impl_ = rename_impl_generics(impl_, generics);
+    mock_ident_in_type(&mut impl_.self_ty, name);
     for item in impl_.items.iter_mut() {
         if let ImplItem::Method(ref mut iim) = item {
-            mockable_method(iim, name, generics);
+            stubify(iim);
+            if !is_fake(iim) {
+                mockable_method(iim, name, generics);
+            }
         }
     }
     impl_
 }
 
+/// Rename an impl block's generic type parameters to match the names used by
+/// the struct definition.
+///
+/// For example, given `struct Foo<T>`, `impl<U> Bar for Foo<U> { type Item =
+/// U; }` becomes `impl<T> Bar for Foo<T> { type Item = T; }`.  The expectation
+/// storage is generated with the struct's names, so the impl must use them
+/// too.
+fn rename_impl_generics(impl_: ItemImpl, generics: &Generics) -> ItemImpl {
+    fn rename(ts: TokenStream, renames: &HashMap<Ident, Ident>) -> TokenStream
+    {
+        ts.into_iter()
+            .map(|tt| match tt {
+                TokenTree::Ident(ident) => {
+                    match renames.get(&ident) {
+                        Some(new) => {
+                            TokenTree::Ident(Ident::new(&new.to_string(),
+                                ident.span()))
+                        },
+                        None => TokenTree::Ident(ident)
+                    }
+                },
+                TokenTree::Group(group) => {
+                    let mut new = Group::new(group.delimiter(),
+                        rename(group.stream(), renames));
+                    new.set_span(group.span());
+                    TokenTree::Group(new)
+                },
+                tt => tt
+            }).collect()
+    }
+
+    let self_args = match impl_.self_ty.as_ref() {
+        Type::Path(tp) => match &tp.path.segments.last().unwrap().arguments {
+            PathArguments::AngleBracketed(abga) => abga.args.clone(),
+            _ => return impl_
+        },
+        _ => return impl_
+    };
+    let mut renames = HashMap::default();
+    for (arg, tp) in self_args.iter()
+        .filter(|ga| !matches!(ga, GenericArgument::Lifetime(_)))
+        .zip(generics.type_params())
+    {
+        if let GenericArgument::Type(Type::Path(arg_tp)) = arg {
+            if let Some(ident) = arg_tp.path.get_ident() {
+                let is_impl_param = impl_.generics.type_params()
+                    .any(|itp| itp.ident == *ident);
+                if is_impl_param && *ident != tp.ident {
+                    renames.insert(ident.clone(), tp.ident.clone());
+                }
+            }
+        }
+    }
+    if renames.is_empty() {
+        impl_
+    } else {
+        parse2(rename(impl_.into_token_stream(), &renames)).unwrap()
+    }
+}
+
 /// Performs transformations on the method to make it mockable
 fn mockable_method(meth: &mut ImplItemMethod, name: &Ident, generics: &Generics)
 {
@@ -147,7 +324,6 @@ fn mockable_method(meth: &mut ImplItemMethod, name: &Ident, generics: &Generics)
         deselfify(ty, name, generics);
         deanonymize(ty);
     }
-    sanity_check_sig(&meth.sig);
 }
 
 /// Performs transformations on the method to make it mockable
@@ -165,7 +341,6 @@ fn mockable_trait_method(
         deselfify(ty, name, generics);
         deanonymize(ty);
     }
-    sanity_check_sig(&meth.sig);
 }
 
 /// Generates a mockable item impl from a trait method definition
@@ -175,6 +350,11 @@ fn mockable_trait(trait_: ItemTrait, name: &Ident, generics: &Generics)
     let items = trait_.items.into_iter()
     .map(|ti| {
         match ti {
+            TraitItem::Method(tim) if tim.attrs.iter().any(is_stub) => {
+                let mut iim = tim2iim(tim, &Visibility::Inherited);
+                stubify(&mut iim);
+                ImplItem::Method(iim)
+            },
             TraitItem::Method(mut tim) => {
                 mockable_trait_method(&mut tim, name, generics);
                 ImplItem::Method(tim2iim(tim, &Visibility::Inherited))
@@ -220,21 +400,6 @@ fn mockable_trait(trait_: ItemTrait, name: &Ident, generics: &Generics)
     }
 }
 
-fn sanity_check_sig(sig: &Signature) {
-    for arg in sig.inputs.iter() {
-        if let FnArg::Typed(pt) = arg {
-            if let Type::ImplTrait(it) = pt.ty.as_ref() {
-                let bounds = &it.bounds;
-                let s = format!(
-                    "Mockall does not support \"impl trait\" in argument position.  Use \"T: {}\" instead",
-                    quote!(#bounds)
-                );
-                compile_error(it.span(), &s);
-            }
-        }
-    }
-}
-
 /// Converts a TraitItemConst into an ImplItemConst
 fn tic2iic(tic: TraitItemConst, vis: &syn::Visibility) -> ImplItemConst {
     let span = tic.span();
@@ -257,20 +422,61 @@ fn tic2iic(tic: TraitItemConst, vis: &syn::Visibility) -> ImplItemConst {
     }
 }
 
+/// Is this the `#[mockall::stub]` attribute?
+fn is_stub(attr: &Attribute) -> bool {
+    let segments = attr.path.segments.iter()
+        .map(|ps| ps.ident.to_string())
+        .collect::<Vec<_>>();
+    segments == ["stub"] || segments == ["mockall", "stub"]
+}
+
+/// Turn a method marked with `#[mockall::stub]` into a fake, whose body
+/// returns the attribute's argument, or `Default::default()` if it has none.
+/// Either way, the body probably ignores some of the method's arguments.
+fn stubify(meth: &mut ImplItemMethod) {
+    let attr = if let Some(i) = meth.attrs.iter().position(is_stub) {
+        meth.attrs.remove(i)
+    } else {
+        return;
+    };
+    let body = if attr.tokens.is_empty() {
+        quote!(::std::default::Default::default())
+    } else {
+        match attr.parse_args::<Expr>() {
+            Ok(expr) => expr.into_token_stream(),
+            Err(e) => e.to_compile_error()
+        }
+    };
+    meth.block = parse2(quote!({ #body })).unwrap();
+    meth.attrs.push(Attribute {
+        pound_token: <Token![#]>::default(),
+        style: AttrStyle::Outer,
+        bracket_token: token::Bracket::default(),
+        path: Path::from(format_ident!("allow")),
+        tokens: quote!((unused_variables))
+    });
+}
+
+/// A method body consisting of just a semicolon, the way syn parses a method
+/// without a body.
+fn bodiless_block() -> Block {
+    let semi = Item::Verbatim(quote!(;));
+    Block {
+        brace_token: token::Brace::default(),
+        stmts: vec![Stmt::Item(semi)]
+    }
+}
+
 /// Converts a TraitItemMethod into an ImplItemMethod
 fn tim2iim(m: syn::TraitItemMethod, vis: &syn::Visibility)
     -> syn::ImplItemMethod
 {
-    let empty_block = Block {
-        brace_token: token::Brace::default(),
-        stmts: Vec::new()
-    };
     syn::ImplItemMethod{
         attrs: m.attrs,
         vis: vis.clone(),
         defaultness: None,
         sig: m.sig,
-        block: empty_block
+        block: bodiless_block()
     }
 }
 
@@ -298,10 +504,17 @@ fn tit2iit(tit: TraitItemType, vis: &Visibility) -> ImplItemType {
 pub(crate) struct MockableStruct {
     pub attrs: Vec<Attribute>,
     pub consts: Vec<ImplItemConst>,
+    /// Visibility of the `expect_*` methods, if different from the mocked
+    /// methods'
+    pub expect_vis: Option<Visibility>,
+    /// Inherent methods with fixed implementations
+    pub fakes: Vec<ImplItemMethod>,
     pub generics: Generics,
     /// Inherent methods of the mockable struct
     pub methods: Vec<ImplItemMethod>,
     pub name: Ident,
+    /// Give each thread its own expectations for static methods
+    pub thread_local: bool,
     pub vis: Visibility,
     pub impls: Vec<ItemImpl>
 }
@@ -336,30 +549,37 @@ impl MockableStruct {
 impl From<(Attrs, ItemTrait)> for MockableStruct {
     fn from((attrs, item_trait): (Attrs, ItemTrait)) -> MockableStruct {
         let trait_ = attrs.substitute_trait(&item_trait);
+        let name = attrs.name.unwrap_or_else(|| gen_mock_ident(&trait_.ident));
+        let expect_vis = attrs.expect_vis;
+        let thread_local = attrs.thread_local;
         let mut attrs = trait_.attrs.clone();
         attrs.push(derive_debug());
         let vis = trait_.vis.clone();
-        let name = gen_mock_ident(&trait_.ident);
         let generics = trait_.generics.clone();
         let impls = vec![mockable_trait(trait_, &name, &generics)];
         MockableStruct {
             attrs,
             consts: Vec::new(),
+            expect_vis,
+            fakes: Vec::new(),
             vis,
             name,
             generics,
             methods: Vec::new(),
+            thread_local,
             impls
         }
     }
 }
 
-impl From<ItemImpl> for MockableStruct {
-    fn from(mut item_impl: ItemImpl) -> MockableStruct {
+impl From<(Attrs, ItemImpl)> for MockableStruct {
+    fn from((automock_attrs, mut item_impl): (Attrs, ItemImpl))
+        -> MockableStruct
+    {
         let name = match &*item_impl.self_ty {
             Type::Path(type_path) => {
                 let n = find_ident_from_path(&type_path.path).0;
-                gen_mock_ident(&n)
+                automock_attrs.name.unwrap_or_else(|| gen_mock_ident(&n))
             },
             x => {
                 compile_error(x.span(),
@@ -370,11 +590,18 @@ impl From<ItemImpl> for MockableStruct {
         let mut attrs = item_impl.attrs.clone();
         attrs.push(derive_debug());
         let mut consts = Vec::new();
+        let mut fakes = Vec::new();
         let generics = item_impl.generics.clone();
         let mut methods = Vec::new();
         let pub_token = Token![pub](Span::call_site());
         let vis = Visibility::Public(VisPublic{pub_token});
         let mut impls = Vec::new();
+        // automock mocks every method, so the real bodies don't matter
+        for item in item_impl.items.iter_mut() {
+            if let ImplItem::Method(iim) = item {
+                iim.block = bodiless_block();
+            }
+        }
         if let Some((bang, _path, _)) = &item_impl.trait_ {
             if bang.is_some() {
                 compile_error(bang.span(), "Unsupported by automock");
@@ -402,8 +629,14 @@ impl From<ItemImpl> for MockableStruct {
             for item in item_impl.items.into_iter() {
                 match item {
                     ImplItem::Method(mut meth) => {
-                        mockable_method(&mut meth, &name, &item_impl.generics);
-                        methods.push(meth)
+                        stubify(&mut meth);
+                        if is_fake(&meth) {
+                            fakes.push(meth);
+                        } else {
+                            mockable_method(&mut meth, &name,
+                                            &item_impl.generics);
+                            methods.push(meth)
+                        }
                     },
                     ImplItem::Const(iic) => consts.push(iic),
                     // Rust doesn't allow types in an inherent impl
@@ -415,9 +648,12 @@ impl From<ItemImpl> for MockableStruct {
         MockableStruct {
             attrs,
             consts,
+            expect_vis: automock_attrs.expect_vis,
+            fakes,
             generics,
             methods,
             name,
+            thread_local: automock_attrs.thread_local,
             vis,
             impls,
         }
@@ -430,19 +666,31 @@ impl Parse for MockableStruct {
         let vis: syn::Visibility = input.parse()?;
         let original_name: syn::Ident = input.parse()?;
         let mut generics: syn::Generics = input.parse()?;
+        // An optional rename clause, like `Foo<T> as FakeFoo`
+        let name = if input.peek(Token![as]) {
+            input.parse::<Token![as]>()?;
+            input.parse()?
+        } else {
+            gen_mock_ident(&original_name)
+        };
         let wc: Option<syn::WhereClause> = input.parse()?;
         generics.where_clause = wc;
-        let name = gen_mock_ident(&original_name);
         let impl_content;
         let _brace_token = braced!(impl_content in input);
         let mut consts = Vec::new();
+        let mut fakes = Vec::new();
         let mut methods = Vec::new();
         while !impl_content.is_empty() {
             let item: ImplItem = impl_content.parse()?;
             match item {
                 ImplItem::Method(mut iim) => {
-                    mockable_method(&mut iim, &name, &generics);
-                    methods.push(iim);
+                    stubify(&mut iim);
+                    if is_fake(&iim) {
+                        fakes.push(iim);
+                    } else {
+                        mockable_method(&mut iim, &name, &generics);
+                        methods.push(iim);
+                    }
                 },
                 ImplItem::Const(iic) => consts.push(iic),
                 _ => {
@@ -453,6 +701,10 @@ impl Parse for MockableStruct {
 
         let mut impls = Vec::new();
         while !input.is_empty() {
+            if let Some(ii) = impl_shorthand(input)? {
+                impls.push(mockable_item_impl(ii, &name, &generics));
+                continue;
+            }
             let item: Item = input.parse()?;
             match item {
                 Item::Trait(it) => {
@@ -472,14 +724,18 @@ impl Parse for MockableStruct {
                 _ => return Err(input.error("Unsupported in this context")),
             }
         }
+        fill_deref_methods(&mut impls, &name, &generics)?;
 
         Ok(
             MockableStruct {
                 attrs,
                 consts,
+                expect_vis: None,
+                fakes,
                 generics,
                 methods,
                 name,
+                thread_local: false,
                 vis,
                 impls
             }
@@ -501,7 +757,7 @@ mod add_lifetime_parameters {
         )).unwrap();
         add_lifetime_parameters(&mut meth.sig);
         assert_eq!(
-            quote!(fn foo<'__mockall_x>(&self, x: [&(dyn T + '__mockall_x); 1]);)
+            quote!(fn foo<'__mockall_arg_x>(&self, x: [&(dyn T + '__mockall_arg_x); 1]);)
                 .to_string(),
             quote!(#meth).to_string()
         );
@@ -526,7 +782,7 @@ mod add_lifetime_parameters {
         )).unwrap();
         add_lifetime_parameters(&mut meth.sig);
         assert_eq!(
-            quote!(fn foo<'__mockall_x>(&self, x: &(dyn T + '__mockall_x));)
+            quote!(fn foo<'__mockall_arg_x>(&self, x: &(dyn T + '__mockall_arg_x));)
                 .to_string(),
             quote!(#meth).to_string()
         );
@@ -539,7 +795,7 @@ mod add_lifetime_parameters {
         )).unwrap();
         add_lifetime_parameters(&mut meth.sig);
         assert_eq!(
-            quote!(fn foo<'__mockall_x>(&self, x: &[&(dyn T + '__mockall_x)]);)
+            quote!(fn foo<'__mockall_arg_x>(&self, x: &[&(dyn T + '__mockall_arg_x)]);)
                 .to_string(),
             quote!(#meth).to_string()
         );
@@ -552,7 +808,7 @@ mod add_lifetime_parameters {
         )).unwrap();
         add_lifetime_parameters(&mut meth.sig);
         assert_eq!(
-            quote!(fn foo<'__mockall_x>(&self, x: (&(dyn T + '__mockall_x), u32));)
+            quote!(fn foo<'__mockall_arg_x>(&self, x: (&(dyn T + '__mockall_arg_x), u32));)
                 .to_string(),
             quote!(#meth).to_string()
         );
@@ -577,7 +833,7 @@ mod add_lifetime_parameters {
         )).unwrap();
         add_lifetime_parameters(&mut meth.sig);
         assert_eq!(
-            quote!(fn foo<'__mockall_x>(&self, x: &(dyn T + '__mockall_x));)
+            quote!(fn foo<'__mockall_arg_x>(&self, x: &(dyn T + '__mockall_arg_x));)
                 .to_string(),
             quote!(#meth).to_string()
         );
@@ -609,16 +865,141 @@ mod add_lifetime_parameters {
 
 }
 
-mod sanity_check_sig {
+mod fill_deref_methods {
+    use super::*;
+
+    fn deref_items(ms: &MockableStruct) -> Vec<String> {
+        ms.impls.iter()
+            .flat_map(|impl_| impl_.items.iter())
+            .map(|item| quote!(#item).to_string())
+            .collect()
+    }
+
+    #[test]
+    fn deref() {
+        let ms: MockableStruct = parse2(quote!(
+            Foo {}
+            impl Deref for Foo {
+                type Target = u32;
+            }
+            impl DerefMut for Foo {}
+        )).unwrap();
+        let items = deref_items(&ms);
+        assert_eq!(3, items.len());
+        assert!(items.contains(&quote!(fn deref(&self) -> &u32;).to_string()));
+        assert!(items.contains(
+            &quote!(fn deref_mut(&mut self) -> &mut u32;).to_string()));
+    }
+
+    #[test]
+    fn explicit() {
+        let ms: MockableStruct = parse2(quote!(
+            Foo {}
+            impl Deref for Foo {
+                type Target = u32;
+                fn deref(&self) -> &u32;
+            }
+        )).unwrap();
+        assert_eq!(2, deref_items(&ms).len());
+    }
+
+    #[test]
+    fn no_target() {
+        let e = parse2::<MockableStruct>(quote!(
+            Foo {}
+            impl DerefMut for Foo;
+        )).err().unwrap();
+        assert!(e.to_string().contains("requires a Deref impl with a Target"));
+    }
+
+    #[test]
+    fn partial_eq() {
+        let ms: MockableStruct = parse2(quote!(
+            Foo {}
+            impl PartialEq for Foo;
+            impl PartialEq<u32> for Foo;
+            impl PartialEq<Foo> for Foo;
+        )).unwrap();
+        let traits = ms.impls.iter()
+            .map(|impl_| {
+                let path = &impl_.trait_.as_ref().unwrap().1;
+                quote!(#path).to_string()
+            }).collect::<Vec<_>>();
+        assert_eq!(traits, vec![
+            quote!(PartialEq).to_string(),
+            quote!(PartialEq<u32>).to_string(),
+            quote!(PartialEq<Self>).to_string()
+        ]);
+        let items = deref_items(&ms);
+        assert!(items[0].contains(&quote!(other: &MockFoo).to_string()));
+        assert!(items[1].contains(&quote!(other: &u32).to_string()));
+        assert!(items[2].contains(&quote!(other: &MockFoo).to_string()));
+    }
+
+    #[test]
+    fn other_shorthand() {
+        let e = parse2::<MockableStruct>(quote!(
+            Foo {}
+            impl Default for Foo;
+        )).err().unwrap();
+        assert_eq!(e.to_string(),
+            "Only Clone, DerefMut, PartialEq, Debug, and Display may be mocked without an impl body");
+    }
+}
+
+mod stubify {
     use super::*;
 
     #[test]
-    #[should_panic(expected = "Mockall does not support \"impl trait\" in argument position.  Use \"T: SomeTrait\" instead.")]
-    fn impl_trait() {
-        let meth: ImplItemMethod = parse2(quote!(
-            fn foo(&self, x: impl SomeTrait);
+    fn default() {
+        let mut meth: ImplItemMethod = parse2(quote!(
+            #[mockall::stub]
+            fn foo(&self) -> u32;
+        )).unwrap();
+        let expected: ImplItemMethod = parse2(quote!(
+            #[allow(unused_variables)]
+            fn foo(&self) -> u32 { ::std::default::Default::default() }
+        )).unwrap();
+        stubify(&mut meth);
+        assert_eq!(meth, expected);
+    }
+
+    #[test]
+    fn expression() {
+        let mut meth: ImplItemMethod = parse2(quote!(
+            #[doc = "foo"]
+            #[stub(x + 1)]
+            fn foo(&self, x: u32) -> u32;
+        )).unwrap();
+        let expected: ImplItemMethod = parse2(quote!(
+            #[doc = "foo"]
+            #[allow(unused_variables)]
+            fn foo(&self, x: u32) -> u32 { x + 1 }
+        )).unwrap();
+        stubify(&mut meth);
+        assert_eq!(meth, expected);
+    }
+
+    #[test]
+    fn not_stub() {
+        let mut meth: ImplItemMethod = parse2(quote!(
+            fn foo(&self) -> u32;
+        )).unwrap();
+        let expected = meth.clone();
+        stubify(&mut meth);
+        assert_eq!(meth, expected);
+    }
+
+    /// Another crate's attribute named `stub` isn't Mockall's
+    #[test]
+    fn other_stub() {
+        let mut meth: ImplItemMethod = parse2(quote!(
+            #[foo::stub]
+            fn foo(&self) -> u32;
         )).unwrap();
-        sanity_check_sig(&meth.sig);
+        let expected = meth.clone();
+        stubify(&mut meth);
+        assert_eq!(meth, expected);
     }
 }
 }
@@ -112,6 +112,13 @@ fn do_double(_attrs: TokenStream, input: TokenStream) -> TokenStream {
 ///     Bean
 /// };
 /// ```
+/// (though a group's `self`, which imports the module itself, is left alone),
+/// ```no_run
+/// # mod foo { pub mod bar { pub struct Baz{} } }
+/// # use mockall_double::double;
+/// #[double]
+/// use foo::bar::{self, Baz};
+/// ```
 /// type aliases,
 /// ```no_run
 /// # mod bar { pub struct Baz {} }
@@ -205,6 +212,11 @@ fn mock_usetree(mut orig: &mut UseTree) {
                 mock_usetree(ut);
             }
         },
+        UseTree::Name(UseName{ident}) |
+        UseTree::Rename(UseRename{ident, ..}) if ident == "self" => {
+            // The module itself, as in `use foo::{self, Bar}`.  It can't be
+            // doubled, because it's named by the parent path.
+        },
         UseTree::Name(un) => {
             *orig = UseTree::Rename(UseRename {
                 ident: mock_ident(&un.ident),
@@ -272,6 +284,18 @@ mod double {
         cmp("", code, expected);
     }
 
+    #[test]
+    fn group_with_self() {
+        let code = r#"use foo::bar::{self, Baz};"#;
+        let expected = r#"
+            #[cfg(not(test))]
+            use foo::bar::{self, Baz};
+            #[cfg(test)]
+            use foo::bar::{self, MockBaz as Baz};
+        "#;
+        cmp("", code, expected);
+    }
+
     #[test]
     fn module() {
         let code = r#"use foo::bar;"#;